@@ -0,0 +1,129 @@
+use rand::Rng;
+
+use crate::game::Game;
+use crate::warm_up::DayForm;
+
+// A beat at address before the computer swings, so its turn reads as one.
+const ADDRESS_SECS: f32 = 0.8;
+// A putter with no touch at all misjudges pace by up to this fraction and
+// the line by up to this many radians; better putters by proportionally less.
+const PUTT_PACE_SLOP: f32 = 0.6;
+const PUTT_LINE_SLOP: f32 = 0.08;
+// It picks up this many over par rather than hack on.
+const GIVE_UP_OVER_PAR: u32 = 4;
+
+#[derive(Clone, Copy)]
+pub struct AiSkill {
+    pub name: &'static str,
+    // Multiplies every club's dispersion, as the player's day form does.
+    pub dispersion: f32,
+    // 1.0 judges every putt like the caddie would; 0.0 has no feel at all.
+    pub putting: f32,
+}
+
+const PRESETS: [AiSkill; 3] = [
+    AiSkill {
+        name: "Easy",
+        dispersion: 2.6,
+        putting: 0.2,
+    },
+    AiSkill {
+        name: "Fair",
+        dispersion: 1.6,
+        putting: 0.6,
+    },
+    AiSkill {
+        name: "Tough",
+        dispersion: 0.9,
+        putting: 0.95,
+    },
+];
+
+impl Default for AiSkill {
+    fn default() -> Self {
+        PRESETS[1]
+    }
+}
+
+impl AiSkill {
+    // A preset by name, or a dispersion multiplier and putting quality
+    // given as "1.2,0.7".
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        if let Some(preset) = PRESETS
+            .into_iter()
+            .find(|preset| preset.name.eq_ignore_ascii_case(arg))
+        {
+            return Some(preset);
+        }
+        let (dispersion, putting) = arg.split_once(',')?;
+        Some(Self {
+            name: "Custom",
+            dispersion: dispersion.trim().parse::<f32>().ok()?.clamp(0.5, 3.0),
+            putting: putting.trim().parse::<f32>().ok()?.clamp(0.0, 1.0),
+        })
+    }
+}
+
+// A computer golfer playing the same hole as the player with its own ball.
+// It picks clubs and lines up the way the caddie does and swings with its
+// skill's dispersion and touch.
+pub struct AiPlayer {
+    pub skill: AiSkill,
+    pub game: Game,
+    address_secs: f32,
+}
+
+impl AiPlayer {
+    // `game` is a simulation of the player's hole with the ball on the tee.
+    pub fn new(skill: AiSkill, mut game: Game) -> Self {
+        game.day_form = Some(DayForm {
+            dispersion_mult: skill.dispersion,
+        });
+        Self {
+            skill,
+            game,
+            address_secs: 0.0,
+        }
+    }
+
+    pub fn is_moving(&self) -> bool {
+        !self.game.hole_done && !self.game.can_shoot()
+    }
+
+    pub fn picked_up(&self) -> bool {
+        !self.game.hole_done && self.game.strokes >= self.game.par + GIVE_UP_OVER_PAR
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.game.hole_done || self.picked_up()
+    }
+
+    pub fn update(&mut self, dt_secs: f32) {
+        self.game.update(dt_secs);
+    }
+
+    // Called each step while it's the computer's turn and its ball is at
+    // rest; it swings once it has stood over the ball a moment.
+    pub fn address(&mut self, dt_secs: f32) {
+        self.address_secs += dt_secs;
+        if self.address_secs >= ADDRESS_SECS {
+            self.address_secs = 0.0;
+            self.take_turn();
+        }
+    }
+
+    fn take_turn(&mut self) {
+        let mut rng = rand::thread_rng();
+        let slop = 1.0 - self.skill.putting;
+        let mut putt_power = self.game.line_up();
+        let aim_offset = match putt_power.as_mut() {
+            Some(power) => {
+                let pace = 1.0 + rng.gen_range(-1.0..=1.0) * slop * PUTT_PACE_SLOP;
+                *power = (*power * pace).clamp(0.0, 1.0);
+                rng.gen_range(-1.0..=1.0) * slop * PUTT_LINE_SLOP
+            }
+            None => 0.0,
+        };
+        self.game.strike(aim_offset, putt_power);
+    }
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::{ShotRecord, Surface};
+
+// What the titles are judged on, counted up hole by hole over a round.
+const LAG_MIN_FT: f32 = 30.0;
+const LAG_LEAVE_FT: f32 = 3.0;
+const WINDY_MPH: f32 = 12.0;
+const WIND_CLOSE_YD: f32 = 8.0;
+// A bunker shot this close to the pin is a chance at a sand save, and so is
+// the last shot from off the green at an up-and-down.
+const GREENSIDE_YD: f32 = 30.0;
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RoundTally {
+    pub holes: u32,
+    pub bunker_shots: u32,
+    pub long_lags: u32,
+    pub windy_stiffs: u32,
+    pub penalty_strokes: u32,
+    pub one_putts: u32,
+    pub over_par_holes: u32,
+    // The round's statistics, each against the holes that gave a chance at
+    // it: fairways from the tee of a par 4 or 5, greens in regulation on
+    // every hole, and saves from beside the green.
+    pub fairways_hit: u32,
+    pub fairway_chances: u32,
+    pub greens_in_regulation: u32,
+    pub putts: u32,
+    pub sand_saves: u32,
+    pub sand_chances: u32,
+    pub up_and_downs: u32,
+    pub up_and_down_chances: u32,
+}
+
+impl RoundTally {
+    // Each shot finished where the next one was played from, or in the cup.
+    pub fn of_hole(shots: &[ShotRecord], strokes: u32, par: u32, penalties: u32) -> Self {
+        let mut tally = Self {
+            holes: 1,
+            penalty_strokes: penalties,
+            over_par_holes: (strokes > par) as u32,
+            ..Self::default()
+        };
+        let left_yd = shots
+            .iter()
+            .skip(1)
+            .map(|shot| shot.to_pin_yd)
+            .chain(std::iter::once(0.0));
+        let mut putts = 0;
+        for (shot, left) in shots.iter().zip(left_yd) {
+            if shot.lie == Surface::Bunker {
+                tally.bunker_shots += 1;
+            }
+            if shot.putter && shot.lie == Surface::Green {
+                putts += 1;
+                if shot.to_pin_yd * 3.0 >= LAG_MIN_FT && left * 3.0 <= LAG_LEAVE_FT {
+                    tally.long_lags += 1;
+                }
+            } else if shot.wind_mph >= WINDY_MPH && left <= WIND_CLOSE_YD {
+                tally.windy_stiffs += 1;
+            }
+        }
+        tally.one_putts = (putts == 1) as u32;
+        tally.putts = putts;
+
+        // The tee shot is only in the fairway if the next is played from it
+        // with no penalty stroke in between.
+        if par >= 4 {
+            tally.fairway_chances = 1;
+            tally.fairways_hit = shots
+                .get(1)
+                .is_some_and(|next| next.lie == Surface::Fairway && next.stroke == 2)
+                as u32;
+        }
+        // Holing out from off the green counts as reaching it.
+        let on_green = shots
+            .iter()
+            .find(|shot| shot.lie == Surface::Green)
+            .map_or(strokes, |shot| shot.stroke - 1);
+        tally.greens_in_regulation = (on_green + 2 <= par) as u32;
+        let greenside = |shot: &&ShotRecord| shot.to_pin_yd <= GREENSIDE_YD;
+        if shots
+            .iter()
+            .filter(greenside)
+            .any(|shot| shot.lie == Surface::Bunker)
+        {
+            tally.sand_chances = 1;
+            tally.sand_saves = (strokes <= par) as u32;
+        }
+        let last_chip = shots.iter().rev().find(|shot| shot.lie != Surface::Green);
+        if let Some(chip) = last_chip.filter(greenside) {
+            if tally.greens_in_regulation == 0 {
+                tally.up_and_down_chances = 1;
+                tally.up_and_downs = (strokes - chip.stroke <= 1) as u32;
+            }
+        }
+        tally
+    }
+
+    pub fn add(&mut self, hole: RoundTally) {
+        self.holes += hole.holes;
+        self.bunker_shots += hole.bunker_shots;
+        self.long_lags += hole.long_lags;
+        self.windy_stiffs += hole.windy_stiffs;
+        self.penalty_strokes += hole.penalty_strokes;
+        self.one_putts += hole.one_putts;
+        self.over_par_holes += hole.over_par_holes;
+        self.fairways_hit += hole.fairways_hit;
+        self.fairway_chances += hole.fairway_chances;
+        self.greens_in_regulation += hole.greens_in_regulation;
+        self.putts += hole.putts;
+        self.sand_saves += hole.sand_saves;
+        self.sand_chances += hole.sand_chances;
+        self.up_and_downs += hole.up_and_downs;
+        self.up_and_down_chances += hole.up_and_down_chances;
+    }
+}
+
+pub struct Award {
+    pub key: &'static str,
+    pub title: &'static str,
+    pub why: &'static str,
+    earned: fn(&RoundTally) -> bool,
+}
+
+pub const AWARDS: [Award; 6] = [
+    Award {
+        key: "bunker_magnet",
+        title: "Bunker Magnet",
+        why: "three or more shots from the sand",
+        earned: |t| t.bunker_shots >= 3,
+    },
+    Award {
+        key: "lag_master",
+        title: "Lag Master",
+        why: "two lags from 30 ft to within 3 ft",
+        earned: |t| t.long_lags >= 2,
+    },
+    Award {
+        key: "wind_whisperer",
+        title: "Wind Whisperer",
+        why: "two shots stiff in a 12 mph wind",
+        earned: |t| t.windy_stiffs >= 2,
+    },
+    Award {
+        key: "splash_brother",
+        title: "Splash Brother",
+        why: "two or more penalty strokes",
+        earned: |t| t.penalty_strokes >= 2,
+    },
+    Award {
+        key: "flat_stick",
+        title: "Flat Stick",
+        why: "one-putted half the holes",
+        earned: |t| t.holes >= 3 && t.one_putts * 2 >= t.holes,
+    },
+    Award {
+        key: "bogey_free",
+        title: "Bogey Free",
+        why: "nothing over par all round",
+        earned: |t| t.holes >= 3 && t.over_par_holes == 0,
+    },
+];
+
+pub fn earned(tally: &RoundTally) -> Vec<&'static Award> {
+    AWARDS
+        .iter()
+        .filter(|award| (award.earned)(tally))
+        .collect()
+}
+
+pub fn find(key: &str) -> Option<&'static Award> {
+    AWARDS.iter().find(|award| award.key == key)
+}
@@ -0,0 +1,169 @@
+use rand::seq::index;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::CLUBS;
+
+pub const RANDOM_BAG_CLUBS: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Challenge {
+    None,
+    RandomBag,
+    SevenIronOnly,
+    NoDriver,
+    PutterAnywhere,
+}
+
+impl Challenge {
+    pub const ALL: [Challenge; 5] = [
+        Challenge::None,
+        Challenge::RandomBag,
+        Challenge::SevenIronOnly,
+        Challenge::NoDriver,
+        Challenge::PutterAnywhere,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Challenge::None => "Off",
+            Challenge::RandomBag => "Random bag",
+            Challenge::SevenIronOnly => "7 Iron only",
+            Challenge::NoDriver => "No driver",
+            Challenge::PutterAnywhere => "Putter from anywhere",
+        }
+    }
+
+    // Stable name for the stats file.
+    pub fn key(self) -> &'static str {
+        match self {
+            Challenge::None => "none",
+            Challenge::RandomBag => "random_bag",
+            Challenge::SevenIronOnly => "seven_iron_only",
+            Challenge::NoDriver => "no_driver",
+            Challenge::PutterAnywhere => "putter_anywhere",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn bag<R: Rng>(self, rng: &mut R) -> Bag {
+        match self {
+            Challenge::None => Bag::full(),
+            Challenge::RandomBag => Bag::random(rng, RANDOM_BAG_CLUBS),
+            Challenge::SevenIronOnly => Bag::only("7 Iron"),
+            Challenge::NoDriver => Bag::without("Driver"),
+            Challenge::PutterAnywhere => Bag {
+                putter_anywhere: true,
+                ..Bag::full()
+            },
+        }
+    }
+}
+
+// Which clubs may be played this hole. Club cycling and the caddie only ever
+// pick from what is allowed.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bag {
+    allowed: [bool; CLUBS.len()],
+    // Lets the caddie reach for the putter from any distance.
+    pub putter_anywhere: bool,
+}
+
+impl Bag {
+    pub fn full() -> Self {
+        Self {
+            allowed: [true; CLUBS.len()],
+            putter_anywhere: false,
+        }
+    }
+
+    // A single club from the long game, plus the putter.
+    pub fn only(name: &str) -> Self {
+        Self {
+            allowed: CLUBS.map(|club| club.putter || club.name == name),
+            putter_anywhere: false,
+        }
+    }
+
+    pub fn without(name: &str) -> Self {
+        Self {
+            allowed: CLUBS.map(|club| club.name != name),
+            putter_anywhere: false,
+        }
+    }
+
+    // `count` clubs drawn from the long game, plus the putter.
+    pub fn random<R: Rng>(rng: &mut R, count: usize) -> Self {
+        let long_game: Vec<usize> = (0..CLUBS.len()).filter(|&i| !CLUBS[i].putter).collect();
+        let mut allowed = CLUBS.map(|club| club.putter);
+        for pick in index::sample(rng, long_game.len(), count.min(long_game.len())) {
+            allowed[long_game[pick]] = true;
+        }
+        Self {
+            allowed,
+            putter_anywhere: false,
+        }
+    }
+
+    pub fn allows(&self, idx: usize) -> bool {
+        self.allowed.get(idx).copied().unwrap_or(false)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.allowed.iter().all(|a| *a)
+    }
+
+    pub fn rule_label(&self) -> Option<String> {
+        if !self.is_full() {
+            Some(self.label())
+        } else if self.putter_anywhere {
+            Some("full bag, putter from anywhere".to_string())
+        } else {
+            None
+        }
+    }
+
+    // Walks `delta` allowed clubs away from `from`, wrapping round the bag.
+    pub fn step(&self, from: usize, delta: i32) -> usize {
+        let len = CLUBS.len() as i32;
+        let mut idx = from as i32;
+        for _ in 0..delta.unsigned_abs() {
+            for _ in 0..len {
+                idx = (idx + delta.signum()).rem_euclid(len);
+                if self.allows(idx as usize) {
+                    break;
+                }
+            }
+        }
+        idx as usize
+    }
+
+    // The allowed club nearest to `idx`, preferring shorter clubs.
+    pub fn nearest(&self, idx: usize) -> usize {
+        if self.allows(idx) {
+            idx
+        } else {
+            self.step(idx, 1)
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let names: Vec<&str> = CLUBS
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.allows(*i))
+            .map(|(_, club)| club.name)
+            .collect();
+        names.join(", ")
+    }
+}
+
+impl Default for Bag {
+    fn default() -> Self {
+        Self::full()
+    }
+}
@@ -0,0 +1,79 @@
+use crate::game::{Game, CELL_ASPECT, HEIGHT, WIDTH, WORLD_HEIGHT};
+
+pub const GREEN_ZOOM: f32 = 2.0;
+
+#[derive(Clone, Copy)]
+pub struct Camera {
+    pub left: f32,
+    pub top: f32,
+    pub zoom: f32,
+}
+
+impl Camera {
+    // Long holes are shrunk to fit the playfield so the whole hole stays visible.
+    pub fn full(game: &Game) -> Self {
+        let zoom_x = WIDTH as f32 / game.layout.width as f32;
+        let zoom_y = WORLD_HEIGHT as f32 / game.layout.height as f32;
+        Self {
+            left: 0.0,
+            top: 0.0,
+            zoom: zoom_x.min(zoom_y).min(1.0),
+        }
+    }
+
+    pub fn green(game: &Game) -> Self {
+        let view_w = (WIDTH as f32 / GREEN_ZOOM) as i32;
+        let view_h = (WORLD_HEIGHT as f32 / GREEN_ZOOM) as i32;
+
+        let center_x = ((game.ball.x + game.hole.x) * 0.5).round() as i32;
+        let center_y = ((game.ball.y + game.hole.y) * 0.5).round() as i32;
+
+        let left = (center_x - view_w / 2).clamp(0, game.layout.width - view_w);
+        let top = (center_y - view_h / 2).clamp(0, game.layout.height - view_h);
+        Self {
+            left: left as f32,
+            top: top as f32,
+            zoom: GREEN_ZOOM,
+        }
+    }
+
+    pub fn for_view(game: &Game, zoomed: bool) -> Self {
+        if zoomed {
+            Self::green(game)
+        } else {
+            Self::full(game)
+        }
+    }
+
+    pub fn lerp(from: Camera, to: Camera, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        Self {
+            left: from.left + (to.left - from.left) * t,
+            top: from.top + (to.top - from.top) * t,
+            zoom: from.zoom + (to.zoom - from.zoom) * t,
+        }
+    }
+
+    pub fn screen_to_world(&self, sx: i32, sy: i32) -> (i32, i32) {
+        (
+            (self.left + sx as f32 / self.zoom).floor() as i32,
+            (self.top + (sy * CELL_ASPECT) as f32 / self.zoom).floor() as i32,
+        )
+    }
+
+    pub fn world_to_screen(&self, wx: f32, wy: f32) -> Option<(i32, i32)> {
+        let lx = wx - self.left;
+        let ly = wy - self.top;
+        if lx < 0.0 || ly < 0.0 {
+            return None;
+        }
+
+        let sx = (lx * self.zoom).round() as i32;
+        let sy = (ly * self.zoom / CELL_ASPECT as f32).round() as i32;
+        if sx < 0 || sy < 0 || sx >= WIDTH || sy >= HEIGHT {
+            None
+        } else {
+            Some((sx, sy))
+        }
+    }
+}
@@ -0,0 +1,92 @@
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+// Horizontal bar `width` cells long at full scale, using eighth-blocks for
+// the fractional tail so small differences stay visible.
+pub fn bar(value: f32, max: f32, width: usize) -> String {
+    let ratio = if max > 0.0 {
+        (value / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let eighths = (ratio * width as f32 * 8.0).round() as usize;
+    let full = eighths / 8;
+    let partial = eighths % 8;
+
+    let mut out = "█".repeat(full);
+    if partial > 0 {
+        out.push(PARTIAL_BLOCKS[partial]);
+    }
+    let used = full + usize::from(partial > 0);
+    out.push_str(&" ".repeat(width.saturating_sub(used)));
+    out
+}
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// One character per value, scaled between the smallest and largest value so
+// the shape of a trend reads at a glance.
+pub fn sparkline(values: &[f32]) -> String {
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    spark_levels(values, min, max)
+}
+
+// The same, on a fixed scale from zero to `max`, for readings whose size
+// matters more than their trend.
+pub fn sparkline_to(values: &[f32], max: f32) -> String {
+    spark_levels(values, 0.0, max)
+}
+
+fn spark_levels(values: &[f32], min: f32, max: f32) -> String {
+    let span = max - min;
+    values
+        .iter()
+        .map(|v| {
+            let level = if span > 0.0 {
+                ((v.clamp(min, max) - min) / span * (SPARK_LEVELS.len() - 1) as f32).round()
+                    as usize
+            } else {
+                SPARK_LEVELS.len() / 2
+            };
+            SPARK_LEVELS[level.min(SPARK_LEVELS.len() - 1)]
+        })
+        .collect()
+}
+
+// Plots points inside a square of +/- `extent` around the origin, positive y
+// pointing up. Cells hit more than once get a heavier mark.
+pub fn scatter(points: &[(f32, f32)], extent: f32, width: usize, height: usize) -> Vec<String> {
+    let (cx, cy) = (width / 2, height / 2);
+    let mut grid = vec![vec![' '; width]; height];
+    for (y, row) in grid.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            *cell = match (x == cx, y == cy) {
+                (true, true) => '┼',
+                (true, false) => '│',
+                (false, true) => '─',
+                _ => ' ',
+            };
+        }
+    }
+
+    let mut hits = vec![vec![0u32; width]; height];
+    for &(px, py) in points {
+        let col = ((px / extent + 1.0) * 0.5 * (width - 1) as f32).round();
+        let row = ((1.0 - py / extent) * 0.5 * (height - 1) as f32).round();
+        if col < 0.0 || row < 0.0 || col >= width as f32 || row >= height as f32 {
+            continue;
+        }
+        hits[row as usize][col as usize] += 1;
+    }
+    for (y, row) in hits.iter().enumerate() {
+        for (x, &count) in row.iter().enumerate() {
+            if count > 0 {
+                grid[y][x] = if count > 1 { '●' } else { '•' };
+            }
+        }
+    }
+
+    grid.into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect()
+}
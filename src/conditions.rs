@@ -0,0 +1,218 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::Surface;
+use crate::hole::GreenSpec;
+use crate::wind::Wind;
+
+// How the ground is playing, picked when the game starts: firm turf kicks
+// landing balls on and lets them run, soft turf holds them, and the green
+// speed scales every green's stimp. The green side of it is folded into each
+// hole's `GreenSpec` when the round starts, after the day's preset.
+#[derive(Clone, Copy, PartialEq)]
+pub struct GroundConditions {
+    // From 0 soaked to 1 baked; 0.5 plays the course as it is laid out.
+    pub green_firmness: f32,
+    pub fairway_firmness: f32,
+    pub green_speed: f32,
+}
+
+impl Default for GroundConditions {
+    fn default() -> Self {
+        Self {
+            green_firmness: 0.5,
+            fairway_firmness: 0.5,
+            green_speed: 1.0,
+        }
+    }
+}
+
+impl GroundConditions {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "firm" => Some(Self {
+                green_firmness: 0.85,
+                fairway_firmness: 0.85,
+                green_speed: 1.15,
+            }),
+            "soft" => Some(Self {
+                green_firmness: 0.15,
+                fairway_firmness: 0.15,
+                green_speed: 0.85,
+            }),
+            "normal" => Some(Self::default()),
+            _ => None,
+        }
+    }
+
+    pub fn green(self, base: GreenSpec) -> GreenSpec {
+        GreenSpec {
+            stimp: base.stimp * self.green_speed.max(0.25),
+            firmness: (base.firmness + self.green_firmness - 0.5).clamp(0.0, 1.0),
+            ..base
+        }
+    }
+
+    // Greens take theirs from `green`.
+    pub fn drag_mult(self, surface: Surface) -> f32 {
+        match surface {
+            Surface::Fairway => 1.0 - (self.fairway_firmness.clamp(0.0, 1.0) - 0.5) * 0.6,
+            _ => 1.0,
+        }
+    }
+
+    pub fn bounce_mult(self, surface: Surface) -> f32 {
+        match surface {
+            Surface::Fairway => 0.6 + self.fairway_firmness.clamp(0.0, 1.0) * 0.8,
+            _ => 1.0,
+        }
+    }
+
+    // How likely a ball landing on the surface comes up with mud on it: often
+    // on soaked turf, hardly ever on baked.
+    pub fn mud_chance(self, surface: Surface) -> f32 {
+        match surface {
+            Surface::Fringe | Surface::Fairway => {
+                (1.0 - self.fairway_firmness.clamp(0.0, 1.0)) * 0.3
+            }
+            Surface::Rough | Surface::HeavyRough => {
+                (1.0 - self.fairway_firmness.clamp(0.0, 1.0)) * 0.4
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn describe(self) -> String {
+        let firmness = |value: f32| {
+            if value > 0.65 {
+                "firm"
+            } else if value < 0.35 {
+                "soft"
+            } else {
+                "normal"
+            }
+        };
+        let pace = if self.green_speed > 1.05 {
+            "quick"
+        } else if self.green_speed < 0.95 {
+            "slow"
+        } else {
+            "true"
+        };
+        format!(
+            "{} greens, {} fairways, {} pace",
+            firmness(self.green_firmness),
+            firmness(self.fairway_firmness),
+            pace
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConditionPreset {
+    CalmMorning,
+    BreezyAfternoon,
+    MajorSunday,
+}
+
+impl ConditionPreset {
+    pub const ALL: [ConditionPreset; 3] = [
+        ConditionPreset::CalmMorning,
+        ConditionPreset::BreezyAfternoon,
+        ConditionPreset::MajorSunday,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ConditionPreset::CalmMorning => "Calm morning",
+            ConditionPreset::BreezyAfternoon => "Breezy afternoon",
+            ConditionPreset::MajorSunday => "Major Sunday",
+        }
+    }
+
+    pub fn badge(self) -> &'static str {
+        match self {
+            ConditionPreset::CalmMorning => "[CALM AM]",
+            ConditionPreset::BreezyAfternoon => "[BREEZY PM]",
+            ConditionPreset::MajorSunday => "[SUNDAY]",
+        }
+    }
+
+    // `None` means a fresh preset is drawn for every round.
+    pub fn pick<R: Rng>(choice: Option<Self>, rng: &mut R) -> Self {
+        choice.unwrap_or_else(|| Self::ALL[rng.gen_range(0..Self::ALL.len())])
+    }
+
+    pub fn next_choice(choice: Option<Self>) -> Option<Self> {
+        match choice {
+            None => Some(ConditionPreset::CalmMorning),
+            Some(ConditionPreset::CalmMorning) => Some(ConditionPreset::BreezyAfternoon),
+            Some(ConditionPreset::BreezyAfternoon) => Some(ConditionPreset::MajorSunday),
+            Some(ConditionPreset::MajorSunday) => None,
+        }
+    }
+
+    pub fn opening_wind<R: Rng>(self, rng: &mut R) -> Wind {
+        let heading = rng.gen_range(0.0..std::f32::consts::TAU);
+        let base = match self {
+            ConditionPreset::CalmMorning => 0.0,
+            ConditionPreset::BreezyAfternoon => rng.gen_range(0.0..0.35),
+            ConditionPreset::MajorSunday => rng.gen_range(0.0..0.2),
+        };
+        Wind::new(heading, base)
+    }
+
+    // How much a gust can add on top of the steady wind, as a share of it.
+    pub fn gustiness(self) -> f32 {
+        match self {
+            ConditionPreset::CalmMorning => 0.2,
+            ConditionPreset::BreezyAfternoon => 0.6,
+            ConditionPreset::MajorSunday => 0.4,
+        }
+    }
+
+    // How far the wind can wander between shots, and its ceiling.
+    pub fn wind_drift(self) -> f32 {
+        match self {
+            ConditionPreset::CalmMorning => 0.04,
+            ConditionPreset::BreezyAfternoon => 0.14,
+            ConditionPreset::MajorSunday => 0.1,
+        }
+    }
+
+    pub fn max_wind(self) -> f32 {
+        match self {
+            ConditionPreset::CalmMorning => 0.12,
+            ConditionPreset::BreezyAfternoon => 0.5,
+            ConditionPreset::MajorSunday => 0.4,
+        }
+    }
+
+    pub fn green(self, base: GreenSpec) -> GreenSpec {
+        match self {
+            ConditionPreset::CalmMorning => GreenSpec {
+                stimp: (base.stimp - 1.0).max(8.0),
+                firmness: (base.firmness - 0.15).max(0.2),
+                ..base
+            },
+            ConditionPreset::BreezyAfternoon => GreenSpec {
+                exposure: (base.exposure + 0.2).min(1.0),
+                ..base
+            },
+            ConditionPreset::MajorSunday => GreenSpec {
+                stimp: (base.stimp + 2.5).min(14.0),
+                firmness: base.firmness.max(0.8),
+                ..base
+            },
+        }
+    }
+
+    // Fraction of the green radius the pin sits away from the middle.
+    pub fn pin_tuck(self) -> f32 {
+        match self {
+            ConditionPreset::CalmMorning => 0.1,
+            ConditionPreset::BreezyAfternoon => 0.35,
+            ConditionPreset::MajorSunday => 0.7,
+        }
+    }
+}
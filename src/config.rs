@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::{Settings, Units};
+use crate::storage::{self, Schema};
+
+const CONFIG_FILE: &str = "config.toml";
+const SCHEMA: Schema = Schema {
+    name: "settings",
+    migrations: &[],
+};
+
+// How the player set the game up on the first launch, kept in `config.toml`
+// in the config directory. Flags on the command line still have the last
+// word for the session.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub left_handed: bool,
+    pub units: Units,
+    pub high_contrast: bool,
+    pub pull_back_putting: bool,
+    pub tutorial: bool,
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let Some(path) = config_path() else {
+            return Self::default();
+        };
+        let Some(text) = storage::read(&path, &SCHEMA).ok().flatten() else {
+            return Self::default();
+        };
+        toml::from_str(&text).unwrap_or_else(|err: toml::de::Error| {
+            storage::set_aside(&path, &SCHEMA, err.message());
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = config_path() else {
+            return Ok(());
+        };
+        let text = toml::to_string(self).map_err(std::io::Error::other)?;
+        storage::write(&path, &SCHEMA, &text)
+    }
+
+    // The defaults with these choices made.
+    pub fn settings(&self) -> Settings {
+        Settings {
+            left_handed: self.left_handed,
+            units: self.units,
+            high_contrast: self.high_contrast,
+            pull_back_putting: self.pull_back_putting,
+            tutorial: self.tutorial,
+            ..Settings::default()
+        }
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    storage::config_dir().map(|dir| dir.join(CONFIG_FILE))
+}
+
+// The tutorial is seen through once; `--tutorial` brings it back.
+pub fn finish_tutorial() -> std::io::Result<()> {
+    let mut config = Config::load();
+    config.tutorial = false;
+    config.save()
+}
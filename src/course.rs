@@ -0,0 +1,203 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::awards::RoundTally;
+use crate::hole::HoleLayout;
+use scorecard::Scorecard;
+
+pub mod loader;
+pub mod notes;
+pub mod pack;
+pub mod scorecard;
+
+pub struct CourseHole {
+    pub number: u32,
+    pub name: String,
+    pub layout: HoleLayout,
+}
+
+// A routed course: its holes, and the nines listing hole indices in the
+// order they are played. See `loader` for the file format.
+pub struct Course {
+    pub name: String,
+    pub holes: Vec<CourseHole>,
+    pub front: Vec<usize>,
+    pub back: Vec<usize>,
+    // Both nines start and finish at the clubhouse, so a round can begin on
+    // the 10th tee.
+    pub returning_nines: bool,
+    // What it was loaded by, so a saved round can load it again.
+    pub source: PathBuf,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StartOption {
+    FirstTee,
+    BackNineOnly,
+    // Start on this hole number and play all the way round back to it.
+    Shotgun(u32),
+}
+
+impl Course {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let installed = if path.exists() {
+            None
+        } else {
+            pack::load_installed(&path.to_string_lossy())
+        };
+        let course = installed.unwrap_or_else(|| loader::load(path))?;
+        Ok(Self {
+            source: path.to_path_buf(),
+            ..course
+        })
+    }
+
+    // Hole indices in the order they are played for the chosen start.
+    pub fn routing(&self, start: StartOption) -> Result<Vec<usize>, String> {
+        let full: Vec<usize> = self.front.iter().chain(&self.back).copied().collect();
+        match start {
+            StartOption::FirstTee => Ok(full),
+            StartOption::BackNineOnly if self.back.is_empty() => {
+                Err(format!("{} has no back nine", self.name))
+            }
+            StartOption::BackNineOnly if !self.returning_nines => Err(format!(
+                "{} doesn't return to the clubhouse after the front nine",
+                self.name
+            )),
+            StartOption::BackNineOnly => Ok(self.back.clone()),
+            StartOption::Shotgun(number) => {
+                let at = full
+                    .iter()
+                    .position(|&i| self.holes[i].number == number)
+                    .ok_or(format!("{} has no hole {}", self.name, number))?;
+                Ok(full[at..].iter().chain(&full[..at]).copied().collect())
+            }
+        }
+    }
+}
+
+// Progress through one round of a course: where we are in the routing and
+// what was scored on each hole so far.
+pub struct CourseRound {
+    pub course: Course,
+    pub start: StartOption,
+    pub order: Vec<usize>,
+    pub position: usize,
+    pub card: Scorecard,
+    pub tally: RoundTally,
+    // Play time on the hole in progress, and what each finished hole took.
+    pub hole_clock: f32,
+    pub hole_secs: Vec<Option<f32>>,
+}
+
+pub struct Pace {
+    pub holes_done: usize,
+    pub secs_per_hole: f32,
+    pub projected_secs: f32,
+}
+
+impl CourseRound {
+    pub fn new(course: Course, start: StartOption) -> Result<Self, String> {
+        let order = course.routing(start)?;
+        if order.is_empty() {
+            return Err(format!("{} has no holes to play", course.name));
+        }
+        let card = Scorecard::new(&course, order.clone());
+        let hole_secs = vec![None; course.holes.len()];
+        Ok(Self {
+            course,
+            start,
+            order,
+            position: 0,
+            card,
+            tally: RoundTally::default(),
+            hole_clock: 0.0,
+            hole_secs,
+        })
+    }
+
+    pub fn current_idx(&self) -> usize {
+        let last = self.order.len().saturating_sub(1);
+        self.order[self.position.min(last)]
+    }
+
+    pub fn current(&self) -> &CourseHole {
+        &self.course.holes[self.current_idx()]
+    }
+
+    pub fn next(&self) -> Option<&CourseHole> {
+        let idx = *self.order.get(self.position + 1)?;
+        self.course.holes.get(idx)
+    }
+
+    pub fn is_last_hole(&self) -> bool {
+        self.position + 1 >= self.order.len()
+    }
+
+    pub fn record(&mut self, strokes: u32) {
+        let idx = self.current_idx();
+        self.card.record(idx, strokes);
+        self.hole_secs[idx] = Some(self.hole_clock);
+    }
+
+    // Moves to the next hole, or starts the round over once it is complete.
+    pub fn advance(&mut self) {
+        if self.is_last_hole() {
+            self.position = 0;
+            self.card.clear();
+            self.tally = RoundTally::default();
+            self.hole_secs.iter_mut().for_each(|s| *s = None);
+        } else {
+            self.position += 1;
+        }
+        self.hole_clock = 0.0;
+    }
+
+    // How the round is going for time, once a hole has been finished. The
+    // projection assumes the holes left go at the same average.
+    pub fn pace(&self) -> Option<Pace> {
+        let times: Vec<f32> = self
+            .order
+            .iter()
+            .filter_map(|&i| self.hole_secs[i])
+            .collect();
+        if times.is_empty() {
+            return None;
+        }
+        let spent: f32 = times.iter().sum();
+        let secs_per_hole = spent / times.len() as f32;
+        Some(Pace {
+            holes_done: times.len(),
+            secs_per_hole,
+            projected_secs: spent + secs_per_hole * (self.order.len() - times.len()) as f32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn course(front: Vec<usize>, back: Vec<usize>) -> Course {
+        Course {
+            name: "Test".to_string(),
+            holes: vec![CourseHole {
+                number: 1,
+                name: String::new(),
+                layout: HoleLayout::classic(),
+            }],
+            front,
+            back,
+            returning_nines: false,
+            source: PathBuf::new(),
+        }
+    }
+
+    #[test]
+    fn a_round_needs_a_hole_to_play() {
+        assert!(CourseRound::new(course(Vec::new(), Vec::new()), StartOption::FirstTee).is_err());
+        let round = CourseRound::new(course(vec![0], Vec::new()), StartOption::FirstTee).unwrap();
+        assert_eq!(round.current_idx(), 0);
+    }
+}
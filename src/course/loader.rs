@@ -0,0 +1,447 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Deserialize;
+
+use super::{Course, CourseHole};
+use crate::game::{Vec2, DEFAULT_YARDS_PER_TILE, HEIGHT, WIDTH};
+use crate::hole::{
+    par_for_yardage, Bunker, Fairway, GreenContour, GreenSpec, HoleLayout, HoleTemplate, Pond,
+};
+
+const DEFAULT_GREEN_RADIUS: f32 = 2.6;
+const DEFAULT_FAIRWAY_HALF_WIDTH: f32 = 2.8;
+const DEFAULT_WAVE_PERIOD: f32 = 11.0;
+// Generated holes keep their shape across this range; past it the cup and
+// the putting green no longer fit the tiles they are drawn on.
+const YARDS_PER_TILE_RANGE: (f32, f32) = (2.0, 8.0);
+// Slower than this and a putt barely reaches the hole; quicker and it
+// never stops.
+const STIMP_RANGE: (f32, f32) = (6.0, 14.0);
+
+// A course description in TOML:
+//
+//   name = "Terminal Links"
+//   front = [1, 2, 3]
+//   back = [4, 5, 6]
+//   returning_nines = true
+//   yards_per_tile = 3.0     # the map's scale, 5 when left out
+//
+//   [[hole]]
+//   number = 1
+//   name = "The Opener"
+//   seed = 4021              # generated from this seed
+//
+//   [[hole]]
+//   number = 2
+//   builtin = "classic"      # the original hand-made hole
+//
+//   [[hole]]
+//   number = 3
+//   tee = [8, 12]            # or lay the hole out tile by tile
+//   pin = [60, 10]
+//   par = 3                  # from the yardage when left out
+//   green = { center = [60, 10], radius = 3.0, tilt = [0.0, 0.1], stimp = 11.5 }
+//   fairway = { half_width = 3.0, wave_amp = 1.5 }
+//   bunkers = [{ center = [55, 7], radius = 2.0 }]
+//   water = [{ center = [50, 10], radius = 2.5, drop = [44, 11] }]
+//
+// Laid-out holes are given in screen cells, columns across and rows down,
+// and play on a 72 x 24 map unless `size = [w, h]` says otherwise. Radii and
+// the fairway's `half_width` are true distances, counted in columns, so a
+// bunker of radius 4 spans 8 columns and 4 rows. Without a fairway table the
+// fairway runs straight from the tee to the green.
+// A ball in water without a `drop` is dropped back where it was played from.
+// A green's `tilt` is the downhill pull across it that putts break with, up
+// to about 0.2; greens are flat without one. Its `stimp` (6 to 14) sets the
+// pace and its `firmness` (0 for soft to 1 for firm) how approaches bounce;
+// left out they are 10 and 0.5.
+// A small `yards_per_tile` suits a short course drawn in detail and a large
+// one a championship layout, from 2 to 8; generated holes keep their yardage
+// and the size of their greens and hazards and grow or shrink on the map,
+// while laid-out holes keep their tiles and play shorter or longer.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CourseFile {
+    name: Option<String>,
+    front: Option<Vec<u32>>,
+    back: Option<Vec<u32>>,
+    #[serde(default)]
+    returning_nines: bool,
+    yards_per_tile: Option<f32>,
+    #[serde(default, rename = "hole")]
+    holes: Vec<HoleFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HoleFile {
+    number: u32,
+    name: Option<String>,
+    seed: Option<u64>,
+    builtin: Option<String>,
+    par: Option<u32>,
+    size: Option<[i32; 2]>,
+    tee: Option<[f32; 2]>,
+    pin: Option<[f32; 2]>,
+    green: Option<GreenFile>,
+    fairway: Option<FairwayFile>,
+    #[serde(default)]
+    bunkers: Vec<CircleFile>,
+    #[serde(default)]
+    water: Vec<PondFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GreenFile {
+    center: Option<[f32; 2]>,
+    radius: Option<f32>,
+    tilt: Option<[f32; 2]>,
+    stimp: Option<f32>,
+    firmness: Option<f32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CircleFile {
+    center: [f32; 2],
+    radius: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PondFile {
+    center: [f32; 2],
+    radius: f32,
+    drop: Option<[f32; 2]>,
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+struct FairwayFile {
+    start_x: Option<f32>,
+    end_x: Option<f32>,
+    base_y: Option<f32>,
+    slope: Option<f32>,
+    wave_amp: Option<f32>,
+    wave_period: Option<f32>,
+    half_width: Option<f32>,
+    width_growth: Option<f32>,
+}
+
+pub fn load(path: &Path) -> Result<Course, String> {
+    if let Some(course) = builtin_course(&path.to_string_lossy()) {
+        return Ok(course);
+    }
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("can't read course {}: {}", path.display(), err))?;
+    parse(&text).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+// Courses that ship with the game and can be named instead of a file.
+fn builtin_course(name: &str) -> Option<Course> {
+    match name {
+        "classic" => Some(Course {
+            name: "Terminal Classic".to_string(),
+            holes: vec![CourseHole {
+                number: 1,
+                name: "The Original".to_string(),
+                layout: HoleLayout::classic(),
+            }],
+            front: vec![0],
+            back: Vec::new(),
+            returning_nines: false,
+            source: PathBuf::new(),
+        }),
+        _ => None,
+    }
+}
+
+pub fn parse(text: &str) -> Result<Course, String> {
+    let file: CourseFile = toml::from_str(text).map_err(|err| err.to_string())?;
+    if file.holes.is_empty() {
+        return Err("a course needs at least one `[[hole]]`".to_string());
+    }
+    let scale = file.yards_per_tile.unwrap_or(DEFAULT_YARDS_PER_TILE);
+    let (min_scale, max_scale) = YARDS_PER_TILE_RANGE;
+    if !(min_scale..=max_scale).contains(&scale) {
+        return Err(format!(
+            "`yards_per_tile` must be between {} and {}",
+            min_scale, max_scale
+        ));
+    }
+    let holes = file
+        .holes
+        .into_iter()
+        .map(|hole| {
+            let number = hole.number;
+            build_hole(hole, scale).map_err(|err| format!("hole {}: {}", number, err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let index_of = |number: u32| {
+        holes.iter().position(|h| h.number == number).ok_or(format!(
+            "the nines name hole {}, which isn't defined",
+            number
+        ))
+    };
+    let front: Vec<usize> = match file.front {
+        Some(numbers) => numbers
+            .into_iter()
+            .map(index_of)
+            .collect::<Result<_, _>>()?,
+        None => (0..holes.len()).collect(),
+    };
+    let back = match file.back {
+        Some(numbers) => numbers
+            .into_iter()
+            .map(index_of)
+            .collect::<Result<_, _>>()?,
+        None => Vec::new(),
+    };
+    if front.is_empty() && back.is_empty() {
+        return Err("a course needs at least one hole in its routing".to_string());
+    }
+    Ok(Course {
+        name: file.name.unwrap_or_else(|| "Unnamed course".to_string()),
+        holes,
+        front,
+        back,
+        returning_nines: file.returning_nines,
+        source: PathBuf::new(),
+    })
+}
+
+fn build_hole(hole: HoleFile, scale: f32) -> Result<CourseHole, String> {
+    let laid_out = hole.tee.is_some() || hole.pin.is_some();
+    let name = hole
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("Hole {}", hole.number));
+    let layout = match (hole.seed, hole.builtin.as_deref(), laid_out) {
+        (Some(seed), None, false) => HoleLayout::random(&mut StdRng::seed_from_u64(seed), scale),
+        (None, Some("classic"), false) => HoleLayout {
+            yards_per_tile: scale,
+            ..HoleLayout::classic()
+        },
+        (None, Some(other), false) => return Err(format!("no built-in hole `{}`", other)),
+        (None, None, true) => lay_out(&hole, scale)?,
+        _ => return Err("give exactly one of `seed`, `builtin`, or `tee` and `pin`".to_string()),
+    };
+    Ok(CourseHole {
+        number: hole.number,
+        name,
+        layout,
+    })
+}
+
+fn lay_out(hole: &HoleFile, scale: f32) -> Result<HoleLayout, String> {
+    let point = |p: [f32; 2]| Vec2::new(p[0], p[1]);
+    let tee = point(hole.tee.ok_or("a laid-out hole needs a `tee`")?);
+    let pin = point(hole.pin.ok_or("a laid-out hole needs a `pin`")?);
+    let [width, height] = hole.size.unwrap_or([WIDTH, HEIGHT]);
+    if width < WIDTH || height < HEIGHT {
+        return Err(format!("`size` must be at least {} x {}", WIDTH, HEIGHT));
+    }
+    let on_map = |p: Vec2| p.x >= 1.0 && p.y >= 1.0 && p.x < width as f32 && p.y < height as f32;
+    if !on_map(tee) || !on_map(pin) {
+        return Err("the tee and pin must be on the map".to_string());
+    }
+    if (pin.x - tee.x).abs() < 1.0 {
+        return Err("the pin must be across the map from the tee".to_string());
+    }
+
+    let green = hole.green.as_ref();
+    let green_center = green.and_then(|g| g.center).map_or(pin, point);
+    let green_radius = green.and_then(|g| g.radius).unwrap_or(DEFAULT_GREEN_RADIUS);
+    let standard = GreenSpec::standard();
+    let stimp = green.and_then(|g| g.stimp).unwrap_or(standard.stimp);
+    let (min_stimp, max_stimp) = STIMP_RANGE;
+    if !(min_stimp..=max_stimp).contains(&stimp) {
+        return Err(format!(
+            "a green's `stimp` must be between {} and {}",
+            min_stimp, max_stimp
+        ));
+    }
+    let firmness = green.and_then(|g| g.firmness).unwrap_or(standard.firmness);
+    if !(0.0..=1.0).contains(&firmness) {
+        return Err("a green's `firmness` must be between 0 and 1".to_string());
+    }
+
+    // The default fairway is a straight band from the tee to the front of
+    // the green.
+    let f = hole.fairway.unwrap_or_default();
+    let slope = f.slope.unwrap_or((pin.y - tee.y) / (pin.x - tee.x));
+    let (near, far) = if tee.x < pin.x {
+        (tee.x, pin.x - green_radius)
+    } else {
+        (pin.x + green_radius, tee.x)
+    };
+    let fairway = Fairway {
+        start_x: f.start_x.unwrap_or(near),
+        end_x: f.end_x.unwrap_or(far),
+        base_y: f.base_y.unwrap_or(tee.y - slope * tee.x),
+        slope,
+        wave_amp: f.wave_amp.unwrap_or(0.0),
+        wave_period: f.wave_period.unwrap_or(DEFAULT_WAVE_PERIOD).max(0.1),
+        half_width: f.half_width.unwrap_or(DEFAULT_FAIRWAY_HALF_WIDTH),
+        width_growth: f.width_growth.unwrap_or(0.0),
+    };
+
+    let mut layout = HoleLayout::from_cells(HoleLayout {
+        width,
+        height,
+        tee,
+        pin,
+        green_center,
+        par: 0,
+        template: HoleTemplate::Standard,
+        green_radius,
+        green: GreenSpec {
+            stimp,
+            firmness,
+            ..standard
+        },
+        contour: GreenContour {
+            tilt: green.and_then(|g| g.tilt).map_or(Vec2::default(), point),
+            ..GreenContour::default()
+        },
+        fairway,
+        bunkers: hole
+            .bunkers
+            .iter()
+            .map(|b| Bunker {
+                center: point(b.center),
+                radius: b.radius,
+            })
+            .collect(),
+        ponds: hole
+            .water
+            .iter()
+            .map(|w| Pond {
+                center: point(w.center),
+                radius: w.radius,
+                drop: w.drop.map(point),
+            })
+            .collect(),
+        yards_per_tile: scale,
+    });
+    layout.par = hole
+        .par
+        .unwrap_or_else(|| par_for_yardage(layout.length_yd()));
+    Ok(layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Surface;
+
+    #[test]
+    fn holes_are_routed_in_the_order_the_nines_give() {
+        let course = parse(
+            "front = [2]\nback = [1]\n\
+             [[hole]]\nnumber = 1\nseed = 3\n\
+             [[hole]]\nnumber = 2\nbuiltin = \"classic\"\n",
+        )
+        .unwrap();
+        assert_eq!((course.front, course.back), (vec![1], vec![0]));
+    }
+
+    #[test]
+    fn bad_routing_is_rejected() {
+        let hole = "[[hole]]\nnumber = 1\nseed = 3\n";
+        assert!(parse(&format!("front = []\n{}", hole)).is_err());
+        assert!(parse(&format!("front = []\nback = []\n{}", hole)).is_err());
+        assert!(parse(&format!("front = [1, 4]\n{}", hole)).is_err());
+        assert!(parse("front = [1]\n").is_err());
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(parse("par = 72\n[[hole]]\nnumber = 1\nseed = 3\n").is_err());
+        assert!(parse("[[hole]]\nnumber = 1\nseed = 3\nlength = 400\n").is_err());
+        assert!(parse(
+            "[[hole]]\nnumber = 1\ntee = [8, 12]\npin = [60, 10]\n\
+             green = { radius = 3.0, slope = 0.1 }\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn the_scale_must_be_in_range() {
+        let hole = "[[hole]]\nnumber = 1\nseed = 3\n";
+        let (min, max) = YARDS_PER_TILE_RANGE;
+        for scale in [min - 0.5, max + 1.0, 0.0, -5.0] {
+            let text = format!("yards_per_tile = {:?}\n{}", scale, hole);
+            assert!(parse(&text).is_err(), "{} yards per tile", scale);
+        }
+        for scale in [min, DEFAULT_YARDS_PER_TILE, max] {
+            let text = format!("yards_per_tile = {:?}\n{}", scale, hole);
+            assert!(parse(&text).is_ok(), "{} yards per tile", scale);
+        }
+    }
+
+    #[test]
+    fn a_laid_out_green_keeps_its_pace_and_firmness() {
+        let hole = |green: &str| {
+            format!(
+                "[[hole]]\nnumber = 1\ntee = [8, 12]\npin = [60, 10]\ngreen = {{ {} }}\n",
+                green
+            )
+        };
+        let course = parse(&hole("stimp = 12.5, firmness = 0.8")).unwrap();
+        let spec = course.holes[0].layout.green;
+        assert_eq!((spec.stimp, spec.firmness), (12.5, 0.8));
+        let course = parse(&hole("radius = 3.0")).unwrap();
+        let spec = course.holes[0].layout.green;
+        let standard = GreenSpec::standard();
+        assert_eq!(
+            (spec.stimp, spec.firmness),
+            (standard.stimp, standard.firmness)
+        );
+        for green in [
+            "stimp = 3.0",
+            "stimp = 20.0",
+            "firmness = -0.1",
+            "firmness = 1.5",
+        ] {
+            assert!(parse(&hole(green)).is_err(), "{}", green);
+        }
+    }
+
+    #[test]
+    fn generated_holes_keep_their_shape_across_the_scale_range() {
+        let (min, max) = YARDS_PER_TILE_RANGE;
+        for scale in [min, max] {
+            for seed in 0..200 {
+                let text = format!(
+                    "yards_per_tile = {:?}\n[[hole]]\nnumber = 1\nseed = {}\n",
+                    scale, seed
+                );
+                let layout = &parse(&text).unwrap().holes[0].layout;
+                let tee = layout.surface(layout.tee.x as i32, layout.tee.y as i32);
+                let pin = layout.surface(layout.pin.x as i32, layout.pin.y as i32);
+                assert!(
+                    tee == Surface::Rough,
+                    "tee on {} at seed {}, {} yd/tile",
+                    tee.name(),
+                    seed,
+                    scale
+                );
+                assert!(pin == Surface::Green, "seed {}, {} yd/tile", seed, scale);
+                let green_yd = layout.green_radius * scale;
+                assert!(
+                    (11.5..15.5).contains(&green_yd),
+                    "{} yd green at seed {}, {} yd/tile",
+                    green_yd,
+                    seed,
+                    scale
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::storage::{self, Schema};
+
+const NOTES_FILE: &str = "course_notes.txt";
+const SCHEMA: Schema = Schema {
+    name: "course notes",
+    migrations: &[storage::unversioned],
+};
+pub const MAX_RATING: u8 = 5;
+
+// What the player thinks of one course: a rating out of five and a line of
+// notes for any hole, like a page of a yardage book.
+#[derive(Clone, Default)]
+pub struct CourseNotes {
+    pub rating: Option<u8>,
+    pub holes: BTreeMap<u32, String>,
+}
+
+// Notes on every course played, by course name, saved as
+//
+//   course Terminal Links
+//   rating 4
+//   note 7 aim left of the pot bunker
+#[derive(Default)]
+pub struct NoteBook {
+    courses: BTreeMap<String, CourseNotes>,
+    path: Option<PathBuf>,
+}
+
+impl NoteBook {
+    pub fn load() -> Self {
+        let path = storage::data_dir().map(|dir| dir.join(NOTES_FILE));
+        let mut book = Self {
+            path: path.clone(),
+            ..Self::default()
+        };
+        if let Some(text) = path.and_then(|p| storage::read(&p, &SCHEMA).ok().flatten()) {
+            book.parse(&text);
+        }
+        book
+    }
+
+    fn parse(&mut self, text: &str) {
+        let mut current: Option<String> = None;
+        for line in text.lines() {
+            let (key, rest) = line.split_once(' ').unwrap_or((line, ""));
+            if key == "course" {
+                current = Some(rest.to_string());
+                continue;
+            }
+            let Some(course) = current.as_ref() else {
+                continue;
+            };
+            let notes = self.courses.entry(course.clone()).or_default();
+            match key {
+                "rating" => {
+                    notes.rating = rest.parse().ok().filter(|n| (1..=MAX_RATING).contains(n));
+                }
+                "note" => {
+                    if let Some((hole, text)) = rest.split_once(' ') {
+                        if let Ok(hole) = hole.parse() {
+                            notes.holes.insert(hole, text.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut text = String::new();
+        for (course, notes) in &self.courses {
+            text.push_str(&format!("course {}\n", course));
+            if let Some(rating) = notes.rating {
+                text.push_str(&format!("rating {}\n", rating));
+            }
+            for (hole, note) in &notes.holes {
+                text.push_str(&format!("note {} {}\n", hole, note));
+            }
+        }
+        storage::write(path, &SCHEMA, &text)
+    }
+
+    pub fn course(&self, name: &str) -> Option<&CourseNotes> {
+        self.courses.get(name)
+    }
+
+    pub fn note(&self, course: &str, hole: u32) -> Option<&str> {
+        self.course(course)?.holes.get(&hole).map(String::as_str)
+    }
+
+    pub fn rate(&mut self, course: &str, rating: u8) {
+        let rating = rating.clamp(1, MAX_RATING);
+        self.courses.entry(course.to_string()).or_default().rating = Some(rating);
+    }
+
+    // An empty note rubs out whatever was written for the hole.
+    pub fn set_note(&mut self, course: &str, hole: u32, text: &str) {
+        let notes = self.courses.entry(course.to_string()).or_default();
+        let text = text.trim();
+        if text.is_empty() {
+            notes.holes.remove(&hole);
+        } else {
+            notes.holes.insert(hole, text.to_string());
+        }
+    }
+}
+
+pub fn stars(rating: Option<u8>) -> String {
+    let filled = rating.unwrap_or(0).min(MAX_RATING) as usize;
+    format!(
+        "{}{}",
+        "★".repeat(filled),
+        "☆".repeat(MAX_RATING as usize - filled)
+    )
+}
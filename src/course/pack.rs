@@ -0,0 +1,207 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::{loader, Course};
+use crate::storage;
+
+const PACKS_DIR: &str = "courses";
+const PACK_EXT: &str = "tgpack";
+const CHECKSUM_PREFIX: &str = "fnv1a64:";
+
+// A course pack bundles several courses into one `.tgpack` file, itself
+// TOML, with a manifest entry and checksum for each course:
+//
+//   name = "Terminal Sampler"
+//   version = "1.0"
+//
+//   [[course]]
+//   file = "links.toml"
+//   checksum = "fnv1a64:9f3c0e4b2a1d5e67"
+//   toml = '''
+//   name = "Terminal Links"
+//   ...
+//   '''
+//
+// The checksum is the 64-bit FNV-1a hash of the `toml` text in hex. Every
+// course is checked and parsed before a pack is installed, and again when
+// one of its courses is played with `--course=<pack>/<file>`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PackFile {
+    name: String,
+    version: Option<String>,
+    #[serde(rename = "course")]
+    courses: Vec<PackCourse>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PackCourse {
+    file: String,
+    checksum: String,
+    toml: String,
+}
+
+pub struct Pack {
+    pub name: String,
+    pub version: Option<String>,
+    // Where it is installed, which is also how its courses are named.
+    pub id: String,
+    pub courses: Vec<(String, Course)>,
+}
+
+impl Pack {
+    fn parse(text: &str, id: String) -> Result<Self, String> {
+        let file: PackFile = toml::from_str(text).map_err(|err| err.to_string())?;
+        if file.courses.is_empty() {
+            return Err("a pack needs at least one `[[course]]`".to_string());
+        }
+        let courses = file
+            .courses
+            .into_iter()
+            .map(|entry| {
+                let expected = format!("{}{:016x}", CHECKSUM_PREFIX, checksum(&entry.toml));
+                if entry.checksum != expected {
+                    return Err(format!(
+                        "{}: checksum mismatch (manifest says {}, contents are {})",
+                        entry.file, entry.checksum, expected
+                    ));
+                }
+                let course =
+                    loader::parse(&entry.toml).map_err(|err| format!("{}: {}", entry.file, err))?;
+                Ok((entry.file, course))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self {
+            name: file.name,
+            version: file.version,
+            id,
+            courses,
+        })
+    }
+
+    fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("can't read pack {}: {}", path.display(), err))?;
+        let id = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self::parse(&text, id).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+}
+
+pub fn checksum(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn packs_dir() -> Option<PathBuf> {
+    storage::data_dir().map(|dir| dir.join(PACKS_DIR))
+}
+
+// Checks every course in the pack, then copies it into the data folder under
+// an id made from its name.
+pub fn import(path: &Path) -> Result<Pack, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("can't read pack {}: {}", path.display(), err))?;
+    let parsed =
+        Pack::parse(&text, String::new()).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let id = pack_id(&parsed.name);
+    if id.is_empty() {
+        return Err(format!("{}: the pack needs a name", path.display()));
+    }
+    let dir = packs_dir().ok_or("no data folder to install packs into")?;
+    fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(dir.join(format!("{}.{}", id, PACK_EXT)), &text))
+        .map_err(|err| format!("can't install pack: {}", err))?;
+    Ok(Pack { id, ..parsed })
+}
+
+fn pack_id(name: &str) -> String {
+    let id: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    id.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// Installed packs in name order; ones that no longer check out are left out.
+pub fn installed() -> Vec<Pack> {
+    let Some(entries) = packs_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut packs: Vec<Pack> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == PACK_EXT))
+        .filter_map(|path| Pack::load(&path).ok())
+        .collect();
+    packs.sort_by(|a, b| a.name.cmp(&b.name));
+    packs
+}
+
+// `<pack>/<file>` names a course in an installed pack; `None` when there is
+// no such pack, so the name can be tried as a path instead.
+pub fn load_installed(name: &str) -> Option<Result<Course, String>> {
+    let (id, file) = name.split_once('/')?;
+    let path = packs_dir()?.join(format!("{}.{}", id, PACK_EXT));
+    if !path.is_file() {
+        return None;
+    }
+    let found = Pack::load(&path).and_then(|pack| {
+        pack.courses
+            .into_iter()
+            .find(|(name, _)| name == file)
+            .map(|(_, course)| course)
+            .ok_or(format!("pack `{}` has no course `{}`", id, file))
+    });
+    Some(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COURSE: &str = "name = \"Test Links\"\n[[hole]]\nnumber = 1\nseed = 3\n";
+
+    fn pack_text(checksum: &str) -> String {
+        format!(
+            "name = \"Test Pack\"\nversion = \"1.0\"\n\n[[course]]\nfile = \"links.toml\"\n\
+             checksum = \"{}\"\ntoml = '''\n{}'''\n",
+            checksum, COURSE
+        )
+    }
+
+    #[test]
+    fn a_packed_course_reads_back() {
+        let text = pack_text(&format!("{}{:016x}", CHECKSUM_PREFIX, checksum(COURSE)));
+        let pack = Pack::parse(&text, pack_id("Test Pack")).unwrap();
+        assert_eq!(pack.id, "test-pack");
+        assert_eq!(pack.version.as_deref(), Some("1.0"));
+        assert_eq!(pack.courses.len(), 1);
+        assert_eq!(pack.courses[0].0, "links.toml");
+        assert_eq!(pack.courses[0].1.name, "Test Links");
+    }
+
+    #[test]
+    fn a_checksum_mismatch_is_rejected() {
+        let wrong = format!("{}{:016x}", CHECKSUM_PREFIX, checksum(COURSE) ^ 1);
+        let err = Pack::parse(&pack_text(&wrong), String::new())
+            .err()
+            .unwrap();
+        assert!(err.contains("checksum mismatch"), "{}", err);
+    }
+}
@@ -0,0 +1,86 @@
+use super::Course;
+
+#[derive(Clone)]
+pub struct ScoreLine {
+    pub number: u32,
+    pub par: u32,
+    pub strokes: Option<u32>,
+}
+
+// Strokes taken on each hole of a course, kept by the hole's place in the
+// course file, and the order the round plays them in.
+#[derive(Clone)]
+pub struct Scorecard {
+    pub course: String,
+    pub lines: Vec<ScoreLine>,
+    pub order: Vec<usize>,
+}
+
+impl Scorecard {
+    pub fn new(course: &Course, order: Vec<usize>) -> Self {
+        Self {
+            course: course.name.clone(),
+            lines: course
+                .holes
+                .iter()
+                .map(|hole| ScoreLine {
+                    number: hole.number,
+                    par: hole.layout.par,
+                    strokes: None,
+                })
+                .collect(),
+            order,
+        }
+    }
+
+    pub fn record(&mut self, idx: usize, strokes: u32) {
+        if let Some(line) = self.lines.get_mut(idx) {
+            line.strokes = Some(strokes);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.iter_mut().for_each(|line| line.strokes = None);
+    }
+
+    pub fn strokes(&self, idx: usize) -> Option<u32> {
+        self.lines.get(idx)?.strokes
+    }
+
+    pub fn holes_played(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| line.strokes.is_some())
+            .count()
+    }
+
+    // Strokes and par over the holes in `holes` that have a score.
+    pub fn played_total(&self, holes: &[usize]) -> (u32, u32) {
+        holes
+            .iter()
+            .filter_map(|&i| self.lines.get(i))
+            .filter_map(|line| line.strokes.map(|s| (s, line.par)))
+            .fold((0, 0), |(strokes, par), (s, p)| (strokes + s, par + p))
+    }
+
+    pub fn total(&self) -> (u32, u32) {
+        self.played_total(&self.order)
+    }
+
+    // The holes in playing order, each with where the round stood against
+    // par once it was played.
+    pub fn running(&self) -> Vec<(&ScoreLine, Option<i32>)> {
+        let mut to_par = 0;
+        self.order
+            .iter()
+            .filter_map(|&i| self.lines.get(i))
+            .map(|line| {
+                let standing = line.strokes.map(|s| {
+                    to_par += s as i32 - line.par as i32;
+                    to_par
+                });
+                (line, standing)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,42 @@
+use crate::game::Game;
+
+const BOT_ROUNDS: u32 = 24;
+// The bot never misreads a putt, so it scores well under par everywhere;
+// ratings and the balance band are measured against how it usually does.
+const RATINGS: [(f32, &str); 3] = [(-1.5, "Easy"), (-1.0, "Fair"), (-0.6, "Hard")];
+const BALANCED_TO_PAR: (f32, f32) = (-1.7, -0.7);
+pub const REGENERATE_ATTEMPTS: u32 = 6;
+
+#[derive(Clone, Copy)]
+pub struct HoleDifficulty {
+    pub average_strokes: f32,
+    pub par: u32,
+}
+
+impl HoleDifficulty {
+    // Plays the hole from the tee a couple of dozen times with the caddie
+    // choosing every shot.
+    pub fn estimate(game: &Game) -> Self {
+        let total: u32 = (0..BOT_ROUNDS).map(|_| game.bot_round()).sum();
+        Self {
+            average_strokes: total as f32 / BOT_ROUNDS as f32,
+            par: game.par,
+        }
+    }
+
+    pub fn to_par(self) -> f32 {
+        self.average_strokes - self.par as f32
+    }
+
+    pub fn rating(self) -> &'static str {
+        RATINGS
+            .iter()
+            .find(|(limit, _)| self.to_par() <= *limit)
+            .map_or("Brutal", |(_, name)| name)
+    }
+
+    pub fn is_outlier(self) -> bool {
+        let (easiest, hardest) = BALANCED_TO_PAR;
+        self.to_par() < easiest || self.to_par() > hardest
+    }
+}
@@ -0,0 +1,45 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use crate::game::Vec2;
+use crate::storage;
+
+const AUDIT_FILE: &str = "energy_audit.log";
+// Float error allowed in one substep, as a share of the ball's energy.
+const TOLERANCE: f32 = 1e-4;
+
+// A rolling ball only loses energy to drag, and only gains what the slope,
+// the wind and any backspin do work on it. Both are per unit of mass.
+pub struct Imbalance {
+    pub gained: f32,
+    pub work: f32,
+}
+
+// Checks one substep that took the ball from `before` to `after` under
+// `accel` for `step` seconds. Undamped, `before + accel * step` is where the
+// integrator would leave it, so its energy is the most the ball can have.
+pub fn audit(before: Vec2, after: Vec2, accel: Vec2, step: f32) -> Option<Imbalance> {
+    let energy = |v: Vec2| 0.5 * (v.x * v.x + v.y * v.y);
+    let undamped = Vec2::new(before.x + accel.x * step, before.y + accel.y * step);
+    let gained = energy(after) - energy(before);
+    let work = energy(undamped) - energy(before);
+    let slack = TOLERANCE * energy(before).max(1.0);
+    (gained > work + slack).then_some(Imbalance { gained, work })
+}
+
+// Appends a line to the audit log in the log directory.
+pub fn log(imbalance: &Imbalance, at: Vec2, surface: &str) -> std::io::Result<()> {
+    let Some(dir) = storage::log_dir() else {
+        return Ok(());
+    };
+    fs::create_dir_all(&dir)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(AUDIT_FILE))?;
+    writeln!(
+        file,
+        "gained {:.5} with {:.5} of work at ({:.2}, {:.2}) on {}",
+        imbalance.gained, imbalance.work, at.x, at.y, surface
+    )
+}
@@ -0,0 +1,97 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::game::{ClubSpec, ShotShape, ShotType, StrikeQuality, Surface, Vec2};
+use crate::terrain::TerrainMap;
+use crate::wind;
+
+const SAMPLES: usize = 64;
+const SEED: u64 = 0x666f_6f74;
+const WARN_SHARE: f32 = 0.2;
+
+// What the footprint was built for; aim and wind are left out on purpose so
+// turning or a gust only re-reads the map under the cached shape.
+#[derive(Clone, Copy, PartialEq)]
+pub struct FootprintKey {
+    pub club_idx: usize,
+    pub shot: ShotType,
+    pub shape: ShotShape,
+    pub lie: Surface,
+    pub buried: bool,
+}
+
+// Likely landing spots relative to the ball along an aim of zero: distance
+// forward and sideways, moved by the wind when they're laid over the map.
+pub struct Footprint {
+    pub key: FootprintKey,
+    offsets: Vec<(f32, f32)>,
+    carry_tiles: f32,
+}
+
+#[derive(Clone, Copy)]
+pub struct HazardWarning {
+    pub share: f32,
+    pub hazard: &'static str,
+}
+
+impl Footprint {
+    pub fn build(
+        key: FootprintKey,
+        club: ClubSpec,
+        carry_mult: f32,
+        dispersion: f32,
+        yards_per_tile: f32,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let offsets = (0..SAMPLES)
+            .map(|_| {
+                let roll: f32 = rng.gen_range(-1.0..1.0);
+                let strike = StrikeQuality::from_roll(roll);
+                let carry =
+                    club.carry_yd * key.shot.carry_mult() * strike.carry_mult() * carry_mult
+                        / yards_per_tile;
+                let angle = roll * dispersion;
+                let bend = carry * key.shape.swerve();
+                (
+                    carry * angle.cos() - bend * angle.sin(),
+                    carry * angle.sin() + bend * angle.cos(),
+                )
+            })
+            .collect();
+        Self {
+            key,
+            offsets,
+            carry_tiles: club.carry_yd / yards_per_tile,
+        }
+    }
+
+    // The hazards under the footprint for this aim, once they cover enough
+    // of the landing spots to be worth a warning. Any water names the lot.
+    pub fn hazard_warning(
+        &self,
+        terrain: &TerrainMap,
+        origin: Vec2,
+        angle: f32,
+        wind: Vec2,
+    ) -> Option<HazardWarning> {
+        let (cos, sin) = (angle.cos(), angle.sin());
+        let push = wind::carry_push(wind, Vec2::new(cos, sin), self.carry_tiles);
+        let in_hazard: Vec<Surface> = self
+            .offsets
+            .iter()
+            .filter_map(|(forward, side)| {
+                let x = origin.x + forward * cos - side * sin + push.x;
+                let y = origin.y + forward * sin + side * cos + push.y;
+                let tile = terrain.tile(x as i32, y as i32);
+                tile.hazard.then_some(tile.surface)
+            })
+            .collect();
+
+        let share = in_hazard.len() as f32 / self.offsets.len() as f32;
+        let wet = in_hazard.contains(&Surface::Water);
+        (share >= WARN_SHARE).then_some(HazardWarning {
+            share,
+            hazard: if wet { "water" } else { "sand" },
+        })
+    }
+}
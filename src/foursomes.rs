@@ -0,0 +1,35 @@
+pub const PARTNERS: [&str; 2] = ["Player 1", "Player 2"];
+
+// Two partners sharing one ball. They take turns to drive, one on the odd
+// holes and the other on the even ones, and alternate strokes from the tee
+// until the ball is in. Penalty strokes don't pass the turn.
+#[derive(Clone)]
+pub struct Foursomes {
+    // Holes finished, which settles who drives the next.
+    pub holes: u32,
+    // Strokes each partner has played over the round.
+    pub shots: [u32; 2],
+}
+
+impl Foursomes {
+    pub fn new() -> Self {
+        Self {
+            holes: 0,
+            shots: [0; 2],
+        }
+    }
+
+    pub fn on_tee(&self) -> usize {
+        (self.holes % 2) as usize
+    }
+
+    // The partner due to play once `played` strokes have been hit on the
+    // hole.
+    pub fn to_play(&self, played: u32) -> usize {
+        (self.on_tee() + played as usize) % 2
+    }
+
+    pub fn record_shot(&mut self, played: u32) {
+        self.shots[self.to_play(played)] += 1;
+    }
+}
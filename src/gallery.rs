@@ -0,0 +1,326 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::camera::Camera;
+use crate::game::{Game, Surface, HEIGHT, WIDTH};
+use crate::storage;
+
+const GALLERY_DIR: &str = "gallery";
+// Only the newest snapshots are kept, in memory and on disk.
+const GALLERY_KEEP: usize = 60;
+const NEAR_ACE_FT: f32 = 10.0;
+// Chip-ins from the fringe are too routine to keep.
+const HOLE_OUT_MIN_YD: f32 = 25.0;
+const DISASTER_OVER_PAR: u32 = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Moment {
+    HoleOut,
+    NearAce,
+    Disaster,
+}
+
+impl Moment {
+    pub fn name(self) -> &'static str {
+        match self {
+            Moment::HoleOut => "Hole-out",
+            Moment::NearAce => "Near ace",
+            Moment::Disaster => "Disaster",
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Moment::HoleOut => "hole_out",
+            Moment::NearAce => "near_ace",
+            Moment::Disaster => "disaster",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        [Moment::HoleOut, Moment::NearAce, Moment::Disaster]
+            .into_iter()
+            .find(|m| m.key() == key)
+    }
+
+    // Called once a shot has come to rest. Holing out from off the green
+    // beats the other two, so an ace is a hole-out rather than a near ace.
+    pub fn detect(game: &Game) -> Option<(Self, String)> {
+        let shot = game.shot_history.last()?;
+        if game.hole_done && shot.lie != Surface::Green && shot.to_pin_yd >= HOLE_OUT_MIN_YD {
+            let caption = if game.strokes == 1 {
+                format!("Ace! {} from {:.0} yd", shot.club, shot.to_pin_yd)
+            } else {
+                format!(
+                    "Holed a {} from {:.0} yd for a {} on a par {}",
+                    shot.club, shot.to_pin_yd, game.strokes, game.par
+                )
+            };
+            return Some((Moment::HoleOut, caption));
+        }
+        if game.hole_done && game.strokes >= game.par + DISASTER_OVER_PAR {
+            let caption = format!("Took {} on a par {}", game.strokes, game.par);
+            return Some((Moment::Disaster, caption));
+        }
+        let left_ft = game.distance_to_hole_yd() * 3.0;
+        if !game.hole_done && game.strokes == 1 && game.par == 3 && left_ft <= NEAR_ACE_FT {
+            let caption = format!(
+                "{} from {:.0} yd to {:.0} ft",
+                shot.club, shot.to_pin_yd, left_ft
+            );
+            return Some((Moment::NearAce, caption));
+        }
+        None
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Off,
+    Ground(Surface),
+    Trail,
+    Marker(u8),
+    Cup,
+    Ball,
+}
+
+impl Cell {
+    fn code(self) -> char {
+        match self {
+            Cell::Off => ' ',
+            Cell::Ground(Surface::Green) => 'g',
+            Cell::Ground(Surface::Fringe) => 'e',
+            Cell::Ground(Surface::Fairway) => 'f',
+            Cell::Ground(Surface::Rough) => 'r',
+            Cell::Ground(Surface::HeavyRough) => 'h',
+            Cell::Ground(Surface::Bunker) => 'b',
+            Cell::Ground(Surface::Water) => 'w',
+            Cell::Trail => '*',
+            Cell::Marker(n) => char::from_digit(n as u32, 10).unwrap_or('+'),
+            Cell::Cup => '@',
+            Cell::Ball => 'o',
+        }
+    }
+
+    fn from_code(code: char) -> Self {
+        match code {
+            'g' => Cell::Ground(Surface::Green),
+            'e' => Cell::Ground(Surface::Fringe),
+            'f' => Cell::Ground(Surface::Fairway),
+            'r' => Cell::Ground(Surface::Rough),
+            'h' => Cell::Ground(Surface::HeavyRough),
+            'b' => Cell::Ground(Surface::Bunker),
+            'w' => Cell::Ground(Surface::Water),
+            '*' => Cell::Trail,
+            '@' => Cell::Cup,
+            'o' => Cell::Ball,
+            c => c.to_digit(10).map_or(Cell::Off, |n| Cell::Marker(n as u8)),
+        }
+    }
+}
+
+// A frame of the full-hole view captured off screen: the ground, the stroke
+// markers, the ball's last roll and where it finished.
+pub struct Snapshot {
+    pub moment: Moment,
+    pub caption: String,
+    pub par: u32,
+    pub yards: f32,
+    pub conditions: String,
+    pub taken: u64,
+    pub frame: Vec<Vec<Cell>>,
+}
+
+impl Snapshot {
+    pub fn capture(game: &Game, moment: Moment, caption: String) -> Self {
+        let camera = Camera::full(game);
+        let mut frame: Vec<Vec<Cell>> = (0..HEIGHT)
+            .map(|sy| {
+                (0..WIDTH)
+                    .map(|sx| {
+                        let (wx, wy) = camera.screen_to_world(sx, sy);
+                        let inside =
+                            wx >= 0 && wy >= 0 && wx < game.layout.width && wy < game.layout.height;
+                        if inside {
+                            Cell::Ground(game.terrain.surface(wx, wy))
+                        } else {
+                            Cell::Off
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut mark = |x: f32, y: f32, cell: Cell| {
+            if let Some((sx, sy)) = camera.world_to_screen(x, y) {
+                frame[sy as usize][sx as usize] = cell;
+            }
+        };
+        for (i, shot) in game.shot_history.iter().enumerate().take(9) {
+            mark(shot.from.x, shot.from.y, Cell::Marker(i as u8 + 1));
+        }
+        for p in &game.trail {
+            mark(p.pos.x, p.pos.y, Cell::Trail);
+        }
+        mark(game.hole.x, game.hole.y, Cell::Cup);
+        if !game.hole_done {
+            mark(game.ball.x, game.ball.y, Cell::Ball);
+        }
+
+        Self {
+            moment,
+            caption,
+            par: game.par,
+            yards: game.layout.length_yd(),
+            conditions: game.conditions.name().to_string(),
+            taken: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            frame,
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!(
+            "moment {}\ncaption {}\npar {}\nyards {:.0}\nconditions {}\ntaken {}\nframe\n",
+            self.moment.key(),
+            self.caption,
+            self.par,
+            self.yards,
+            self.conditions,
+            self.taken
+        );
+        for row in &self.frame {
+            text.extend(row.iter().map(|c| c.code()));
+            text.push('\n');
+        }
+        text
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        let mut field = |key: &str| {
+            lines
+                .next()
+                .and_then(|line| line.strip_prefix(key))
+                .map(|rest| rest.trim_start().to_string())
+        };
+        let moment = Moment::from_key(&field("moment")?)?;
+        let caption = field("caption")?;
+        let par = field("par")?.parse().ok()?;
+        let yards = field("yards")?.parse().ok()?;
+        let conditions = field("conditions")?;
+        let taken = field("taken")?.parse().ok()?;
+        field("frame")?;
+        let frame = lines
+            .map(|line| line.chars().map(Cell::from_code).collect())
+            .collect();
+        Some(Self {
+            moment,
+            caption,
+            par,
+            yards,
+            conditions,
+            taken,
+            frame,
+        })
+    }
+}
+
+// Snapshots saved under the data directory, one file each, browsed newest
+// first.
+#[derive(Default)]
+pub struct Gallery {
+    pub shots: Vec<Snapshot>,
+    pub selected: usize,
+    dir: Option<PathBuf>,
+}
+
+impl Gallery {
+    pub fn load() -> Self {
+        let dir = storage::data_dir().map(|dir| dir.join(GALLERY_DIR));
+        let files = dir.as_deref().map(prune).unwrap_or_default();
+        let shots = files
+            .iter()
+            .rev()
+            .filter_map(|path| Snapshot::parse(&fs::read_to_string(path).ok()?))
+            .collect();
+        Self {
+            shots,
+            selected: 0,
+            dir,
+        }
+    }
+
+    pub fn add(&mut self, snapshot: Snapshot) -> std::io::Result<()> {
+        let result = match &self.dir {
+            Some(dir) => {
+                // Several moments can land in the same second.
+                let path = (0..)
+                    .map(|n| {
+                        dir.join(format!(
+                            "{}-{:02}-{}.txt",
+                            snapshot.taken,
+                            n,
+                            snapshot.moment.key()
+                        ))
+                    })
+                    .find(|path| !path.exists())
+                    .unwrap_or_default();
+                let result =
+                    fs::create_dir_all(dir).and_then(|_| fs::write(path, snapshot.to_text()));
+                prune(dir);
+                result
+            }
+            None => Ok(()),
+        };
+        self.shots.insert(0, snapshot);
+        self.shots.truncate(GALLERY_KEEP);
+        self.selected = 0;
+        result
+    }
+
+    pub fn current(&self) -> Option<&Snapshot> {
+        self.shots.get(self.selected)
+    }
+
+    pub fn browse(&mut self, delta: i32) {
+        let len = self.shots.len() as i32;
+        if len > 0 {
+            self.selected = (self.selected as i32 + delta).rem_euclid(len) as usize;
+        }
+    }
+}
+
+// Deletes all but the newest snapshot files and returns those left, oldest
+// first. Files are named by the time they were taken, so they sort by it.
+fn prune(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok().map(|e| e.path())).collect())
+        .unwrap_or_default();
+    files.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+    files.sort();
+    let excess = files.len().saturating_sub(GALLERY_KEEP);
+    for path in files.drain(..excess) {
+        let _ = fs::remove_file(path);
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_newest_snapshot_files_are_kept() {
+        let dir = storage::scratch_dir("gallery");
+        for taken in 0..GALLERY_KEEP + 5 {
+            fs::write(dir.join(format!("{}-00-ace.txt", 1_000 + taken)), "").unwrap();
+        }
+        let kept = prune(&dir);
+        assert_eq!(kept.len(), GALLERY_KEEP);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), GALLERY_KEEP);
+        assert!(!dir.join("1004-00-ace.txt").exists());
+        assert!(dir.join("1005-00-ace.txt").exists());
+    }
+}
@@ -1,17 +1,134 @@
 use std::f32::consts::PI;
+use std::rc::Rc;
 
 use crossterm::style::Color;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::ai::AiPlayer;
+use crate::awards::{self, Award, RoundTally};
+use crate::bag::{Bag, Challenge};
+use crate::conditions::{ConditionPreset, GroundConditions};
+use crate::config;
+use crate::course::notes::NoteBook;
+use crate::course::scorecard::Scorecard;
+use crate::course::CourseRound;
+use crate::difficulty::{HoleDifficulty, REGENERATE_ATTEMPTS};
+use crate::energy;
+use crate::footprint::{Footprint, FootprintKey, HazardWarning};
+use crate::foursomes::Foursomes;
+use crate::gallery::{Gallery, Moment, Snapshot};
+use crate::ghost::{GhostHole, GhostRace};
+use crate::heat_map::HeatMap;
+use crate::hole::HoleLayout;
+use crate::match_play::{HoleResult, MatchPlay, MATCH_HOLES};
+use crate::pressure::{PressureMeter, PressureResult, PRESSURE_PUTT_MAX_YD};
+use crate::profile::{Profile, RoundRecord};
+use crate::recap::HoleRecap;
+use crate::replay::{Replay, ReplayBuffer, ReplayFrame};
+use crate::rules::{self, Relief, ReliefChoice};
+use crate::save::SavedRound;
+use crate::settings::Settings;
+use crate::shadow::ShadowRival;
+use crate::shop;
+use crate::stats::{ApproachMiss, HoleSideStats};
+use crate::terrain::TerrainMap;
+use crate::tournament::Tournament;
+use crate::tutorial::TutorialStep;
+use crate::warm_up::{self, DayForm, WarmUp};
+use crate::wind::{self, Wind, WindHistory};
 
 pub const WIDTH: i32 = 72;
 pub const HEIGHT: i32 = 24;
+// A terminal cell is about twice as tall as it is wide, so a screen row
+// covers this many world tiles down where a column covers one across. The
+// world stays square; only the view is squeezed.
+pub const CELL_ASPECT: i32 = 2;
+// World tiles down the playfield at full size.
+pub const WORLD_HEIGHT: i32 = HEIGHT * CELL_ASPECT;
+// The default update step, and the one simulations always use.
 pub const TICK_MS: u64 = 33;
-pub const TRAIL_LEN: usize = 18;
+pub const TRAIL_LEN: usize = 96;
+// The trail takes a point this often in simulated time, in the air and on
+// the ground alike, however the frames happen to fall.
+const TRAIL_SAMPLE_SECS: f32 = 0.05;
 pub const AIM_STEP_RAD: f32 = 0.08;
-pub const YARDS_PER_TILE: f32 = 5.0;
+// Courses may set their own scale; random holes and the range use this.
+pub const DEFAULT_YARDS_PER_TILE: f32 = 5.0;
 pub const SWING_FRAMES: usize = 6;
-
-#[derive(Clone, Copy)]
+pub const LAYUP_TARGET_YD: f32 = 100.0;
+pub const AWKWARD_LEFTOVER_YD: f32 = 70.0;
+pub const AIM_PRESET_LIMIT: usize = 3;
+pub const CELEBRATION_SECS: f32 = 2.5;
+const AIR_HOLE_OUT_RADIUS: f32 = 0.5;
+// Landing on the green with more backspin than this checks the ball up; at
+// full spin it is pulled back this hard for this long.
+const SPIN_CHECK_MIN: f32 = 0.7;
+const SPIN_PULL: f32 = 3.0;
+const SPIN_GRIP_SECS: f32 = 0.8;
+// A landing ball hops at most this many times, and not at all once the hop
+// would be lower than this many tiles.
+const MAX_BOUNCES: u8 = 2;
+const MIN_BOUNCE_HEIGHT: f32 = 0.15;
+// A hop as high as the driver's apex stays up about as long as its flight.
+const BOUNCE_SECS_AT_APEX: f32 = 1.0;
+const DRIVER_APEX: f32 = 4.1;
+const AIR_HOLE_OUT_MAX_SPEED: f32 = 6.0;
+// A ball dropping into sand from the driver's apex plugs this often, less
+// from lower flights and never from below `BURIED_MIN_APEX`.
+const BURIED_CHANCE: f32 = 0.35;
+const BURIED_MIN_APEX: f32 = 2.0;
+// An embedded ball is dropped this far back from the hole, on the line from
+// it, when free relief is taken.
+const RELIEF_DROP_TILES: f32 = 0.6;
+// A mud ball swerves sideways by this share of its carry.
+const MUD_CURVE_MIN: f32 = 0.05;
+const MUD_CURVE_MAX: f32 = 0.14;
+// The share of its carry a worked shot bends off the line it started on.
+const SHAPE_SWERVE: f32 = 0.06;
+// Ground falling this steeply counts as a full uneven lie; below a quarter of
+// it the stance plays flat.
+const STANCE_FULL_SLOPE: f32 = 1.5;
+const STANCE_MIN: f32 = 0.25;
+const STANCE_PULL_RAD: f32 = 0.06;
+pub const FRINGE_MAX_YD: f32 = 30.0;
+const FRINGE_EDGE_TILES: f32 = 3.0;
+const FRINGE_SAMPLES: u32 = 16;
+const CADDIE_AIM_SWEEP: i32 = 10;
+const CADDIE_AIM_STEP_RAD: f32 = 0.04;
+const CADDIE_SAMPLES: u32 = 200;
+// A fixed seed keeps the odds steady while the player stands over the ball.
+const CADDIE_SAMPLE_SEED: u64 = 0x6361_6464;
+pub const WIND_ROLL_ACCEL: f32 = 1.2;
+pub const WIND_ROLL_THRESHOLD: f32 = 0.15;
+pub const REDUCED_MOTION_METER_SLOWDOWN: f32 = 2.5;
+pub const PULL_BACK_FULL_SECS: f32 = 1.4;
+pub const RESULT_HOLD_SECS: f32 = 1.6;
+pub const CAMERA_TRANSITION_SECS: f32 = 0.3;
+pub const AFK_GRACE_SECS: f32 = 15.0;
+pub const BROWSER_CANDIDATES: usize = 3;
+// A finished course hole walks on to the next tee after this long.
+pub const AUTO_ADVANCE_SECS: f32 = 4.0;
+// Long enough for a yardage book line, short enough for the HUD.
+const MAX_NOTE_CHARS: usize = 60;
+const BOT_GIVE_UP_OVER_PAR: u32 = 5;
+const BOT_MAX_STEPS_PER_SHOT: u32 = 2000;
+const BOT_PUTT_ARRIVE_RADIUS: f32 = 1.12;
+const BOT_PUTT_ARRIVE_SPEED: f32 = 1.2;
+// A rolling ball is stepped at least this often, and finely enough that no
+// substep carries it further than a fraction of the cup so it can't skip
+// over it.
+const ROLL_STEP_SECS: f32 = 0.016;
+const ROLL_STEP_MAX_TILES: f32 = 0.2;
+const ROLL_MAX_SUBSTEPS: u32 = 256;
+// A ball slow enough to stop stays put on a green tilted less than this.
+const GREEN_HOLD_SLOPE: f32 = 0.5;
+// Lines either side of the cup the caddie rolls a putt on to read the break.
+const PUTT_READ_LINES: i32 = 8;
+const PUTT_READ_STEP_RAD: f32 = 0.012;
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct Vec2 {
     pub x: f32,
     pub y: f32,
@@ -39,27 +156,64 @@ impl Vec2 {
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Surface {
     Green,
+    // The closely mown collar around a green.
+    Fringe,
     Fairway,
     Rough,
+    // Long grass well away from the short stuff.
+    HeavyRough,
     Bunker,
+    // The ball never rests here; it costs a stroke and a drop.
+    Water,
 }
 
 impl Surface {
-    pub fn drag_strength(self) -> f32 {
-        match self {
+    pub fn drag_strength(self, conditions: GroundConditions) -> f32 {
+        let base = match self {
             Surface::Green => 2.35,
+            Surface::Fringe => 3.0,
             Surface::Fairway => 2.0,
             Surface::Rough => 4.2,
+            Surface::HeavyRough => 6.5,
             Surface::Bunker => 9.0,
+            Surface::Water => 12.0,
+        };
+        base * conditions.drag_mult(self)
+    }
+
+    pub fn wind_coupling(self) -> f32 {
+        match self {
+            Surface::Green => 1.0,
+            Surface::Fringe => 0.7,
+            Surface::Fairway => 0.5,
+            Surface::Rough => 0.15,
+            Surface::HeavyRough => 0.05,
+            Surface::Bunker | Surface::Water => 0.0,
+        }
+    }
+
+    // How much of its height a landing ball keeps when it kicks back up, and
+    // how far forward each bit of that height carries it. Sand swallows it.
+    pub fn bounce(self) -> (f32, f32) {
+        match self {
+            Surface::Green => (0.18, 2.0),
+            Surface::Fringe => (0.2, 2.0),
+            Surface::Fairway => (0.3, 2.6),
+            Surface::Rough => (0.12, 1.5),
+            Surface::HeavyRough => (0.05, 1.0),
+            Surface::Bunker | Surface::Water => (0.0, 0.0),
         }
     }
 
     pub fn name(self) -> &'static str {
         match self {
             Surface::Green => "Green",
+            Surface::Fringe => "Fringe",
             Surface::Fairway => "Fairway",
             Surface::Rough => "Rough",
+            Surface::HeavyRough => "Heavy rough",
             Surface::Bunker => "Bunker",
+            Surface::Water => "Water",
         }
     }
 }
@@ -75,7 +229,7 @@ pub struct ClubSpec {
     pub putter: bool,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ShotType {
     Full,
     ThreeQuarter,
@@ -134,6 +288,136 @@ impl ShotType {
     }
 }
 
+// Which way a full swing is worked through the air. A draw bends left for a
+// right-hander and runs on landing; a fade bends right and sits down.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShotShape {
+    Straight,
+    Draw,
+    Fade,
+}
+
+impl ShotShape {
+    pub const ALL: [ShotShape; 3] = [ShotShape::Straight, ShotShape::Draw, ShotShape::Fade];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ShotShape::Straight => "Straight",
+            ShotShape::Draw => "Draw",
+            ShotShape::Fade => "Fade",
+        }
+    }
+
+    // How far the ball bends off the line by the time it lands, as a share
+    // of the carry; positive bends right.
+    pub fn swerve(self) -> f32 {
+        match self {
+            ShotShape::Straight => 0.0,
+            ShotShape::Draw => -SHAPE_SWERVE,
+            ShotShape::Fade => SHAPE_SWERVE,
+        }
+    }
+
+    pub fn roll_mult(self) -> f32 {
+        match self {
+            ShotShape::Straight => 1.0,
+            ShotShape::Draw => 1.15,
+            ShotShape::Fade => 0.85,
+        }
+    }
+}
+
+// How the club met the ball, read off the same draw that sets the dispersion so
+// the feel matches the result.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum StrikeQuality {
+    Flushed,
+    Solid,
+    Thin,
+    Heavy,
+}
+
+impl StrikeQuality {
+    // `roll` is the dispersion draw as a fraction of the club's spread.
+    pub fn from_roll(roll: f32) -> Self {
+        if roll.abs() < 0.2 {
+            StrikeQuality::Flushed
+        } else if roll.abs() < 0.7 {
+            StrikeQuality::Solid
+        } else if roll > 0.0 {
+            StrikeQuality::Thin
+        } else {
+            StrikeQuality::Heavy
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StrikeQuality::Flushed => "Flushed",
+            StrikeQuality::Solid => "Solid",
+            StrikeQuality::Thin => "Thin",
+            StrikeQuality::Heavy => "Heavy",
+        }
+    }
+
+    pub fn carry_mult(self) -> f32 {
+        match self {
+            StrikeQuality::Flushed => 1.03,
+            StrikeQuality::Solid => 1.0,
+            StrikeQuality::Thin => 0.93,
+            StrikeQuality::Heavy => 0.86,
+        }
+    }
+
+    pub fn roll_mult(self) -> f32 {
+        match self {
+            StrikeQuality::Flushed | StrikeQuality::Solid => 1.0,
+            StrikeQuality::Thin => 1.35,
+            StrikeQuality::Heavy => 0.7,
+        }
+    }
+
+    pub fn arc_mult(self) -> f32 {
+        match self {
+            StrikeQuality::Flushed | StrikeQuality::Solid => 1.0,
+            StrikeQuality::Thin => 0.65,
+            StrikeQuality::Heavy => 1.05,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ClubOption {
+    pub club_idx: usize,
+    pub shot: ShotType,
+    pub chance: f32,
+}
+
+#[derive(Clone, Copy)]
+pub struct FringeOption {
+    pub club_idx: usize,
+    pub shot: ShotType,
+    pub make_rate: f32,
+    pub leave_ft: f32,
+    pub finish: Vec2,
+}
+
+// Putting against chipping from just off the green on the current line,
+// played out with the real roll physics a handful of times each.
+#[derive(Clone, Copy)]
+pub struct FringeComparison {
+    pub putt: FringeOption,
+    pub chip: FringeOption,
+    angle: f32,
+}
+
+#[derive(Clone, Copy)]
+pub struct CaddieAdvice {
+    pub primary: ClubOption,
+    pub alternative: Option<ClubOption>,
+    pub going_for_green: bool,
+}
+
 pub const CLUBS: [ClubSpec; 16] = [
     ClubSpec {
         name: "Driver",
@@ -281,6 +565,174 @@ pub const CLUBS: [ClubSpec; 16] = [
     },
 ];
 
+#[derive(Clone, Copy)]
+pub struct ShotRecord {
+    pub from: Vec2,
+    pub lie: Surface,
+    pub buried: bool,
+    pub club: &'static str,
+    pub putter: bool,
+    pub to_pin_yd: f32,
+    pub wind_mph: f32,
+    // Which stroke on the hole it was, penalty strokes included.
+    pub stroke: u32,
+}
+
+// A few freshly generated holes, each already set up with its conditions,
+// to pick the next one from.
+pub struct HoleBrowser {
+    pub candidates: Vec<Game>,
+    pub selected: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum StatsPage {
+    Closed,
+    HoleSide,
+    Lifetime,
+    Scorecard,
+    Gallery,
+    ProShop,
+}
+
+// Holds the camera used for the shot on its result for a beat, so the view
+// doesn't cut to the green zoom while the outcome is still being shown.
+#[derive(Clone)]
+pub struct ShotPresentation {
+    pub zoomed: bool,
+    pub elapsed: f32,
+    pub summary: String,
+    // Where an approach came down, marked on the green next to the ball.
+    pub landing: Option<Vec2>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PenaltyKind {
+    Water,
+    OutOfBounds,
+    Unplayable,
+}
+
+impl PenaltyKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            PenaltyKind::Water => "Water",
+            PenaltyKind::OutOfBounds => "OB",
+            PenaltyKind::Unplayable => "Unplayable",
+        }
+    }
+}
+
+// What the latest shot cost, where it went in the water or crossed the
+// boundary, and how the player is going on from there.
+#[derive(Clone, Copy)]
+pub struct PenaltyDrop {
+    pub kind: PenaltyKind,
+    pub at: Vec2,
+    pub relief: Relief,
+}
+
+impl PenaltyDrop {
+    pub fn announcement(self) -> String {
+        format!("{} — {} (+1)", self.kind.name(), self.relief.action())
+    }
+}
+
+// Where the ball has been on its latest shot. Airborne points are where the
+// ball was drawn in the sky, not the ground under it.
+#[derive(Clone, Copy)]
+pub struct TrailPoint {
+    pub pos: Vec2,
+    pub airborne: bool,
+    // Rolling back against the line of the shot on its backspin.
+    pub spun_back: bool,
+}
+
+// Backspin grabbing a ball that lands on the green: for a moment it pulls
+// back against the line of the shot, enough to check the ball up or suck it
+// back toward where it landed.
+#[derive(Clone, Copy)]
+pub struct SpinCheck {
+    pub dir: Vec2,
+    pub pull: f32,
+    pub grip_secs: f32,
+}
+
+impl SpinCheck {
+    pub fn gripping(self) -> bool {
+        self.grip_secs > 0.0
+    }
+}
+
+// How the ground tilts under the player's feet for the shot being lined up,
+// each from -1 to 1: `uphill` against `downhill`, and the ball above the
+// feet against below them.
+#[derive(Clone, Copy, Default)]
+pub struct Stance {
+    pub uphill: f32,
+    pub ball_above: f32,
+}
+
+impl Stance {
+    // `downhill` is the way the ground falls. A right-hander stands to the
+    // left of the line, facing across it.
+    fn on(downhill: Vec2, angle: f32) -> Self {
+        let (along, across) = (
+            downhill.x * angle.cos() + downhill.y * angle.sin(),
+            downhill.y * angle.cos() - downhill.x * angle.sin(),
+        );
+        Self {
+            uphill: (-along / STANCE_FULL_SLOPE).clamp(-1.0, 1.0),
+            ball_above: (-across / STANCE_FULL_SLOPE).clamp(-1.0, 1.0),
+        }
+    }
+
+    fn is_flat(self) -> bool {
+        self.uphill.abs() < STANCE_MIN && self.ball_above.abs() < STANCE_MIN
+    }
+
+    // Ball above the feet pulls the shot left, below them pushes it right.
+    fn pull_rad(self) -> f32 {
+        -self.ball_above * STANCE_PULL_RAD
+    }
+
+    // Uphill adds loft: higher and shorter, and it sits down. Downhill takes
+    // it off: lower, a touch longer, and it runs.
+    fn carry_mult(self) -> f32 {
+        if self.uphill > 0.0 {
+            1.0 - 0.1 * self.uphill
+        } else {
+            1.0 - 0.03 * self.uphill
+        }
+    }
+
+    fn roll_mult(self) -> f32 {
+        1.0 - 0.35 * self.uphill
+    }
+
+    fn arc_mult(self) -> f32 {
+        1.0 + 0.25 * self.uphill
+    }
+
+    pub fn warning(self) -> Option<&'static str> {
+        let side = self.ball_above.abs() >= self.uphill.abs();
+        match (side, self.ball_above > 0.0, self.uphill > 0.0) {
+            _ if self.is_flat() => None,
+            (true, true, _) => Some("Ball above feet — expect a pull"),
+            (true, false, _) => Some("Ball below feet — expect a push"),
+            (false, _, true) => Some("Uphill lie — higher and shorter"),
+            (false, _, false) => Some("Downhill lie — lower, and it runs"),
+        }
+    }
+}
+
+// Where the ball is drawn: its spot on the ground and how far above it.
+#[derive(Clone, Copy, Default)]
+pub struct BallView {
+    pub ground: Vec2,
+    pub height: f32,
+}
+
 #[derive(Clone, Copy)]
 pub struct AirState {
     pub start: Vec2,
@@ -289,6 +741,13 @@ pub struct AirState {
     pub duration: f32,
     pub apex: f32,
     pub rollout_speed: f32,
+    // Backspin from 0 to 1, which keeps the hops low and short.
+    pub spin: f32,
+    // Zero for the shot's own flight, then one more for each hop after it.
+    pub bounces: u8,
+    // Tiles the ball swerves sideways by the time it lands, bending more and
+    // more as it flies; positive swerves right of the line.
+    pub curve: f32,
 }
 
 impl AirState {
@@ -298,271 +757,2226 @@ impl AirState {
 
     pub fn ground_pos(self) -> Vec2 {
         let t = self.progress();
+        let line = Vec2::new(self.landing.x - self.start.x, self.landing.y - self.start.y);
+        let side = line.normalized();
+        let bend = self.curve * (t * t - t);
         Vec2::new(
-            self.start.x + (self.landing.x - self.start.x) * t,
-            self.start.y + (self.landing.y - self.start.y) * t,
+            self.start.x + line.x * t - side.y * bend,
+            self.start.y + line.y * t + side.x * bend,
         )
     }
 
+    // The way the ball is travelling as it comes down, turned off the line
+    // by however much it has bent.
+    pub fn landing_dir(self) -> Vec2 {
+        let line = Vec2::new(self.landing.x - self.start.x, self.landing.y - self.start.y);
+        let side = line.normalized();
+        Vec2::new(line.x - side.y * self.curve, line.y + side.x * self.curve).normalized()
+    }
+
     pub fn arc_height(self) -> f32 {
         let t = self.progress();
         4.0 * self.apex * t * (1.0 - t)
     }
+
+    // Where the ball is drawn: up off the ground by the height of the arc.
+    pub fn sky_pos(self) -> Vec2 {
+        let ground = self.ground_pos();
+        Vec2::new(ground.x, ground.y - self.arc_height())
+    }
 }
 
 pub struct Game {
+    pub layout: HoleLayout,
+    // Shared with the simulations the caddie and bots run.
+    pub terrain: Rc<TerrainMap>,
+    pub settings: Settings,
+    pub stats: HoleSideStats,
+    pub profile: Profile,
+    // Career earnings paid out for the hole just finished.
+    pub payout: u32,
+    pub gallery: Gallery,
+    pub stats_page: StatsPage,
+    pub notes: NoteBook,
+    // A note being typed on the scorecard for the hole in play.
+    pub note_draft: Option<String>,
+    pub shop_cursor: usize,
+    // Set while the terminal is in the background; the simulation holds.
+    pub paused: bool,
+    // Frames are slow getting to the terminal; set by the main loop.
+    pub slow_link: bool,
+    // How far the frame being drawn falls between the last two steps; set by
+    // the main loop.
+    pub frame_alpha: f32,
+    // Roll substeps the energy audit has caught gaining energy.
+    pub energy_imbalances: u32,
+    // The ball as the last step found it.
+    last_view: BallView,
+    pub show_help: bool,
+    // Time spent at address since the last key press.
+    pub idle_secs: f32,
+    pub difficulty: Option<HoleDifficulty>,
+    pub hole_browser: Option<HoleBrowser>,
+    pub course_round: Option<CourseRound>,
+    pub warm_up: Option<WarmUp>,
+    // Set by the warm-up and kept for the rest of the course round.
+    pub day_form: Option<DayForm>,
     pub ball: Vec2,
     pub velocity: Vec2,
-    pub trail: Vec<Vec2>,
+    pub trail: Vec<TrailPoint>,
+    pub spin_check: Option<SpinCheck>,
+    // Simulated time since the last trail point.
+    trail_clock: f32,
+    pub shot_history: Vec<ShotRecord>,
+    // The round as it stood before the last stroke, saved in its place
+    // while the ball is still moving.
+    pub pre_shot: Option<SavedRound>,
     pub hole: Vec2,
     pub angle: f32,
     pub selected_club_idx: usize,
+    pub bag: Bag,
+    pub challenge: Challenge,
     pub selected_shot: ShotType,
+    pub selected_shape: ShotShape,
     pub auto_caddie: bool,
+    pub caddie_aim: Option<f32>,
+    pub caddie_advice: Option<CaddieAdvice>,
+    pub target_cursor: Option<Vec2>,
+    pub fringe: Option<FringeComparison>,
+    footprint: Option<Footprint>,
+    pub hazard_warning: Option<HazardWarning>,
+    simulated: bool,
+    pub aim_presets: Vec<Vec2>,
+    aim_preset_idx: usize,
     pub strokes: u32,
+    // Penalty strokes taken on this hole; already counted in `strokes`.
+    pub penalty_strokes: u32,
+    pub penalty_drop: Option<PenaltyDrop>,
+    // Plugged in a bunker or embedded in soft rough until it is played out.
+    pub buried: bool,
+    // The ways the rules allow play to go on after a penalty or from an
+    // embedded ball, open to change until the next stroke.
+    pub relief: Option<ReliefChoice>,
+    // Mud picked up on landing, which swerves the next swing one way or the
+    // other.
+    pub mud_ball: bool,
     pub par: u32,
     pub hole_done: bool,
+    pub jarred: bool,
+    pub celebration: Option<f32>,
+    // Time since the hole was finished, once any replay is over.
+    pub hole_done_secs: f32,
+    // Between two course holes: the one just played and the next, until a
+    // key is pressed.
+    pub between_holes: Option<HoleRecap>,
+    // The signed card, shown once the last hole of a course round is done.
+    pub round_card: Option<Scorecard>,
+    // The tournament standings, shown after the card of each round.
+    pub show_leaderboard: bool,
+    // Titles won on the round just finished, and what it was judged on.
+    pub round_awards: Vec<&'static Award>,
+    pub round_tally: RoundTally,
+    pub match_play: Option<MatchPlay>,
+    // The computer opponent's ball on this hole, when one is playing.
+    pub ai: Option<Box<AiPlayer>>,
+    // Set when two partners share the ball and alternate shots.
+    pub foursomes: Option<Foursomes>,
+    pub tournament: Option<Tournament>,
+    // Every course round is recorded against the best one kept for the
+    // course, which `--ghost` draws.
+    pub ghost: Option<GhostRace>,
+    // Built for the hole the first time the heat map is shown on it.
+    pub heat_map: Option<HeatMap>,
+    // The tutorial step to show, while there is one.
+    pub tutorial: Option<TutorialStep>,
+    replay_buffer: ReplayBuffer,
+    pub replay: Option<Replay>,
     pub rolling: bool,
-    pub wind: f32,
+    pub wind: Wind,
+    pub wind_history: WindHistory,
+    pub conditions: ConditionPreset,
     pub roll_time: f32,
     pub airborne: Option<AirState>,
+    // Where the last shot came down, kept through its roll-out.
+    pub landed_at: Option<Vec2>,
+    // Set when the caddie bot playing this ball copies the player's game.
+    pub shadow: Option<ShadowRival>,
     pub swing_frame: usize,
     pub swing_active: bool,
     swing_timer: f32,
     pub golfer_anchor: Vec2,
+    pub queued_aim_steps: i32,
+    pub queued_club_steps: i32,
+    pub queued_shot_cycles: u32,
+    pub queued_shape_cycles: u32,
+    pub pressure: Option<PressureMeter>,
+    pub pressure_result: Option<PressureResult>,
+    pub strike_quality: Option<StrikeQuality>,
+    pub putt_charge: Option<f32>,
+    pub presentation: Option<ShotPresentation>,
+    shot_view_zoomed: bool,
+    pub view_zoomed: bool,
+    pub camera_blend: Option<f32>,
 }
 
 impl Game {
     pub fn new() -> Self {
+        Self::with_layout(HoleLayout::classic())
+    }
+
+    pub fn with_layout(layout: HoleLayout) -> Self {
+        let terrain = Rc::new(TerrainMap::from_layout(&layout));
+        Self::with_terrain(layout, terrain)
+    }
+
+    fn with_terrain(layout: HoleLayout, terrain: Rc<TerrainMap>) -> Self {
+        let tee = layout.tee;
+        let pin = layout.pin;
+        let par = layout.par;
         Self {
-            ball: Vec2::new(8.0, (HEIGHT / 2) as f32),
+            layout,
+            terrain,
+            settings: Settings::default(),
+            stats: HoleSideStats::default(),
+            profile: Profile::default(),
+            payout: 0,
+            gallery: Gallery::default(),
+            stats_page: StatsPage::Closed,
+            notes: NoteBook::default(),
+            note_draft: None,
+            shop_cursor: 0,
+            paused: false,
+            slow_link: false,
+            frame_alpha: 1.0,
+            energy_imbalances: 0,
+            last_view: BallView::default(),
+            show_help: false,
+            idle_secs: 0.0,
+            difficulty: None,
+            hole_browser: None,
+            course_round: None,
+            warm_up: None,
+            day_form: None,
+            ball: tee,
             velocity: Vec2::new(0.0, 0.0),
             trail: Vec::with_capacity(TRAIL_LEN),
-            hole: Vec2::new((WIDTH - 8) as f32, (HEIGHT / 2 - 5) as f32),
+            spin_check: None,
+            trail_clock: 0.0,
+            shot_history: Vec::new(),
+            pre_shot: None,
+            hole: pin,
             angle: 0.0,
             selected_club_idx: 0,
+            bag: Bag::full(),
+            challenge: Challenge::None,
             selected_shot: ShotType::Full,
+            selected_shape: ShotShape::Straight,
             auto_caddie: true,
+            caddie_aim: None,
+            caddie_advice: None,
+            target_cursor: None,
+            fringe: None,
+            footprint: None,
+            hazard_warning: None,
+            simulated: false,
+            aim_presets: Vec::new(),
+            aim_preset_idx: 0,
             strokes: 0,
-            par: 4,
+            penalty_strokes: 0,
+            penalty_drop: None,
+            buried: false,
+            relief: None,
+            mud_ball: false,
+            par,
             hole_done: false,
+            jarred: false,
+            celebration: None,
+            hole_done_secs: 0.0,
+            between_holes: None,
+            round_card: None,
+            show_leaderboard: false,
+            round_awards: Vec::new(),
+            round_tally: RoundTally::default(),
+            match_play: None,
+            ai: None,
+            foursomes: None,
+            tournament: None,
+            ghost: None,
+            heat_map: None,
+            tutorial: None,
+            replay_buffer: ReplayBuffer::default(),
+            replay: None,
             rolling: false,
-            wind: 0.0,
+            wind: Wind::default(),
+            wind_history: WindHistory::default(),
+            conditions: ConditionPreset::BreezyAfternoon,
             roll_time: 0.0,
             airborne: None,
+            landed_at: None,
+            shadow: None,
             swing_frame: 0,
             swing_active: false,
             swing_timer: 0.0,
-            golfer_anchor: Vec2::new(8.0, (HEIGHT / 2) as f32),
+            golfer_anchor: tee,
+            queued_aim_steps: 0,
+            queued_club_steps: 0,
+            queued_shot_cycles: 0,
+            queued_shape_cycles: 0,
+            pressure: None,
+            pressure_result: None,
+            strike_quality: None,
+            putt_charge: None,
+            presentation: None,
+            shot_view_zoomed: false,
+            view_zoomed: false,
+            camera_blend: None,
         }
     }
 
     pub fn reset(&mut self) {
-        *self = Self::new();
+        // A match hole stands as it was played.
+        if self.match_play.is_some() {
+            return;
+        }
+        let aim_presets = std::mem::take(&mut self.aim_presets);
+        let terrain = Rc::clone(&self.terrain);
+        self.restart_with(self.layout.clone());
+        self.aim_presets = aim_presets;
+        self.terrain = terrain;
+        self.wind = self.conditions.opening_wind(&mut rand::thread_rng());
     }
 
-    pub fn can_shoot(&self) -> bool {
-        !self.rolling && self.airborne.is_none() && !self.hole_done
+    // A random hole with its round already started, redrawn while it plays
+    // far off the usual difficulty if balanced holes are on.
+    fn generate_round<R: Rng>(&self, rng: &mut R) -> Game {
+        let mut fresh = || {
+            let mut next = Game::with_layout(HoleLayout::random(rng, DEFAULT_YARDS_PER_TILE));
+            next.settings = self.settings;
+            next.start_round();
+            next
+        };
+        let mut next = fresh();
+        for _ in 1..REGENERATE_ATTEMPTS {
+            let outlier = next.difficulty.is_some_and(|d| d.is_outlier());
+            if !self.settings.balanced_holes || !outlier {
+                break;
+            }
+            next = fresh();
+        }
+        next
     }
 
-    pub fn current_surface(&self) -> Surface {
-        terrain_surface(self.ball.x as i32, self.ball.y as i32)
+    // Moves onto a prepared hole, keeping everything that lives across holes.
+    fn adopt_round(&mut self, next: Game) {
+        self.restart_with(next.layout.clone());
+        self.conditions = next.conditions;
+        self.wind = next.wind;
+        self.challenge = next.challenge;
+        self.bag = next.bag;
+        self.difficulty = next.difficulty;
+        self.selected_club_idx = next.selected_club_idx;
     }
 
-    pub fn on_green(&self) -> bool {
-        self.current_surface() == Surface::Green
+    fn finish_warm_up(&mut self, warm_up: WarmUp) {
+        let form = warm_up.form();
+        self.day_form = Some(form);
+        self.play_course_hole();
+        self.presentation = Some(ShotPresentation {
+            zoomed: false,
+            elapsed: 0.0,
+            summary: format!("Warm-up: {}", form.verdict()),
+            landing: None,
+        });
     }
 
-    pub fn aim_step(&self) -> f32 {
-        if self.on_green() {
-            AIM_STEP_RAD * 0.45
+    pub fn start_course(&mut self, round: CourseRound) {
+        self.course_round = Some(round);
+        self.start_course_round();
+    }
+
+    fn start_course_round(&mut self) {
+        self.start_course_modes();
+        if self.settings.warm_up {
+            self.start_warm_up();
         } else {
-            AIM_STEP_RAD
+            self.play_course_hole();
         }
     }
 
-    pub fn current_club(&self) -> ClubSpec {
-        CLUBS[self.selected_club_idx]
+    // Whatever else a course round is played as, or against.
+    fn start_course_modes(&mut self) {
+        self.start_match();
+        self.start_foursomes();
+        self.start_tournament_round();
+        self.ghost = self
+            .course_round
+            .as_ref()
+            .map(|round| GhostRace::load(&round.course.name));
     }
 
-    pub fn selected_shot_distance_yd(&self) -> f32 {
-        let club = self.current_club();
-        if club.putter {
-            self.putter_rollout_target_yd(club)
-        } else {
-            club.carry_yd * self.selected_shot.carry_mult()
-                + club.rollout_yd * self.selected_shot.roll_mult()
+    // Picks a saved round up at the shot it was left on, or at the next tee
+    // if it was left on a finished hole. A match, partners' turns or the
+    // computer golfer start again from this hole.
+    pub fn resume(&mut self, saved: SavedRound, round: Option<CourseRound>) {
+        self.course_round = round;
+        self.start_course_modes();
+        if saved.hole_done {
+            if let Some(round) = self.course_round.as_mut() {
+                round.advance();
+            }
+            self.play_course_hole();
+            return;
         }
+        self.restart_with(saved.layout);
+        self.strokes = saved.strokes;
+        self.penalty_strokes = saved.penalty_strokes;
+        self.angle = saved.angle;
+        self.selected_club_idx = saved.club;
+        self.selected_shot = saved.shot;
+        self.selected_shape = saved.shape;
+        self.buried = saved.buried;
+        self.challenge = saved.challenge;
+        self.conditions = saved.conditions;
+        self.ball = saved.ball;
+        self.hole = saved.cup;
+        self.bag = saved.bag;
+        self.wind = saved.wind;
+        self.difficulty = Some(HoleDifficulty::estimate(self));
     }
 
-    pub fn cycle_club(&mut self, delta: i32) {
-        if !self.can_shoot() {
+    fn start_warm_up(&mut self) {
+        self.day_form = None;
+        self.restart_with(warm_up::range_layout());
+        self.warm_up = Some(WarmUp::new());
+        self.challenge = Challenge::None;
+        self.bag = Bag::full();
+        self.difficulty = None;
+        self.selected_club_idx = self.best_shot_for(self.distance_to_hole_yd(), false).0;
+    }
+
+    // Back to the mat for another ball, or off to the first tee once the
+    // bucket is empty with the day's form settled.
+    fn next_range_ball(&mut self) {
+        let Some(warm_up) = self.warm_up.as_mut() else {
+            return;
+        };
+        if warm_up.is_done() {
+            if let Some(warm_up) = self.warm_up.take() {
+                self.finish_warm_up(warm_up);
+            }
             return;
         }
-        let len = CLUBS.len() as i32;
-        let mut idx = self.selected_club_idx as i32 + delta;
-        if idx < 0 {
-            idx += len;
-        }
-        if idx >= len {
-            idx -= len;
-        }
-        self.selected_club_idx = idx as usize;
-        self.selected_shot = ShotType::Full;
-        self.auto_caddie = false;
+        warm_up.ball_out = false;
+        let club_idx = self.selected_club_idx;
+        self.restart_with(self.layout.clone());
+        self.selected_club_idx = club_idx;
     }
 
-    pub fn cycle_shot_type(&mut self) {
-        if !self.can_shoot() || self.current_club().putter {
+    fn play_course_hole(&mut self) {
+        let Some(round) = &self.course_round else {
             return;
-        }
-        let mut idx = ShotType::NON_PUTTER
-            .iter()
-            .position(|s| *s == self.selected_shot)
-            .unwrap_or(0);
-        idx = (idx + 1) % ShotType::NON_PUTTER.len();
-        self.selected_shot = ShotType::NON_PUTTER[idx];
-        self.auto_caddie = false;
+        };
+        self.restart_with(round.current().layout.clone());
+        self.start_round();
     }
 
-    pub fn toggle_auto_caddie(&mut self) {
-        self.auto_caddie = !self.auto_caddie;
-        if self.auto_caddie && self.can_shoot() {
-            self.auto_select_shot();
+    // On a course this walks to the next tee (an unfinished hole goes down
+    // without a score, and a warm-up cut short is judged on what was hit),
+    // stopping first to look back at a finished hole and ahead to the next;
+    // otherwise it opens the hole browser.
+    pub fn next_hole(&mut self) {
+        if let Some(warm_up) = self.warm_up.take() {
+            self.finish_warm_up(warm_up);
+            return;
+        }
+        // In a tournament the leaderboard follows the signed card.
+        if self.tournament.is_some() && self.round_card.take().is_some() {
+            self.show_leaderboard = true;
+            return;
+        }
+        // Leaving any page moves on; finishing a hole opens one of them.
+        let leaving = self.between_holes.take().is_some()
+            | self.round_card.take().is_some()
+            | std::mem::take(&mut self.show_leaderboard);
+        if !leaving && self.hole_done && !self.simulated {
+            self.between_holes = HoleRecap::of(self);
+            if self.between_holes.is_none() {
+                self.round_card = self.course_round.as_ref().map(|round| round.card.clone());
+            }
+            if self.between_holes.is_some() || self.round_card.is_some() {
+                return;
+            }
+        }
+        match self.course_round.as_mut() {
+            Some(round) => {
+                let finished = round.is_last_hole();
+                round.advance();
+                if finished {
+                    self.start_course_round();
+                } else {
+                    self.play_course_hole();
+                }
+            }
+            None => self.open_hole_browser(),
         }
     }
 
-    pub fn distance_to_hole_yd(&self) -> f32 {
-        let dx = self.hole.x - self.ball.x;
-        let dy = self.hole.y - self.ball.y;
-        (dx * dx + dy * dy).sqrt() * YARDS_PER_TILE
+    // Seconds until a finished course hole moves on by itself. The final card
+    // then waits for the player so it can be read.
+    pub fn auto_advance_in(&self) -> Option<f32> {
+        self.course_round.as_ref()?;
+        let waiting = self.hole_done
+            && self.replay.is_none()
+            && self.between_holes.is_none()
+            && self.round_card.is_none()
+            && !self.show_leaderboard
+            && !self.ai_to_play()
+            && !self.simulated;
+        waiting.then_some(AUTO_ADVANCE_SECS - self.hole_done_secs)
     }
 
-    pub fn update(&mut self, dt_secs: f32) {
-        self.update_swing(dt_secs);
+    pub fn open_hole_browser(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.hole_browser = Some(HoleBrowser {
+            candidates: (0..BROWSER_CANDIDATES)
+                .map(|_| self.generate_round(&mut rng))
+                .collect(),
+            selected: 0,
+        });
+    }
 
-        if self.hole_done {
-            return;
+    pub fn close_hole_browser(&mut self) {
+        self.hole_browser = None;
+    }
+
+    pub fn move_browser_selection(&mut self, delta: i32) {
+        if let Some(browser) = self.hole_browser.as_mut() {
+            let len = browser.candidates.len() as i32;
+            browser.selected = (browser.selected as i32 + delta).rem_euclid(len) as usize;
         }
+    }
 
-        if let Some(mut air) = self.airborne {
-            air.elapsed += dt_secs;
-            if air.elapsed >= air.duration {
-                self.ball = Vec2::new(
-                    air.landing.x.clamp(1.0, (WIDTH - 2) as f32),
-                    air.landing.y.clamp(1.0, (HEIGHT - 2) as f32),
-                );
-                self.airborne = None;
-                let dir = Vec2::new(air.landing.x - air.start.x, air.landing.y - air.start.y)
-                    .normalized();
-                self.velocity = Vec2::new(
-                    dir.x * air.rollout_speed + self.wind * 0.12,
-                    dir.y * air.rollout_speed,
-                );
-                self.rolling = true;
-                self.roll_time = 0.0;
-            } else {
-                self.airborne = Some(air);
+    pub fn select_browsed_hole(&mut self, idx: usize) -> bool {
+        match self.hole_browser.as_mut() {
+            Some(browser) if idx < browser.candidates.len() => {
+                browser.selected = idx;
+                true
             }
+            _ => false,
         }
+    }
 
-        if !self.rolling {
-            if self.can_shoot() && self.auto_caddie {
-                self.auto_select_shot();
-            }
-            return;
+    pub fn choose_browsed_hole(&mut self) {
+        if let Some(mut browser) = self.hole_browser.take() {
+            let next = browser.candidates.swap_remove(browser.selected);
+            self.adopt_round(next);
         }
+    }
 
-        let substeps = (dt_secs / 0.016).ceil().max(1.0) as u32;
-        let step = dt_secs / substeps as f32;
-        self.roll_time += dt_secs;
+    fn restart_with(&mut self, layout: HoleLayout) {
+        let settings = self.settings;
+        let stats = std::mem::take(&mut self.stats);
+        let profile = std::mem::take(&mut self.profile);
+        let gallery = std::mem::take(&mut self.gallery);
+        let notes = std::mem::take(&mut self.notes);
+        let wind_history = std::mem::take(&mut self.wind_history);
+        let course_round = self.course_round.take();
+        let match_play = self.match_play.take();
+        let foursomes = self.foursomes.take();
+        let tournament = self.tournament.take();
+        let ghost = self.ghost.take();
+        let energy_imbalances = self.energy_imbalances;
+        let warm_up = self.warm_up.take();
+        let day_form = self.day_form;
+        let stats_page = self.stats_page;
+        let shop_cursor = self.shop_cursor;
+        let conditions = self.conditions;
+        let bag = self.bag;
+        let challenge = self.challenge;
+        let difficulty = self.difficulty;
+        *self = Self::with_layout(layout);
+        self.difficulty = difficulty;
+        self.challenge = challenge;
+        self.settings = settings;
+        self.stats = stats;
+        self.profile = profile;
+        self.gallery = gallery;
+        self.notes = notes;
+        self.wind_history = wind_history;
+        self.course_round = course_round;
+        self.match_play = match_play;
+        self.foursomes = foursomes;
+        self.tournament = tournament;
+        self.ghost = ghost;
+        self.energy_imbalances = energy_imbalances;
+        self.warm_up = warm_up;
+        self.day_form = day_form;
+        self.stats_page = stats_page;
+        self.shop_cursor = shop_cursor;
+        self.conditions = conditions;
+        self.bag = bag;
+        self.selected_club_idx = bag.nearest(self.selected_club_idx);
+    }
 
-        for _ in 0..substeps {
-            let surface = self.current_surface();
-            self.ball.x += self.velocity.x * step;
-            self.ball.y += self.velocity.y * step;
+    // Draws the day's conditions from the chosen preset (or at random) and
+    // applies them to the green, the pin and the opening wind; the session's
+    // ground conditions go on the green after them.
+    pub fn start_round(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.conditions = ConditionPreset::pick(self.settings.conditions, &mut rng);
+        self.layout.green = self
+            .settings
+            .course_conditions
+            .green(self.conditions.green(self.layout.green));
+        self.layout.tuck_pin(self.conditions.pin_tuck(), &mut rng);
+        self.hole = self.layout.pin;
+        self.wind = self.conditions.opening_wind(&mut rng);
+        self.challenge = self.settings.challenge;
+        self.bag = self.challenge.bag(&mut rng);
+        self.selected_club_idx = self.bag.nearest(self.selected_club_idx);
+        self.difficulty = Some(HoleDifficulty::estimate(self));
+    }
 
-            let speed = self.velocity.length();
-            let drag = surface.drag_strength() * step;
-            if speed > 0.0001 {
-                let drag_scale = (1.0 - drag).max(0.0);
-                self.velocity.x *= drag_scale;
-                self.velocity.y *= drag_scale;
-            }
+    // The yardage book page for the hole in play on a course.
+    pub fn hole_note(&self) -> Option<&str> {
+        let round = self.course_round.as_ref()?;
+        self.notes.note(&round.course.name, round.current().number)
+    }
 
-            if self.ball.x < 1.0 || self.ball.x > (WIDTH - 2) as f32 {
-                self.velocity.x *= -0.35;
-                self.ball.x = self.ball.x.clamp(1.0, (WIDTH - 2) as f32);
-            }
-            if self.ball.y < 1.0 || self.ball.y > (HEIGHT - 2) as f32 {
-                self.velocity.y *= -0.35;
-                self.ball.y = self.ball.y.clamp(1.0, (HEIGHT - 2) as f32);
-            }
+    pub fn rate_course(&mut self, rating: u8) {
+        if let Some(round) = &self.course_round {
+            self.notes.rate(&round.course.name, rating);
+            let _ = self.notes.save();
+        }
+    }
 
-            let dx = self.ball.x - self.hole.x;
-            let dy = self.ball.y - self.hole.y;
-            let distance_to_hole = (dx * dx + dy * dy).sqrt();
+    pub fn start_note(&mut self) {
+        if self.course_round.is_some() {
+            self.note_draft = Some(self.hole_note().unwrap_or_default().to_string());
+        }
+    }
+
+    pub fn type_note(&mut self, c: char) {
+        if let Some(draft) = self.note_draft.as_mut() {
+            if draft.chars().count() < MAX_NOTE_CHARS {
+                draft.push(c);
+            }
+        }
+    }
+
+    pub fn erase_note_char(&mut self) {
+        if let Some(draft) = self.note_draft.as_mut() {
+            draft.pop();
+        }
+    }
+
+    pub fn finish_note(&mut self) {
+        let (Some(draft), Some(round)) = (self.note_draft.take(), &self.course_round) else {
+            return;
+        };
+        self.notes
+            .set_note(&round.course.name, round.current().number, &draft);
+        let _ = self.notes.save();
+    }
+
+    pub fn cancel_note(&mut self) {
+        self.note_draft = None;
+    }
+
+    pub fn cycle_challenge(&mut self) {
+        self.settings.cycle_challenge();
+    }
+
+    pub fn cycle_conditions(&mut self) {
+        self.settings.cycle_conditions();
+    }
+
+    pub fn note_input(&mut self) {
+        self.idle_secs = 0.0;
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+    }
+
+    pub fn idle_hint_due(&self) -> bool {
+        let after = self.settings.idle_hint_secs;
+        after > 0.0 && self.idle_secs >= after && self.can_shoot()
+    }
+
+    // Racing players who leave the keyboard are marked away, and after a
+    // grace period the caddie plays for them.
+    pub fn is_afk(&self) -> bool {
+        self.idle_hint_due()
+    }
+
+    pub fn afk_autoplay_due(&self) -> bool {
+        self.settings.afk_autoplay
+            && self.is_afk()
+            && self.idle_secs >= self.settings.idle_hint_secs + AFK_GRACE_SECS
+    }
+
+    // Plays out the hole from here with the caddie choosing every shot and
+    // returns the final stroke count, giving up a few over par.
+    pub fn bot_round(&self) -> u32 {
+        let mut sim = self.simulation();
+        sim.strokes = self.strokes;
+        sim.play_out(self.par + BOT_GIVE_UP_OVER_PAR);
+        sim.strokes
+    }
+
+    // The caddie's average score from `at`, over `playouts` tries with the
+    // ball lying there and nothing yet played.
+    pub fn expected_strokes_from(&self, at: Vec2, playouts: u32) -> f32 {
+        let total: u32 = (0..playouts)
+            .map(|_| {
+                let mut sim = self.simulation();
+                sim.ball = at;
+                sim.play_out(self.par + BOT_GIVE_UP_OVER_PAR);
+                sim.strokes
+            })
+            .sum();
+        total as f32 / playouts as f32
+    }
+
+    fn play_out(&mut self, give_up: u32) {
+        let dt = TICK_MS as f32 / 1000.0;
+        while !self.hole_done && self.strokes < give_up {
+            self.autoplay();
+            let mut steps = 0;
+            while !self.can_shoot() && steps < BOT_MAX_STEPS_PER_SHOT {
+                self.update(dt);
+                steps += 1;
+            }
+        }
+    }
+
+    // Starts a match against the shadow rival over the course round, or over
+    // a run of generated holes, when match play is on.
+    pub fn start_match(&mut self) {
+        if !self.settings.match_play {
+            return;
+        }
+        let holes = self
+            .course_round
+            .as_ref()
+            .map_or(MATCH_HOLES, |round| round.order.len() as u32);
+        self.match_play = Some(MatchPlay::new(holes));
+    }
+
+    // With `--ghost`, the best round's play of this hole, and how far into
+    // the hole this one has got.
+    pub fn ghost_hole(&self) -> Option<(&GhostHole, f32)> {
+        let round = self
+            .course_round
+            .as_ref()
+            .filter(|_| self.settings.ghost && self.warm_up.is_none())?;
+        let hole = self.ghost.as_ref()?.hole(round.current().number)?;
+        Some((hole, round.hole_clock))
+    }
+
+    // Picks up a tournament on the course where it was left, and once one
+    // is over the next round starts another.
+    fn start_tournament_round(&mut self) {
+        let Some(round) = self
+            .course_round
+            .as_ref()
+            .filter(|_| self.settings.tournament)
+        else {
+            return;
+        };
+        let course = &round.course.name;
+        self.tournament = match self.tournament.take() {
+            Some(tournament) if !tournament.is_over() => Some(tournament),
+            Some(_) => Some(Tournament::new(course)),
+            None => Some(Tournament::load(course)),
+        };
+    }
+
+    pub fn start_foursomes(&mut self) {
+        if self.settings.foursomes {
+            self.foursomes = Some(Foursomes::new());
+        }
+    }
+
+    // The partner whose turn it is, in foursomes.
+    pub fn partner_to_play(&self) -> Option<usize> {
+        let played = self.strokes - self.penalty_strokes;
+        self.foursomes.as_ref().map(|f| f.to_play(played))
+    }
+
+    // The rival plays the hole from the tee like the player would, and picks
+    // up once it reaches `give_up` strokes without holing out.
+    fn rival_score(&self, give_up: u32) -> Option<u32> {
+        let mut sim = self.simulation();
+        sim.ball = self.layout.tee;
+        sim.shadow = Some(ShadowRival::from_stats(&self.stats));
+        sim.play_out(give_up);
+        sim.hole_done.then_some(sim.strokes)
+    }
+
+    // On the holes the rival plays first its score is up before the player
+    // tees off.
+    fn post_rival_score(&mut self) {
+        let due = self
+            .match_play
+            .as_ref()
+            .is_some_and(|m| m.rival_first() && m.rival.is_none() && !m.is_over());
+        if !due || self.hole_done || self.simulated || self.warm_up.is_some() {
+            return;
+        }
+        let give_up = self.par + BOT_GIVE_UP_OVER_PAR;
+        let rival = self.rival_score(give_up).unwrap_or(give_up);
+        if let Some(m) = self.match_play.as_mut() {
+            m.rival = Some(rival);
+        }
+    }
+
+    // Once the player can no longer halve the hole it's given up.
+    fn concede_if_beaten(&mut self) {
+        let beaten = self
+            .match_play
+            .as_ref()
+            .is_some_and(|m| !m.is_over() && m.beaten(self.strokes));
+        if beaten && !self.hole_done && self.can_shoot() {
+            self.hole_done = true;
+            self.finish_hole(true);
+        }
+    }
+
+    // Scores the hole in the match; the rival plays it now if the player
+    // went first.
+    fn settle_match_hole(&mut self, conceded: bool) {
+        let Some(m) = self.match_play.as_mut() else {
+            return;
+        };
+        // Once it's decided the remaining holes are just golf.
+        if m.is_over() {
+            m.last = None;
+            return;
+        }
+        let posted = m.rival;
+        let rival = posted.or_else(|| self.rival_score(self.strokes));
+        let result = match rival {
+            _ if conceded => HoleResult::Conceded,
+            None => HoleResult::RivalConceded,
+            Some(rival) if rival < self.strokes => HoleResult::Lost,
+            Some(rival) if rival == self.strokes => HoleResult::Halved,
+            Some(_) => HoleResult::Won,
+        };
+        if let Some(m) = self.match_play.as_mut() {
+            m.record(result);
+        }
+    }
+
+    pub fn autoplay(&mut self) {
+        if !self.can_shoot() || self.ai_to_play() {
+            return;
+        }
+        let mut putt_power = self.line_up();
+        let mut aim_offset = 0.0;
+        if let Some(shadow) = &self.shadow {
+            let mut rng = rand::thread_rng();
+            match putt_power.as_mut() {
+                Some(power) => {
+                    let (aim, pace) = shadow.putt_error(self.distance_to_hole_yd() * 3.0, &mut rng);
+                    aim_offset = aim;
+                    *power = (*power * pace).clamp(0.0, 1.0);
+                }
+                None => {
+                    aim_offset = shadow.aim_error(&mut rng);
+                }
+            }
+        }
+        self.strike(aim_offset, putt_power);
+    }
+
+    // Takes the caddie's club and line, as the bots do, and the pace it would
+    // give a putt.
+    pub fn line_up(&mut self) -> Option<f32> {
+        self.auto_select_shot();
+        if let Some(angle) = self.caddie_aim {
+            self.angle = angle;
+        }
+        self.pressure = None;
+        self.pressure_result = None;
+        self.putt_charge = None;
+        self.on_green().then(|| self.bot_putt_power())
+    }
+
+    // The computer opponent joins each hole on its first step, once the pin
+    // is set for the day.
+    fn deal_in_ai(&mut self) {
+        let Some(skill) = self.settings.ai else {
+            return;
+        };
+        if self.ai.is_some() || self.simulated || self.warm_up.is_some() {
+            return;
+        }
+        let mut sim = self.simulation();
+        sim.ball = self.layout.tee;
+        sim.settings.ai = None;
+        self.ai = Some(Box::new(AiPlayer::new(skill, sim)));
+    }
+
+    // Whoever is farther from the hole plays next; the player has the honor
+    // on the tee, and the computer plays on alone once the player is in.
+    pub fn ai_to_play(&self) -> bool {
+        self.ai.as_ref().is_some_and(|ai| {
+            !ai.is_done()
+                && (ai.is_moving()
+                    || self.strokes > 0
+                        && (self.hole_done
+                            || ai.game.distance_to_hole_yd() > self.distance_to_hole_yd()))
+        })
+    }
+
+    fn update_ai(&mut self, dt_secs: f32) {
+        self.deal_in_ai();
+        let due = self.ai_to_play() && !self.rolling && self.airborne.is_none();
+        let wind = self.wind;
+        let Some(ai) = self.ai.as_mut() else {
+            return;
+        };
+        if due && !ai.is_moving() {
+            ai.game.wind = wind;
+            ai.address(dt_secs);
+        }
+        ai.update(dt_secs);
+    }
+
+    // Die-weights the putt: enough pace to reach the edge of the cup just
+    // under the speed that would lip out, read off the green's drag.
+    fn bot_putt_power(&self) -> f32 {
+        let club = self.current_club();
+        let drag = self.surface_drag(Surface::Green);
+        let to_cup = self.distance_to_hole_yd() / self.layout.yards_per_tile;
+        let speed = drag * (to_cup - BOT_PUTT_ARRIVE_RADIUS).max(0.0) + BOT_PUTT_ARRIVE_SPEED;
+        (speed / 2.2 * self.layout.yards_per_tile / club.rollout_yd).clamp(0.0, 1.0)
+    }
+
+    pub fn focus_lost(&mut self) {
+        self.paused = self.settings.pause_on_focus_loss;
+    }
+
+    pub fn focus_gained(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn cycle_stats_screen(&mut self) {
+        self.stats_page = match self.stats_page {
+            StatsPage::Closed => StatsPage::HoleSide,
+            StatsPage::HoleSide => StatsPage::Lifetime,
+            StatsPage::Lifetime if self.course_round.is_some() => StatsPage::Scorecard,
+            StatsPage::Lifetime | StatsPage::Scorecard => StatsPage::Gallery,
+            StatsPage::Gallery => StatsPage::ProShop,
+            StatsPage::ProShop => StatsPage::Closed,
+        };
+    }
+
+    pub fn browse_shop(&mut self, step: i32) {
+        let len = shop::CATALOG.len() as i32;
+        self.shop_cursor = (self.shop_cursor as i32 + step).rem_euclid(len) as usize;
+    }
+
+    pub fn buy_or_equip_selected(&mut self) {
+        if self.profile.buy_or_equip(&shop::CATALOG[self.shop_cursor]) {
+            let _ = self.profile.save();
+        }
+    }
+
+    pub fn toggle_wind_on_putts(&mut self) {
+        self.settings.toggle_wind_on_putts();
+    }
+
+    pub fn cycle_theme(&mut self) {
+        self.settings.cycle_theme();
+    }
+
+    pub fn cycle_hud(&mut self) {
+        self.settings.cycle_hud();
+    }
+
+    pub fn toggle_slope_shading(&mut self) {
+        self.settings.toggle_slope_shading();
+    }
+
+    pub fn toggle_distance_arcs(&mut self) {
+        self.settings.toggle_distance_arcs();
+    }
+
+    pub fn toggle_heat_map(&mut self) {
+        self.settings.toggle_heat_map();
+    }
+
+    pub fn toggle_high_contrast(&mut self) {
+        self.settings.toggle_high_contrast();
+    }
+
+    pub fn toggle_reduced_motion(&mut self) {
+        self.settings.toggle_reduced_motion();
+    }
+
+    pub fn toggle_low_bandwidth(&mut self) {
+        self.settings.toggle_low_bandwidth();
+    }
+
+    pub fn toggle_pull_back_putting(&mut self) {
+        self.settings.toggle_pull_back_putting();
+        self.putt_charge = None;
+    }
+
+    pub fn stroke_in_progress(&self) -> bool {
+        self.pressure.is_some() || self.putt_charge.is_some()
+    }
+
+    pub fn uses_pull_back_putt(&self) -> bool {
+        self.settings.pull_back_putting && self.current_club().putter && self.can_shoot()
+    }
+
+    // Pull-back putts skip the pressure meter; holding tempo is the test.
+    pub fn press_hit(&mut self) {
+        if self.ai_to_play() {
+            return;
+        }
+        if self.uses_pull_back_putt() {
+            if self.putt_charge.is_none() {
+                self.putt_charge = Some(0.0);
+            }
+        } else {
+            self.hit_ball();
+        }
+    }
+
+    pub fn release_hit(&mut self) {
+        if let Some(charge) = self.putt_charge.take() {
+            if self.can_shoot() {
+                self.pressure_result = None;
+                self.strike(0.0, Some(charge.max(0.03)));
+            }
+        }
+    }
+
+    pub fn toggle_pressure_putts(&mut self) {
+        self.settings.toggle_pressure_putts();
+        if !self.settings.pressure_putts {
+            self.pressure = None;
+        }
+    }
+
+    // A short putt that would win or halve the hole against the rival's
+    // posted score in a match, or make par or better otherwise.
+    pub fn is_pressure_putt(&self) -> bool {
+        let target = self
+            .match_play
+            .as_ref()
+            .and_then(|m| m.rival)
+            .unwrap_or(self.par);
+        self.current_club().putter
+            && self.on_green()
+            && self.distance_to_hole_yd() <= PRESSURE_PUTT_MAX_YD
+            && self.strokes < target
+    }
+
+    // Links-style drift: only wind above the threshold pushes a rolling ball,
+    // scaled by how exposed the surface (and green) is.
+    pub fn rolling_wind_accel(&self, surface: Surface) -> Vec2 {
+        if !self.settings.wind_on_putts {
+            return Vec2::default();
+        }
+        let wind = self.wind.vector();
+        let strength = (wind.length() - WIND_ROLL_THRESHOLD).max(0.0);
+        let exposure = if surface == Surface::Green {
+            self.layout.green.exposure
+        } else {
+            1.0
+        };
+        let accel = strength * WIND_ROLL_ACCEL * surface.wind_coupling() * exposure;
+        let dir = wind.normalized();
+        Vec2::new(dir.x * accel, dir.y * accel)
+    }
+
+    pub fn in_motion(&self) -> bool {
+        self.rolling || self.airborne.is_some()
+    }
+
+    fn ball_now(&self) -> BallView {
+        match self.airborne {
+            Some(air) => BallView {
+                ground: air.ground_pos(),
+                height: air.arc_height(),
+            },
+            None => BallView {
+                ground: self.ball,
+                height: 0.0,
+            },
+        }
+    }
+
+    // A moving ball `alpha` of the way from where the step before last left
+    // it to where the last one did, so frames drawn between steps show it
+    // between cells instead of jumping. The physics never sees this.
+    pub fn ball_view(&self, alpha: f32) -> BallView {
+        let now = self.ball_now();
+        if !self.in_motion() {
+            return now;
+        }
+        let from = self.last_view;
+        let lerp = |a: f32, b: f32| a + (b - a) * alpha.clamp(0.0, 1.0);
+        BallView {
+            ground: Vec2::new(
+                lerp(from.ground.x, now.ground.x),
+                lerp(from.ground.y, now.ground.y),
+            ),
+            height: lerp(from.height, now.height),
+        }
+    }
+
+    pub fn can_shoot(&self) -> bool {
+        !self.rolling && self.airborne.is_none() && !self.hole_done
+    }
+
+    pub fn current_surface(&self) -> Surface {
+        self.terrain.surface(self.ball.x as i32, self.ball.y as i32)
+    }
+
+    pub fn surface_drag(&self, surface: Surface) -> f32 {
+        let drag = surface.drag_strength(self.settings.course_conditions);
+        if surface == Surface::Green {
+            drag * self.layout.green.drag_mult()
+        } else {
+            drag
+        }
+    }
+
+    pub fn max_x(&self) -> f32 {
+        (self.layout.width - 2) as f32
+    }
+
+    pub fn max_y(&self) -> f32 {
+        (self.layout.height - 2) as f32
+    }
+
+    pub fn on_green(&self) -> bool {
+        self.current_surface() == Surface::Green
+    }
+
+    pub fn aim_step(&self) -> f32 {
+        if self.on_green() {
+            AIM_STEP_RAD * 0.45
+        } else {
+            AIM_STEP_RAD
+        }
+    }
+
+    pub fn current_club(&self) -> ClubSpec {
+        CLUBS[self.selected_club_idx]
+    }
+
+    pub fn selected_shot_distance_yd(&self) -> f32 {
+        let club = self.current_club();
+        if let (true, Some(charge)) = (club.putter, self.putt_charge) {
+            club.rollout_yd * charge
+        } else if club.putter {
+            self.putter_rollout_target_yd(club)
+        } else {
+            club.carry_yd * self.selected_shot.carry_mult()
+                + club.rollout_yd * self.selected_shot.roll_mult()
+        }
+    }
+
+    pub fn ball_in_motion(&self) -> bool {
+        (self.rolling || self.airborne.is_some()) && !self.hole_done
+    }
+
+    pub fn has_queued_input(&self) -> bool {
+        self.queued_aim_steps != 0
+            || self.queued_club_steps != 0
+            || self.queued_shot_cycles > 0
+            || self.queued_shape_cycles > 0
+    }
+
+    pub fn adjust_aim(&mut self, steps: i32) {
+        if self.ball_in_motion() {
+            self.queued_aim_steps += steps;
+            return;
+        }
+        if !self.can_shoot() || self.stroke_in_progress() {
+            return;
+        }
+        self.angle = wrap_angle_rad(self.angle + self.aim_step() * steps as f32);
+        self.tutorial_done(TutorialStep::Aim);
+    }
+
+    pub fn cycle_club(&mut self, delta: i32) {
+        if self.ball_in_motion() {
+            self.queued_club_steps += delta;
+            return;
+        }
+        if !self.can_shoot() || self.stroke_in_progress() {
+            return;
+        }
+        self.selected_club_idx = self.bag.step(self.selected_club_idx, delta);
+        self.selected_shot = ShotType::Full;
+        self.auto_caddie = false;
+        self.refresh_caddie_aim();
+        self.tutorial_done(TutorialStep::Club);
+    }
+
+    // Moves the tutorial past `step` once the player has done it, and turns
+    // it off for good after the last.
+    fn tutorial_done(&mut self, step: TutorialStep) {
+        if self.tutorial.is_none_or(|current| current > step) {
+            return;
+        }
+        self.tutorial = step.next();
+        if self.tutorial.is_none() {
+            self.settings.tutorial = false;
+            let _ = config::finish_tutorial();
+        }
+    }
+
+    pub fn cycle_shot_type(&mut self) {
+        if self.ball_in_motion() {
+            self.queued_shot_cycles += 1;
+            return;
+        }
+        if !self.can_shoot() || self.current_club().putter {
+            return;
+        }
+        let mut idx = ShotType::NON_PUTTER
+            .iter()
+            .position(|s| *s == self.selected_shot)
+            .unwrap_or(0);
+        idx = (idx + 1) % ShotType::NON_PUTTER.len();
+        self.selected_shot = ShotType::NON_PUTTER[idx];
+        self.auto_caddie = false;
+        self.refresh_caddie_aim();
+    }
+
+    pub fn cycle_shot_shape(&mut self) {
+        if self.ball_in_motion() {
+            self.queued_shape_cycles += 1;
+            return;
+        }
+        if !self.can_shoot() || self.current_club().putter {
+            return;
+        }
+        let idx = ShotShape::ALL
+            .iter()
+            .position(|s| *s == self.selected_shape)
+            .unwrap_or(0);
+        self.selected_shape = ShotShape::ALL[(idx + 1) % ShotShape::ALL.len()];
+        self.refresh_caddie_aim();
+    }
+
+    pub fn accept_caddie_aim(&mut self) {
+        if !self.can_shoot() || self.stroke_in_progress() {
+            return;
+        }
+        if let Some(angle) = self.caddie_aim {
+            self.angle = angle;
+        }
+    }
+
+    // Map-driven aiming: the cursor starts on the caddie's target and the
+    // club and line follow it as it moves.
+    pub fn toggle_target_mode(&mut self) {
+        if self.target_cursor.take().is_some() {
+            return;
+        }
+        if !self.can_shoot() || self.stroke_in_progress() || self.on_green() {
+            return;
+        }
+        let to_pin = Vec2::new(self.hole.x - self.ball.x, self.hole.y - self.ball.y).normalized();
+        let tiles = self.caddie_target_yd() / self.layout.yards_per_tile;
+        self.target_cursor = Some(Vec2::new(
+            self.ball.x + to_pin.x * tiles,
+            self.ball.y + to_pin.y * tiles,
+        ));
+        self.auto_caddie = false;
+        self.aim_at_target();
+    }
+
+    pub fn move_target(&mut self, dx: i32, dy: i32) {
+        let Some(cursor) = self.target_cursor else {
+            return;
+        };
+        let x = (cursor.x + dx as f32).clamp(1.0, self.max_x());
+        let y = (cursor.y + (dy * CELL_ASPECT) as f32).clamp(1.0, self.max_y());
+
+        let offset = Vec2::new(x - self.ball.x, y - self.ball.y);
+        let reach = self.max_reach_yd() / self.layout.yards_per_tile;
+        let cursor = if offset.length() > reach {
+            let dir = offset.normalized();
+            Vec2::new(self.ball.x + dir.x * reach, self.ball.y + dir.y * reach)
+        } else {
+            Vec2::new(x, y)
+        };
+        self.target_cursor = Some(cursor);
+        self.aim_at_target();
+    }
+
+    fn aim_at_target(&mut self) {
+        let Some(target) = self.target_cursor else {
+            return;
+        };
+        let (dx, dy) = (target.x - self.ball.x, target.y - self.ball.y);
+        self.angle = dy.atan2(dx);
+        let (club_idx, shot) = self.best_shot_for(self.target_distance_yd().unwrap_or(0.0), true);
+        self.selected_club_idx = club_idx;
+        self.selected_shot = shot;
+        self.refresh_caddie_aim();
+    }
+
+    pub fn target_distance_yd(&self) -> Option<f32> {
+        self.target_cursor.map(|target| {
+            let (dx, dy) = (target.x - self.ball.x, target.y - self.ball.y);
+            (dx * dx + dy * dy).sqrt() * self.layout.yards_per_tile
+        })
+    }
+
+    // Expected spread around the target in tiles: (side to side, short, long),
+    // from the club's dispersion and the range of strike qualities.
+    pub fn target_spread(&self) -> Option<(f32, f32, f32)> {
+        let distance = self.target_distance_yd()?;
+        let club = self.current_club();
+        let shot = self.selected_shot;
+        let (lie_carry, lie_roll, lie_dispersion) = self.lie_modifiers(self.current_surface());
+        let finish = |strike: StrikeQuality| {
+            club.carry_yd * shot.carry_mult() * strike.carry_mult() * lie_carry
+                + club.rollout_yd * shot.roll_mult() * strike.roll_mult() * lie_roll
+        };
+
+        let solid = finish(StrikeQuality::Solid);
+        let strikes = [
+            StrikeQuality::Flushed,
+            StrikeQuality::Thin,
+            StrikeQuality::Heavy,
+        ];
+        let shortest = strikes.iter().map(|s| finish(*s)).fold(solid, f32::min);
+        let longest = strikes.iter().map(|s| finish(*s)).fold(solid, f32::max);
+        let side = distance * self.spread(club, lie_dispersion);
+        Some((
+            side / self.layout.yards_per_tile,
+            (solid - shortest) / self.layout.yards_per_tile,
+            (longest - solid) / self.layout.yards_per_tile,
+        ))
+    }
+
+    // Banks the spot the current line and club play to, so it can be aimed at
+    // again from anywhere on the hole. The oldest preset makes way.
+    pub fn bank_aim_preset(&mut self) {
+        if !self.can_shoot() {
+            return;
+        }
+        let point = self.target_cursor.unwrap_or_else(|| {
+            let tiles = self.selected_shot_distance_yd() / self.layout.yards_per_tile;
+            Vec2::new(
+                self.ball.x + self.angle.cos() * tiles,
+                self.ball.y + self.angle.sin() * tiles,
+            )
+        });
+        if self.aim_presets.len() == AIM_PRESET_LIMIT {
+            self.aim_presets.remove(0);
+        }
+        self.aim_presets.push(point);
+        self.aim_preset_idx = self.aim_presets.len() - 1;
+    }
+
+    pub fn cycle_aim_preset(&mut self) {
+        if self.aim_presets.is_empty() || !self.can_shoot() || self.stroke_in_progress() {
+            return;
+        }
+        self.aim_preset_idx = (self.aim_preset_idx + 1) % self.aim_presets.len();
+        let point = self.aim_presets[self.aim_preset_idx];
+        self.angle = (point.y - self.ball.y).atan2(point.x - self.ball.x);
+    }
+
+    pub fn active_aim_preset(&self) -> Option<usize> {
+        (!self.aim_presets.is_empty()).then_some(self.aim_preset_idx)
+    }
+
+    pub fn on_fringe(&self) -> bool {
+        let green = self.layout.green_center;
+        let (dx, dy) = (self.ball.x - green.x, self.ball.y - green.y);
+        let to_edge = (dx * dx + dy * dy).sqrt() - self.layout.green_radius;
+        !self.on_green()
+            && to_edge < FRINGE_EDGE_TILES
+            && self.distance_to_hole_yd() < FRINGE_MAX_YD
+    }
+
+    // Flips the selection between the putt and the chip the fringe
+    // comparison was run with, so either preview can be looked at.
+    pub fn flip_fringe_preview(&mut self) {
+        let Some(fringe) = self.fringe else {
+            return;
+        };
+        if !self.can_shoot() || self.stroke_in_progress() {
+            return;
+        }
+        let next = if self.current_club().putter {
+            fringe.chip
+        } else {
+            fringe.putt
+        };
+        self.selected_club_idx = next.club_idx;
+        self.selected_shot = next.shot;
+        self.auto_caddie = false;
+        self.refresh_caddie_aim();
+    }
+
+    fn compare_fringe_shots(&self) -> FringeComparison {
+        let distance = self.distance_to_hole_yd();
+        let (chip_idx, chip_shot) = self.best_shot_for(distance, false);
+        FringeComparison {
+            putt: self.sample_fringe_option(CLUBS.len() - 1, ShotType::Full),
+            chip: self.sample_fringe_option(chip_idx, chip_shot),
+            angle: self.angle,
+        }
+    }
+
+    fn sample_fringe_option(&self, club_idx: usize, shot: ShotType) -> FringeOption {
+        let mut makes = 0;
+        let mut leave_ft = 0.0;
+        let mut finish = Vec2::new(0.0, 0.0);
+        for _ in 0..FRINGE_SAMPLES {
+            let mut sim = self.simulation();
+            sim.selected_club_idx = club_idx;
+            sim.selected_shot = shot;
+            sim.strike(0.0, None);
+            for _ in 0..600 {
+                if !sim.ball_in_motion() {
+                    break;
+                }
+                sim.update(0.02);
+            }
+            if sim.hole_done {
+                makes += 1;
+            }
+            leave_ft += sim.distance_to_hole_yd() * 3.0;
+            finish.x += sim.ball.x;
+            finish.y += sim.ball.y;
+        }
+        let n = FRINGE_SAMPLES as f32;
+        FringeOption {
+            club_idx,
+            shot,
+            make_rate: makes as f32 / n,
+            leave_ft: leave_ft / n,
+            finish: Vec2::new(finish.x / n, finish.y / n),
+        }
+    }
+
+    // A throwaway copy at the same spot, line and conditions, with nothing
+    // that would touch the player's stats or need input.
+    fn simulation(&self) -> Game {
+        let mut sim = Game::with_terrain(self.layout.clone(), Rc::clone(&self.terrain));
+        sim.settings = self.settings;
+        sim.settings.pressure_putts = false;
+        sim.settings.reduced_motion = true;
+        sim.conditions = self.conditions;
+        sim.bag = self.bag;
+        sim.day_form = self.day_form;
+        sim.hole = self.hole;
+        sim.ball = self.ball;
+        sim.wind = self.wind;
+        sim.auto_caddie = false;
+        sim.simulated = true;
+        sim.angle = self.angle;
+        sim
+    }
+
+    // Positive when the caddie would aim clockwise of the current line.
+    pub fn caddie_aim_offset_deg(&self) -> Option<f32> {
+        self.caddie_aim
+            .map(|aim| wrap_angle_rad(aim - self.angle).to_degrees())
+    }
+
+    pub fn toggle_auto_caddie(&mut self) {
+        self.auto_caddie = !self.auto_caddie;
+        if self.auto_caddie && self.can_shoot() {
+            self.auto_select_shot();
+        }
+    }
+
+    pub fn distance_to_hole_yd(&self) -> f32 {
+        let dx = self.hole.x - self.ball.x;
+        let dy = self.hole.y - self.ball.y;
+        (dx * dx + dy * dy).sqrt() * self.layout.yards_per_tile
+    }
+
+    // Positive when aiming clockwise (to the player's right) of the pin.
+    pub fn aim_error_rad(&self) -> f32 {
+        let to_pin = (self.hole.y - self.ball.y).atan2(self.hole.x - self.ball.x);
+        wrap_angle_rad(self.angle - to_pin)
+    }
+
+    pub fn update(&mut self, dt_secs: f32) {
+        self.last_view = self.ball_now();
+        self.post_rival_score();
+        self.update_swing(dt_secs);
+        if !self.simulated {
+            self.wind_history.record(&self.wind, dt_secs);
+        }
+        let conditions = self.conditions;
+        self.wind
+            .update(dt_secs, conditions, &mut rand::thread_rng());
+        let ball = self.ball_now().ground;
+        if let Some(round) = self.course_round.as_mut() {
+            if self.warm_up.is_none() && !self.hole_done {
+                round.hole_clock += dt_secs;
+                if let Some(ghost) = self.ghost.as_mut() {
+                    ghost.record(round.current().number, round.hole_clock, ball);
+                }
+            }
+        }
+        if self.settings.heat_map && self.heat_map.is_none() && !self.simulated {
+            self.heat_map = Some(HeatMap::build(self));
+        }
+        if let Some(meter) = self.pressure.as_mut() {
+            meter.update(dt_secs);
+        }
+        if let Some(charge) = self.putt_charge.as_mut() {
+            *charge = (*charge + dt_secs / PULL_BACK_FULL_SECS).min(1.0);
+        }
+        if let Some(presentation) = self.presentation.as_mut() {
+            presentation.elapsed += dt_secs;
+            if presentation.elapsed >= RESULT_HOLD_SECS {
+                self.presentation = None;
+            }
+        }
+        if let Some(replay) = self.replay.as_mut() {
+            replay.elapsed += dt_secs;
+            if replay.is_over() {
+                self.end_replay();
+            }
+        }
+        if let Some(elapsed) = self.celebration.as_mut() {
+            *elapsed += dt_secs;
+            if *elapsed >= CELEBRATION_SECS {
+                self.celebration = None;
+            }
+        }
+        self.update_camera(dt_secs);
+        self.update_ai(dt_secs);
+        if self.can_shoot() {
+            self.idle_secs += dt_secs;
+            self.concede_if_beaten();
+        }
+
+        if self.hole_done {
+            if self.replay.is_none() {
+                self.hole_done_secs += dt_secs;
+            }
+            if self.auto_advance_in().is_some_and(|secs| secs <= 0.0) {
+                self.next_hole();
+            }
+            return;
+        }
+        self.record_replay_frame(dt_secs);
+
+        if let Some(mut air) = self.airborne {
+            let before = air;
+            air.elapsed += dt_secs;
+            let flown = air.elapsed.min(air.duration) - before.elapsed;
+            self.sample_trail(flown, true, |into| {
+                AirState {
+                    elapsed: before.elapsed + into,
+                    ..before
+                }
+                .sky_pos()
+            });
+            if air.elapsed >= air.duration && self.jars_from_air(&air) {
+                self.airborne = None;
+                self.ball = self.hole;
+                self.velocity = Vec2::new(0.0, 0.0);
+                self.hole_done = true;
+                self.jarred = true;
+                self.record_shot_outcome();
+                self.celebrate_hole_out();
+                return;
+            }
+            if air.elapsed >= air.duration {
+                self.ball = air.landing;
+                self.airborne = None;
+                if air.bounces == 0 {
+                    self.landed_at = Some(self.ball);
+                    self.record_carry(&air);
+                }
+                if self.out_of_bounds(self.ball) || self.current_surface() == Surface::Water {
+                    self.take_penalty();
+                    self.record_shot_outcome();
+                    self.begin_presentation();
+                    self.prepare_ready_state();
+                    return;
+                }
+                if air.bounces == 0 {
+                    self.pick_up_lie(&air);
+                }
+                if let Some(hop) = self.bounce_from(&air).filter(|_| !self.buried) {
+                    self.airborne = Some(hop);
+                    return;
+                }
+                let dir = air.landing_dir();
+                let rollout_speed = air.rollout_speed * self.landing_bounce(self.current_surface());
+                self.spin_check = self.spin_check_from(&air, dir);
+                // A plugged ball stays in its pitch mark.
+                let wind = self.wind.vector();
+                let (rollout_speed, wind) = if self.buried {
+                    (0.0, Vec2::new(0.0, 0.0))
+                } else {
+                    (rollout_speed, wind)
+                };
+                self.velocity = Vec2::new(
+                    dir.x * rollout_speed + wind.x * 0.12,
+                    dir.y * rollout_speed + wind.y * 0.12,
+                );
+                self.rolling = true;
+                self.roll_time = 0.0;
+            } else {
+                self.airborne = Some(air);
+            }
+        }
+
+        if !self.rolling {
+            self.prepare_ready_state();
+            return;
+        }
+
+        let by_time = (dt_secs / ROLL_STEP_SECS).ceil();
+        let by_distance = (self.velocity.length() * dt_secs / ROLL_STEP_MAX_TILES).ceil();
+        let substeps = by_time
+            .max(by_distance)
+            .clamp(1.0, ROLL_MAX_SUBSTEPS as f32) as u32;
+        let step = dt_secs / substeps as f32;
+        self.roll_time += dt_secs;
+
+        for _ in 0..substeps {
+            let surface = self.current_surface();
+            let from = self.ball;
+            let before = self.velocity;
+            self.ball.x += self.velocity.x * step;
+            self.ball.y += self.velocity.y * step;
+
+            let speed = self.velocity.length();
+            let slope = self
+                .terrain
+                .tile(self.ball.x as i32, self.ball.y as i32)
+                .slope;
+            let drift = self.rolling_wind_accel(surface);
+            let mut accel = Vec2::new(drift.x + slope.x, drift.y + slope.y);
+            self.velocity.x += accel.x * step;
+            self.velocity.y += accel.y * step;
+
+            if let Some(check) = self.spin_check.as_mut().filter(|c| c.gripping()) {
+                self.velocity.x -= check.dir.x * check.pull * step;
+                self.velocity.y -= check.dir.y * check.pull * step;
+                accel.x -= check.dir.x * check.pull;
+                accel.y -= check.dir.y * check.pull;
+                check.grip_secs -= step;
+            }
+
+            let drag = self.surface_drag(surface) * step;
+            if speed > 0.0001 {
+                let drag_scale = (1.0 - drag).max(0.0);
+                self.velocity.x *= drag_scale;
+                self.velocity.y *= drag_scale;
+            }
+            if self.settings.energy_audit {
+                self.audit_energy(before, accel, step, surface);
+            }
+
+            // The cup is checked against the whole substep, nearest point
+            // first, so it's found before anything further along the path.
+            let nearest = nearest_on_segment(from, self.ball, self.hole);
+            let passed_by = (nearest.x - self.hole.x).hypot(nearest.y - self.hole.y);
             let now_speed = self.velocity.length();
-            let on_green = self.current_surface() == Surface::Green;
+            let on_green =
+                self.terrain.surface(nearest.x as i32, nearest.y as i32) == Surface::Green;
 
             let sink_radius = if on_green { 0.56 } else { 0.42 };
             let soft_sink_radius = if on_green { 1.0 } else { 0.82 };
             let soft_sink_speed = if on_green { 1.45 } else { 1.15 };
 
-            if distance_to_hole < sink_radius
-                || (distance_to_hole < soft_sink_radius && now_speed < soft_sink_speed)
-            {
-                self.ball = self.hole;
-                self.velocity = Vec2::new(0.0, 0.0);
-                self.rolling = false;
-                self.hole_done = true;
-                self.roll_time = 0.0;
-                break;
+            if passed_by < sink_radius
+                || (passed_by < soft_sink_radius && now_speed < soft_sink_speed)
+            {
+                self.ball = self.hole;
+                self.velocity = Vec2::new(0.0, 0.0);
+                self.rolling = false;
+                self.hole_done = true;
+                self.roll_time = 0.0;
+                break;
+            }
+
+            if self.out_of_bounds(self.ball) || self.current_surface() == Surface::Water {
+                self.take_penalty();
+                break;
+            }
+
+            let dx = self.ball.x - self.hole.x;
+            let dy = self.ball.y - self.hole.y;
+            let distance_to_hole = (dx * dx + dy * dy).sqrt();
+            if distance_to_hole < 1.12 && now_speed >= soft_sink_speed {
+                let nx = dx / distance_to_hole.max(0.001);
+                let ny = dy / distance_to_hole.max(0.001);
+                self.velocity.x = self.velocity.x * -0.2 + nx * 0.45;
+                self.velocity.y = self.velocity.y * -0.2 + ny * 0.45;
+            }
+
+            let to = self.ball;
+            self.sample_trail(step, false, |into| {
+                let t = into / step;
+                Vec2::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t)
+            });
+
+            // A ball can't come to rest on a contour; it keeps feeding downhill.
+            let gripping = self.spin_check.is_some_and(|c| c.gripping());
+            let resting =
+                (now_speed < 0.12 && slope.length() < GREEN_HOLD_SLOPE && !gripping) || self.buried;
+            if resting || self.roll_time > 12.0 {
+                self.velocity = Vec2::new(0.0, 0.0);
+                self.rolling = false;
+                self.roll_time = 0.0;
+                break;
+            }
+        }
+
+        if !self.rolling {
+            if self.penalty_drop.is_none() {
+                self.push_trail(self.ball, false);
+            }
+            self.record_shot_outcome();
+        }
+        if self.hole_done {
+            self.celebrate_hole_out();
+        }
+        if !self.rolling && !self.hole_done {
+            self.begin_presentation();
+        }
+        self.prepare_ready_state();
+    }
+
+    // Anything but the cup's rim that leaves the ball with more energy than
+    // the slope, wind and backspin gave it is an integrator bug: it's
+    // counted, logged and stops a debug build.
+    fn audit_energy(&mut self, before: Vec2, accel: Vec2, step: f32, surface: Surface) {
+        let Some(imbalance) = energy::audit(before, self.velocity, accel, step) else {
+            return;
+        };
+        self.energy_imbalances += 1;
+        let _ = energy::log(&imbalance, self.ball, surface.name());
+        debug_assert!(
+            false,
+            "roll gained {} of energy with only {} of work",
+            imbalance.gained, imbalance.work
+        );
+    }
+
+    // The edges of the map are the hole's boundary.
+    pub fn out_of_bounds(&self, pos: Vec2) -> bool {
+        pos.x < 1.0 || pos.y < 1.0 || pos.x > self.max_x() || pos.y > self.max_y()
+    }
+
+    // One stroke, then the ball goes where the rules send it: back to where
+    // the shot was hit, or for a pond, the drop zone or a drop near where it
+    // went in, whichever the player picks. It is at rest after the drop.
+    fn take_penalty(&mut self) {
+        let from = self.shot_history.last().map_or(self.layout.tee, |s| s.from);
+        let (kind, at, choice) = if self.out_of_bounds(self.ball) {
+            let at = rules::crossing_point(from, self.ball, |p| self.out_of_bounds(p));
+            (PenaltyKind::OutOfBounds, at, rules::out_of_bounds(from))
+        } else {
+            let at = self.ball;
+            let in_water = |p: Vec2| self.terrain.surface(p.x as i32, p.y as i32) == Surface::Water;
+            let crossed = rules::crossing_point(from, at, in_water);
+            let zone = self.layout.pond_at(at.x, at.y).and_then(|pond| pond.drop);
+            let choice =
+                rules::penalty_area(from, crossed, self.hole, zone, |p| self.drop_playable(p));
+            (PenaltyKind::Water, at, choice)
+        };
+        let drop = choice.chosen();
+        self.push_trail(at, false);
+        self.penalty_drop = Some(PenaltyDrop {
+            kind,
+            at,
+            relief: drop.relief,
+        });
+        self.relief = Some(choice).filter(ReliefChoice::is_choice);
+        self.ball = drop.spot;
+        self.velocity = Vec2::new(0.0, 0.0);
+        self.rolling = false;
+        self.roll_time = 0.0;
+        self.strokes += drop.penalty;
+        self.penalty_strokes += drop.penalty;
+    }
+
+    // Adds the points that fall within the next `secs` of the ball's path;
+    // `at` gives its position that far into the span.
+    fn sample_trail(&mut self, secs: f32, airborne: bool, at: impl Fn(f32) -> Vec2) {
+        self.trail_clock += secs.max(0.0);
+        while self.trail_clock >= TRAIL_SAMPLE_SECS {
+            self.trail_clock -= TRAIL_SAMPLE_SECS;
+            self.push_trail(at(secs - self.trail_clock), airborne);
+        }
+    }
+
+    fn push_trail(&mut self, pos: Vec2, airborne: bool) {
+        if self.trail.len() >= TRAIL_LEN {
+            self.trail.remove(0);
+        }
+        let spun_back = !airborne && self.rolling_back();
+        self.trail.push(TrailPoint {
+            pos,
+            airborne,
+            spun_back,
+        });
+    }
+
+    fn record_replay_frame(&mut self, dt_secs: f32) {
+        if self.simulated {
+            return;
+        }
+        let (ball, height) = match self.airborne {
+            Some(air) => (air.ground_pos(), air.arc_height()),
+            None if self.rolling => (self.ball, 0.0),
+            None => return,
+        };
+        self.replay_buffer.push(ReplayFrame {
+            ball,
+            height,
+            dt: dt_secs,
+        });
+    }
+
+    // A shot holed from off the green gets its last couple of seconds played
+    // again in slow motion first; jarred shots celebrate once it is over.
+    fn celebrate_hole_out(&mut self) {
+        let from_off_green = self
+            .shot_history
+            .last()
+            .is_some_and(|shot| shot.lie != Surface::Green);
+        let replay = from_off_green
+            && !self.simulated
+            && !self.settings.reduced_motion
+            && !self.replay_buffer.is_empty();
+        if replay {
+            self.replay = Some(self.replay_buffer.take_replay(self.hole, self.jarred));
+        } else if self.jarred {
+            self.celebration = Some(0.0);
+        }
+    }
+
+    // The current shot's last moments, kept hold of after play moves on.
+    pub fn snapshot_replay(&self) -> Option<Replay> {
+        match &self.replay {
+            Some(replay) => Some(Replay {
+                elapsed: 0.0,
+                ..replay.clone()
+            }),
+            None => self.replay_buffer.snapshot(self.jarred),
+        }
+    }
+
+    pub fn end_replay(&mut self) {
+        if let Some(replay) = self.replay.take() {
+            if replay.jarred {
+                self.celebration = Some(0.0);
+            }
+        }
+    }
+
+    // The hop a landing ball takes, if it has one left in it: higher off
+    // firm ground, lower and shorter with backspin, never out of the sand.
+    fn bounce_from(&self, air: &AirState) -> Option<AirState> {
+        if air.bounces >= MAX_BOUNCES {
+            return None;
+        }
+        let surface = self.current_surface();
+        let (keep, kick) = surface.bounce();
+        let firmness = self.landing_bounce(surface);
+        let apex = air.apex * keep * firmness * (1.0 - 0.5 * air.spin);
+        if apex < MIN_BOUNCE_HEIGHT {
+            return None;
+        }
+        let length = apex * kick * firmness * (1.0 - 0.6 * air.spin);
+        let dir = air.landing_dir();
+        Some(AirState {
+            start: air.landing,
+            landing: Vec2::new(
+                air.landing.x + dir.x * length,
+                air.landing.y + dir.y * length,
+            ),
+            elapsed: 0.0,
+            duration: BOUNCE_SECS_AT_APEX * (apex / DRIVER_APEX).sqrt(),
+            apex,
+            bounces: air.bounces + 1,
+            curve: 0.0,
+            ..*air
+        })
+    }
+
+    fn spin_check_from(&self, air: &AirState, dir: Vec2) -> Option<SpinCheck> {
+        if air.spin < SPIN_CHECK_MIN || !self.on_green() {
+            return None;
+        }
+        // Soft greens let the grooves bite harder.
+        let grab = (2.0 - self.landing_bounce(Surface::Green)).max(0.5);
+        Some(SpinCheck {
+            dir,
+            pull: SPIN_PULL * (air.spin - SPIN_CHECK_MIN) / (1.0 - SPIN_CHECK_MIN) * grab,
+            grip_secs: SPIN_GRIP_SECS,
+        })
+    }
+
+    pub fn rolling_back(&self) -> bool {
+        self.rolling
+            && self
+                .spin_check
+                .is_some_and(|c| self.velocity.x * c.dir.x + self.velocity.y * c.dir.y < 0.0)
+    }
+
+    // How much livelier than usual a ball comes off the ground it lands on.
+    fn landing_bounce(&self, surface: Surface) -> f32 {
+        let green = if surface == Surface::Green {
+            self.layout.green.bounce_mult()
+        } else {
+            1.0
+        };
+        green * self.settings.course_conditions.bounce_mult(surface)
+    }
+
+    // A ball that comes down on the cup without too much pace behind it
+    // drops straight in rather than skipping past.
+    fn jars_from_air(&self, air: &AirState) -> bool {
+        let dx = air.landing.x - self.hole.x;
+        let dy = air.landing.y - self.hole.y;
+        (dx * dx + dy * dy).sqrt() < AIR_HOLE_OUT_RADIUS
+            && air.rollout_speed < AIR_HOLE_OUT_MAX_SPEED
+    }
+
+    // How far the club flew against its book carry from that lie and swing,
+    // which is what the shadow rival copies.
+    fn record_carry(&mut self, air: &AirState) {
+        let Some(shot) = self.shot_history.last() else {
+            return;
+        };
+        if self.warm_up.is_some() {
+            return;
+        }
+        let club = self.current_club();
+        let (lie_carry, _, _) = lie_modifiers(shot.lie, shot.buried);
+        let book_yd = club.carry_yd * self.selected_shot.carry_mult() * lie_carry;
+        let carried_yd = Vec2::new(air.landing.x - air.start.x, air.landing.y - air.start.y)
+            .length()
+            * self.layout.yards_per_tile;
+        if book_yd > 0.0 {
+            self.stats
+                .record_carry(self.selected_club_idx, carried_yd / book_yd);
+        }
+    }
+
+    fn record_shot_outcome(&mut self) {
+        // Range balls count for nothing but the warm-up itself.
+        if self.warm_up.is_some() {
+            let miss = self.finish_miss();
+            if let Some(warm_up) = self.warm_up.as_mut() {
+                warm_up.finish_ball(miss);
             }
-
-            if distance_to_hole < 1.12 && now_speed >= soft_sink_speed {
-                let nx = dx / distance_to_hole.max(0.001);
-                let ny = dy / distance_to_hole.max(0.001);
-                self.velocity.x = self.velocity.x * -0.2 + nx * 0.45;
-                self.velocity.y = self.velocity.y * -0.2 + ny * 0.45;
+            self.hole_done = false;
+            return;
+        }
+        if !self.simulated {
+            if let Some((moment, caption)) = Moment::detect(self) {
+                let _ = self.gallery.add(Snapshot::capture(self, moment, caption));
             }
-
-            if self.trail.len() >= TRAIL_LEN {
-                self.trail.remove(0);
+        }
+        let Some(shot) = self.shot_history.last() else {
+            return;
+        };
+        let (putter, from_yd) = (shot.putter, shot.to_pin_yd);
+        if putter && shot.lie == Surface::Green {
+            self.stats.record_putt(from_yd * 3.0, self.hole_done);
+        } else if !putter {
+            if let Some(miss) = self.finish_miss() {
+                self.stats.record_approach(from_yd, miss);
             }
-            self.trail.push(self.ball);
+        }
+        if self.hole_done {
+            self.finish_hole(false);
+        }
+    }
 
-            if now_speed < 0.12 || self.roll_time > 12.0 {
-                self.velocity = Vec2::new(0.0, 0.0);
-                self.rolling = false;
-                self.roll_time = 0.0;
-                break;
+    // Where the last shot finished against the pin, along and across the
+    // line it was played on.
+    fn finish_miss(&self) -> Option<ApproachMiss> {
+        let shot = self.shot_history.last()?;
+        let line = Vec2::new(self.hole.x - shot.from.x, self.hole.y - shot.from.y).normalized();
+        // A penalised ball missed where it went in or out, not where it was
+        // dropped.
+        let finish = self.penalty_drop.map_or(self.ball, |d| d.at);
+        let (dx, dy) = (finish.x - self.hole.x, finish.y - self.hole.y);
+        let tile_ft = self.layout.yards_per_tile * 3.0;
+        Some(ApproachMiss {
+            long_ft: (dx * line.x + dy * line.y) * tile_ft,
+            right_ft: (dy * line.x - dx * line.y) * tile_ft,
+        })
+    }
+
+    fn finish_hole(&mut self, conceded: bool) {
+        if self.challenge != Challenge::None {
+            let to_par = self.strokes as i32 - self.par as i32;
+            self.stats.record_challenge(self.challenge.key(), to_par);
+            self.payout += shop::challenge_prize(to_par);
+        }
+        self.settle_match_hole(conceded);
+        let _ = self.stats.save();
+        if let Some(foursomes) = self.foursomes.as_mut() {
+            foursomes.holes += 1;
+        }
+        self.profile.record_hole(self.strokes, self.hole_key());
+        self.tutorial_done(TutorialStep::Putt);
+        let mut tally = RoundTally::of_hole(
+            &self.shot_history,
+            self.strokes,
+            self.par,
+            self.penalty_strokes,
+        );
+        // Without a course every hole is a round of its own.
+        let round_over = match self.course_round.as_mut() {
+            Some(round) => {
+                round.record(self.strokes);
+                round.tally.add(tally);
+                if let Some(ghost) = self.ghost.as_mut() {
+                    let number = round.current().number;
+                    ghost.finish_hole(number, self.strokes, round.hole_clock, self.ball);
+                }
+                let (strokes, par) = round.card.total();
+                if round.is_last_hole() {
+                    self.payout +=
+                        shop::round_purse(round.order.len(), strokes as i32 - par as i32);
+                    tally = round.tally;
+                    if let Some(tournament) = self.tournament.as_mut() {
+                        tournament.record_round(strokes as i32 - par as i32, round.order.len());
+                        let _ = tournament.save();
+                    }
+                    if let Some(ghost) = self.ghost.as_mut() {
+                        let _ = ghost.finish_round(strokes as i32 - par as i32);
+                    }
+                }
+                round.is_last_hole().then_some((strokes, par))
             }
+            None => Some((self.strokes, self.par)),
+        };
+        if let Some((strokes, par)) = round_over {
+            self.round_awards = awards::earned(&tally);
+            self.round_tally = tally;
+            self.profile.record_round(RoundRecord {
+                holes: tally.holes,
+                strokes,
+                to_par: strokes as i32 - par as i32,
+                titles: self.round_awards.clone(),
+            });
         }
+        self.profile.earn(self.payout);
+        let _ = self.profile.save();
+    }
 
-        if self.can_shoot() && self.auto_caddie {
-            self.auto_select_shot();
+    // What the profile keeps the best score on this hole under: its course
+    // and number, or for a generated hole only its par. Nothing on the range.
+    pub fn hole_key(&self) -> Option<String> {
+        if self.warm_up.is_some() {
+            return None;
+        }
+        Some(match &self.course_round {
+            Some(round) => format!("{} #{}", round.course.name, round.current().number),
+            None => format!("par {}", self.par),
+        })
+    }
+
+    // Once the hole is finished the full view doubles as the route summary.
+    pub fn wants_green_zoom(&self) -> bool {
+        if let Some(presentation) = &self.presentation {
+            return presentation.zoomed;
+        }
+        self.on_green() && !self.hole_done
+    }
+
+    fn update_camera(&mut self, dt_secs: f32) {
+        if let Some(blend) = self.camera_blend {
+            let blend = blend + dt_secs / CAMERA_TRANSITION_SECS;
+            self.camera_blend = if blend >= 1.0 { None } else { Some(blend) };
+        }
+
+        let wants_zoom = self.wants_green_zoom();
+        if wants_zoom != self.view_zoomed {
+            self.view_zoomed = wants_zoom;
+            let instant = self.settings.instant_camera || self.settings.reduced_motion;
+            self.camera_blend = if instant { None } else { Some(0.0) };
+        }
+    }
+
+    pub fn toggle_instant_camera(&mut self) {
+        self.settings.toggle_instant_camera();
+        self.camera_blend = None;
+    }
+
+    pub fn skip_presentation(&mut self) {
+        self.presentation = None;
+    }
+
+    fn begin_presentation(&mut self) {
+        let Some(shot) = self.shot_history.last() else {
+            return;
+        };
+        let dx = self.ball.x - shot.from.x;
+        let dy = self.ball.y - shot.from.y;
+        let travelled = (dx * dx + dy * dy).sqrt() * self.layout.yards_per_tile;
+        let strike = self
+            .strike_quality
+            .map(|s| format!(" ({})", s.name().to_lowercase()))
+            .unwrap_or_default();
+        let summary = match self.penalty_drop {
+            Some(penalty) => format!(
+                "{}{}: {} - {:.0} yd to pin",
+                shot.club,
+                strike,
+                penalty.announcement(),
+                self.distance_to_hole_yd()
+            ),
+            None => format!(
+                "{}{} went {:.0} yd, {} - {:.0} yd to pin",
+                shot.club,
+                strike,
+                travelled,
+                self.current_surface().name(),
+                self.distance_to_hole_yd()
+            ),
+        };
+        let landing = self.landing_readout();
+        let summary = match &landing {
+            Some((_, readout)) => format!("{}, {}", summary, readout),
+            None => summary,
+        };
+        self.presentation = Some(ShotPresentation {
+            zoomed: self.shot_view_zoomed || landing.is_some(),
+            elapsed: 0.0,
+            summary,
+            landing: landing.map(|(at, _)| at),
+        });
+    }
+
+    // For approaches that found the green: where the ball came down against
+    // the pin, and how far it released or spun back from there, both along
+    // the line of the shot.
+    fn landing_readout(&self) -> Option<(Vec2, String)> {
+        let shot = self.shot_history.last()?;
+        let landing = self.landed_at?;
+        let landed_on_green =
+            self.terrain.surface(landing.x as i32, landing.y as i32) == Surface::Green;
+        if shot.putter || !(landed_on_green || self.on_green()) {
+            return None;
         }
+        let line = Vec2::new(landing.x - shot.from.x, landing.y - shot.from.y).normalized();
+        let tile_ft = self.layout.yards_per_tile * 3.0;
+        let along =
+            |from: Vec2, to: Vec2| ((to.x - from.x) * line.x + (to.y - from.y) * line.y) * tile_ft;
+        let past_ft = along(self.hole, landing);
+        let release_ft = along(landing, self.ball);
+        let landed = if past_ft >= 1.0 {
+            format!("landed {:.0} ft past", past_ft)
+        } else if past_ft <= -1.0 {
+            format!("landed {:.0} ft short", -past_ft)
+        } else {
+            "landed pin-high".to_string()
+        };
+        let finish = if release_ft >= 1.0 {
+            format!("released {:.0} ft", release_ft)
+        } else if release_ft <= -1.0 {
+            format!("spun back {:.0} ft", -release_ft)
+        } else {
+            "stopped dead".to_string()
+        };
+        Some((landing, format!("{}, {}", landed, finish)))
     }
 
     pub fn hit_ball(&mut self) {
-        if !self.can_shoot() {
+        if !self.can_shoot() || self.ai_to_play() {
+            return;
+        }
+
+        // Pressure putts take two presses: start the meter, then stop it.
+        let mut pressure_offset = 0.0;
+        if let Some(meter) = self.pressure.take() {
+            pressure_offset = meter.miss_offset_rad();
+            self.pressure_result = Some(PressureResult::from_offset(pressure_offset));
+        } else if self.settings.pressure_putts && self.is_pressure_putt() {
+            let mut period = 0.6 + self.distance_to_hole_yd() * 0.06;
+            if self.settings.reduced_motion {
+                period *= REDUCED_MOTION_METER_SLOWDOWN;
+            }
+            self.pressure = Some(PressureMeter::new(period));
             return;
+        } else {
+            self.pressure_result = None;
         }
 
+        self.strike(pressure_offset, None);
+    }
+
+    // `putt_power` is the pull-back fraction of the putter's full rollout;
+    // without it the putter picks its own pace from the distance to the cup.
+    pub fn strike(&mut self, aim_offset: f32, putt_power: Option<f32>) {
+        if !self.simulated {
+            self.pre_shot = SavedRound::of(self);
+        }
         self.golfer_anchor = self.ball;
+        self.shot_view_zoomed = self.on_green();
+        self.presentation = None;
         self.start_swing_animation();
 
+        let played = self.strokes - self.penalty_strokes;
+        if let Some(foursomes) = self.foursomes.as_mut() {
+            foursomes.record_shot(played);
+        }
         self.strokes += 1;
+        self.tutorial_done(TutorialStep::Swing);
+        self.idle_secs = 0.0;
         self.trail.clear();
+        self.trail_clock = 0.0;
+        self.replay_buffer.clear();
+        self.landed_at = None;
+        self.penalty_drop = None;
+        self.spin_check = None;
+        // A plugged ball plays out of its plug on this stroke and no further.
+        let buried = std::mem::take(&mut self.buried);
+        self.relief = None;
+        let mud = std::mem::take(&mut self.mud_ball);
+        self.caddie_aim = None;
+        self.caddie_advice = None;
+        self.target_cursor = None;
+        self.fringe = None;
+        self.hazard_warning = None;
 
         let mut rng = rand::thread_rng();
-        self.wind = (self.wind + rng.gen_range(-0.14..0.14)).clamp(-0.5, 0.5);
+        self.wind.wander(self.conditions, &mut rng);
 
         let lie = self.current_surface();
-        let (lie_carry, lie_roll, lie_dispersion) = self.lie_modifiers(lie);
+        let (lie_carry, lie_roll, lie_dispersion) = lie_modifiers(lie, buried);
+        let stance = self.stance().unwrap_or_default();
 
         let club = self.current_club();
+        self.shot_history.push(ShotRecord {
+            from: self.ball,
+            lie,
+            buried,
+            club: club.name,
+            putter: club.putter,
+            to_pin_yd: self.distance_to_hole_yd(),
+            wind_mph: self.wind.mph(),
+            stroke: self.strokes,
+        });
         let shot = if club.putter {
             ShotType::Full
         } else {
@@ -572,32 +2986,75 @@ impl Game {
         let dispersion = if club.putter && self.on_green() {
             0.0025
         } else {
-            club.dispersion + lie_dispersion
+            self.spread(club, lie_dispersion)
         };
-        let launch_angle = wrap_angle_rad(self.angle + rng.gen_range(-dispersion..dispersion));
+        let roll: f32 = rng.gen_range(-1.0..1.0);
+        if let Some(warm_up) = self.warm_up.as_mut().filter(|_| !club.putter) {
+            warm_up.record_strike(roll);
+        }
+        let launch_angle =
+            wrap_angle_rad(self.angle + aim_offset + roll * dispersion + stance.pull_rad());
         let dir = Vec2::new(launch_angle.cos(), launch_angle.sin()).normalized();
 
         if club.putter {
-            let rollout_yd = self.putter_rollout_target_yd(club);
-            let rollout_tiles = (rollout_yd * lie_roll) / YARDS_PER_TILE;
-            let rollout_speed = (rollout_tiles * 2.2).max(0.85);
+            let rollout_yd = match putt_power {
+                Some(power) => club.rollout_yd * power,
+                None => self.putter_rollout_target_yd(club),
+            };
+            let rollout_tiles = (rollout_yd * lie_roll) / self.layout.yards_per_tile;
+            let mut rollout_speed = (rollout_tiles * 2.2).max(0.85);
+            if self.on_green() && putt_power.is_none() {
+                rollout_speed *= self.layout.green.putt_speed_mult();
+            }
+            let wind = self.wind.vector();
             self.velocity = Vec2::new(
-                dir.x * rollout_speed + self.wind * 0.035,
-                dir.y * rollout_speed,
+                dir.x * rollout_speed + wind.x * 0.035,
+                dir.y * rollout_speed + wind.y * 0.035,
             );
             self.rolling = true;
             self.roll_time = 0.0;
+            self.strike_quality = None;
             return;
         }
 
-        let carry_tiles = (club.carry_yd * shot.carry_mult() * lie_carry) / YARDS_PER_TILE;
-        let rollout_tiles = (club.rollout_yd * shot.roll_mult() * lie_roll) / YARDS_PER_TILE;
+        let strike = StrikeQuality::from_roll(roll);
+        self.strike_quality = Some(strike);
+        let shadow_carry = self
+            .shadow
+            .as_ref()
+            .map_or(1.0, |s| s.carry_mult(self.selected_club_idx));
+        let carry_tiles = (club.carry_yd
+            * shot.carry_mult()
+            * strike.carry_mult()
+            * lie_carry
+            * shadow_carry
+            * stance.carry_mult())
+            / self.layout.yards_per_tile;
+        let rollout_tiles = (club.rollout_yd
+            * shot.roll_mult()
+            * strike.roll_mult()
+            * lie_roll
+            * stance.roll_mult()
+            * self.selected_shape.roll_mult())
+            / self.layout.yards_per_tile;
         let rollout_speed = rollout_tiles * 2.0;
-        let wind_push_tiles = self.wind * (club.carry_yd / YARDS_PER_TILE) * 0.08;
+        let spin = (1.0 - rollout_tiles / carry_tiles.max(0.1) * 12.0).clamp(0.0, 1.0);
+        let push = wind::carry_push(
+            self.wind.vector(),
+            dir,
+            club.carry_yd / self.layout.yards_per_tile,
+        );
+        let mud_curve = if mud {
+            let sign = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+            sign * carry_tiles * rng.gen_range(MUD_CURVE_MIN..MUD_CURVE_MAX)
+        } else {
+            0.0
+        };
+        let curve = mud_curve + carry_tiles * self.selected_shape.swerve();
 
         let landing = Vec2::new(
-            self.ball.x + dir.x * carry_tiles + wind_push_tiles,
-            self.ball.y + dir.y * carry_tiles,
+            self.ball.x + dir.x * carry_tiles + push.x - dir.y * curve,
+            self.ball.y + dir.y * carry_tiles + push.y + dir.x * curve,
         );
 
         self.airborne = Some(AirState {
@@ -605,12 +3062,93 @@ impl Game {
             landing,
             elapsed: 0.0,
             duration: club.air_time * shot.arc_mult(),
-            apex: club.apex * shot.arc_mult(),
+            apex: club.apex * shot.arc_mult() * strike.arc_mult() * stance.arc_mult(),
             rollout_speed,
+            spin,
+            bounces: 0,
+            curve,
+        });
+    }
+
+    fn prepare_ready_state(&mut self) {
+        if !self.can_shoot() || self.simulated {
+            return;
+        }
+        if self.presentation.is_none() && self.warm_up.as_ref().is_some_and(|w| w.ball_out) {
+            self.next_range_ball();
+        }
+        let stale = !self.fringe.is_some_and(|f| f.angle == self.angle);
+        if stale && self.on_fringe() {
+            self.fringe = Some(self.compare_fringe_shots());
+        }
+        if self.auto_caddie {
+            self.auto_select_shot();
+        } else if self.caddie_aim.is_none() {
+            self.refresh_caddie_aim();
+        }
+        if self.has_queued_input() {
+            self.apply_queued_input();
+        }
+        self.update_hazard_warning();
+    }
+
+    // The landing footprint is only rebuilt when the club, swing, lie or wind
+    // change; aim changes just rotate it over the map again.
+    fn update_hazard_warning(&mut self) {
+        let club = self.current_club();
+        if club.putter {
+            self.hazard_warning = None;
+            return;
+        }
+        let lie = self.current_surface();
+        let key = FootprintKey {
+            club_idx: self.selected_club_idx,
+            shot: self.selected_shot,
+            shape: self.selected_shape,
+            lie,
+            buried: self.buried,
+        };
+        if self.footprint.as_ref().is_none_or(|f| f.key != key) {
+            let (lie_carry, _, lie_dispersion) = self.lie_modifiers(lie);
+            self.footprint = Some(Footprint::build(
+                key,
+                club,
+                lie_carry,
+                self.spread(club, lie_dispersion),
+                self.layout.yards_per_tile,
+            ));
+        }
+        self.hazard_warning = self.footprint.as_ref().and_then(|f| {
+            f.hazard_warning(&self.terrain, self.ball, self.angle, self.wind.vector())
         });
     }
 
+    // Inputs pressed while the ball was moving are replayed once it settles,
+    // after the auto-caddie has picked its club so manual overrides win.
+    fn apply_queued_input(&mut self) {
+        let aim_steps = std::mem::take(&mut self.queued_aim_steps);
+        let club_steps = std::mem::take(&mut self.queued_club_steps);
+        let shot_cycles = std::mem::take(&mut self.queued_shot_cycles);
+        let shape_cycles = std::mem::take(&mut self.queued_shape_cycles);
+
+        if aim_steps != 0 {
+            self.adjust_aim(aim_steps);
+        }
+        if club_steps != 0 {
+            self.cycle_club(club_steps);
+        }
+        for _ in 0..shot_cycles % ShotType::NON_PUTTER.len() as u32 {
+            self.cycle_shot_type();
+        }
+        for _ in 0..shape_cycles % ShotShape::ALL.len() as u32 {
+            self.cycle_shot_shape();
+        }
+    }
+
     fn start_swing_animation(&mut self) {
+        if self.settings.reduced_motion {
+            return;
+        }
         self.swing_active = true;
         self.swing_frame = 0;
         self.swing_timer = 0.0;
@@ -638,37 +3176,206 @@ impl Game {
         let target = self.distance_to_hole_yd();
         if self.on_green() {
             (target * 1.35).clamp(4.0, club.rollout_yd)
+        } else if self.on_fringe() {
+            // Slower grass on the way in eats less than the green's read adds.
+            (target * 0.8).clamp(4.0, club.rollout_yd)
         } else {
             club.rollout_yd
         }
     }
 
+    // A full shot's dispersion from this lie on the day's form.
+    fn spread(&self, club: ClubSpec, lie_dispersion: f32) -> f32 {
+        let form = self.day_form.unwrap_or_default();
+        (club.dispersion + lie_dispersion) * form.dispersion_mult
+    }
+
     fn lie_modifiers(&self, lie: Surface) -> (f32, f32, f32) {
-        match lie {
-            Surface::Green => (1.0, 1.0, 0.002),
-            Surface::Fairway => (1.0, 1.0, 0.004),
-            Surface::Rough => (0.82, 0.72, 0.028),
-            Surface::Bunker => (0.65, 0.46, 0.045),
+        lie_modifiers(lie, self.buried)
+    }
+
+    pub fn lie_name(&self) -> String {
+        let name = lie_name(self.current_surface(), self.buried);
+        if self.mud_ball {
+            format!("{} — mud ball", name)
+        } else {
+            name
         }
     }
 
-    fn auto_select_shot(&mut self) {
-        let distance = self.distance_to_hole_yd();
-        let lie = self.current_surface();
-        let (lie_carry, lie_roll, _) = self.lie_modifiers(lie);
+    // Free relief from an embedded ball: lifted, cleaned and dropped just
+    // behind its pitch mark, or right beside it when there's no room there
+    // outside the rough.
+    fn relief_drop(&self) -> Vec2 {
+        let back = Vec2::new(self.ball.x - self.hole.x, self.ball.y - self.hole.y).normalized();
+        let drop = Vec2::new(
+            self.ball.x + back.x * RELIEF_DROP_TILES,
+            self.ball.y + back.y * RELIEF_DROP_TILES,
+        );
+        if self.drop_playable(drop) {
+            drop
+        } else {
+            self.ball
+        }
+    }
 
-        if self.on_green() {
-            self.selected_club_idx = CLUBS.len() - 1;
-            self.selected_shot = ShotType::Full;
+    // Relief is taken in the general area: through the green, off the
+    // putting surface and out of the hazards.
+    fn drop_playable(&self, spot: Vec2) -> bool {
+        !self.out_of_bounds(spot)
+            && matches!(
+                self.terrain.surface(spot.x as i32, spot.y as i32),
+                Surface::Rough | Surface::HeavyRough | Surface::Fairway | Surface::Fringe
+            )
+    }
+
+    // For a stroke the ball can be taken out of any lie but the green. In a
+    // bunker the drops near the ball have to stay in the sand.
+    pub fn declare_unplayable(&mut self) {
+        if !self.can_shoot() || self.on_green() || self.warm_up.is_some() {
+            return;
+        }
+        let from = self.shot_history.last().map_or(self.layout.tee, |s| s.from);
+        let in_bunker = self.current_surface() == Surface::Bunker;
+        let choice = rules::unplayable(from, self.ball, self.hole, |p| {
+            if in_bunker {
+                !self.out_of_bounds(p)
+                    && self.terrain.surface(p.x as i32, p.y as i32) == Surface::Bunker
+            } else {
+                self.drop_playable(p)
+            }
+        });
+        let drop = choice.chosen();
+        self.penalty_drop = Some(PenaltyDrop {
+            kind: PenaltyKind::Unplayable,
+            at: self.ball,
+            relief: drop.relief,
+        });
+        self.relief = Some(choice);
+        self.ball = drop.spot;
+        self.buried = false;
+        self.mud_ball = false;
+        self.strokes += drop.penalty;
+        self.penalty_strokes += drop.penalty;
+        self.caddie_aim = None;
+        self.fringe = None;
+        self.prepare_ready_state();
+    }
+
+    // Moves the ball to another of the options on offer, settling up any
+    // difference in penalty strokes.
+    pub fn choose_relief(&mut self, idx: usize) {
+        if !self.can_shoot() {
+            return;
+        }
+        let Some(choice) = self.relief.as_mut().filter(|c| idx < c.options.len()) else {
             return;
+        };
+        let old = choice.chosen();
+        choice.chosen = idx;
+        let new = choice.chosen();
+        self.strokes = self.strokes - old.penalty + new.penalty;
+        self.penalty_strokes = self.penalty_strokes - old.penalty + new.penalty;
+        self.ball = new.spot;
+        self.buried = new.relief == Relief::AsItLies;
+        if let Some(penalty) = self.penalty_drop.as_mut() {
+            penalty.relief = new.relief;
+        }
+        self.caddie_aim = None;
+        self.fringe = None;
+        self.prepare_ready_state();
+    }
+
+    // Only full swings feel the slope; putts and balls on flat ground play as
+    // they lie.
+    pub fn stance(&self) -> Option<Stance> {
+        if self.current_club().putter || self.on_green() {
+            return None;
+        }
+        let downhill = self
+            .terrain
+            .tile(self.ball.x as i32, self.ball.y as i32)
+            .slope;
+        Some(Stance::on(downhill, self.angle)).filter(|stance| !stance.is_flat())
+    }
+
+    // Where a shot first comes down decides whether it plugs in the sand or,
+    // on soft ground, comes up muddy.
+    fn pick_up_lie(&mut self, air: &AirState) {
+        let mut rng = rand::thread_rng();
+        let surface = self.current_surface();
+        let softness = 1.0
+            - self
+                .settings
+                .course_conditions
+                .fairway_firmness
+                .clamp(0.0, 1.0);
+        let plug_chance = match surface {
+            Surface::Bunker => buried_chance(air.apex),
+            Surface::Rough | Surface::HeavyRough => buried_chance(air.apex) * softness,
+            _ => 0.0,
+        };
+        self.buried = rng.gen::<f32>() < plug_chance;
+        self.relief = (self.buried && surface != Surface::Bunker)
+            .then(|| rules::embedded(self.ball, self.relief_drop()));
+        self.mud_ball = self.settings.mud_balls
+            && rng.gen::<f32>() < self.settings.course_conditions.mud_chance(surface);
+    }
+
+    pub fn max_reach_yd(&self) -> f32 {
+        let (lie_carry, lie_roll, _) = self.lie_modifiers(self.current_surface());
+        CLUBS
+            .iter()
+            .filter(|c| !c.putter)
+            .map(|c| c.carry_yd * lie_carry + c.rollout_yd * lie_roll)
+            .fold(0.0, f32::max)
+    }
+
+    pub fn shots_to_green(&self) -> u32 {
+        if self.on_green() {
+            return 0;
+        }
+        (self.distance_to_hole_yd() / self.max_reach_yd().max(1.0)).ceil() as u32
+    }
+
+    // When the green is out of reach, play the longest club unless that
+    // would leave an awkward partial wedge; then lay back to a full one.
+    pub fn caddie_target_yd(&self) -> f32 {
+        let distance = self.distance_to_hole_yd();
+        let reach = self.max_reach_yd();
+        if distance <= reach {
+            return distance;
+        }
+
+        let leftover = distance - reach;
+        if leftover < AWKWARD_LEFTOVER_YD {
+            distance - LAYUP_TARGET_YD
+        } else {
+            reach
+        }
+    }
+
+    fn auto_select_shot(&mut self) {
+        let (club_idx, shot) = self.best_shot_for(self.caddie_target_yd(), true);
+        self.selected_club_idx = club_idx;
+        self.selected_shot = shot;
+        self.refresh_caddie_aim();
+    }
+
+    fn best_shot_for(&self, distance: f32, allow_putter: bool) -> (usize, ShotType) {
+        if self.on_green() && allow_putter {
+            return (CLUBS.len() - 1, ShotType::Full);
         }
+        let lie = self.current_surface();
+        let (lie_carry, lie_roll, _) = self.lie_modifiers(lie);
 
         let mut best_idx = self.selected_club_idx;
         let mut best_shot = self.selected_shot;
         let mut best_error = f32::MAX;
 
         for (i, club) in CLUBS.iter().enumerate() {
-            if club.putter && distance > 70.0 {
+            let putter_range = distance <= 70.0 || self.bag.putter_anywhere;
+            if !self.bag.allows(i) || (club.putter && (!putter_range || !allow_putter)) {
                 continue;
             }
 
@@ -699,47 +3406,253 @@ impl Game {
             }
         }
 
-        self.selected_club_idx = best_idx;
-        self.selected_shot = best_shot;
+        (best_idx, best_shot)
     }
-}
 
-pub fn wrap_angle_rad(mut angle: f32) -> f32 {
-    while angle <= -PI {
-        angle += 2.0 * PI;
+    fn refresh_caddie_aim(&mut self) {
+        if !self.can_shoot() {
+            return;
+        }
+        self.caddie_aim = Some(self.suggest_aim(self.current_club(), self.selected_shot));
+        self.caddie_advice = self.caddie_advice();
     }
-    while angle > PI {
-        angle -= 2.0 * PI;
+
+    // The caddie's own pick plus the neighbouring club that gives the better
+    // odds, each scored by sampling the shot many times.
+    fn caddie_advice(&self) -> Option<CaddieAdvice> {
+        let going_for_green = self.shots_to_green() <= 1;
+        let (club_idx, shot) = self.best_shot_for(self.caddie_target_yd(), true);
+        if CLUBS[club_idx].putter {
+            return None;
+        }
+        let option = |club_idx: usize| ClubOption {
+            club_idx,
+            shot,
+            chance: self.success_chance(club_idx, shot, going_for_green),
+        };
+
+        let primary = option(club_idx);
+        let alternative = [self.bag.step(club_idx, -1), self.bag.step(club_idx, 1)]
+            .into_iter()
+            .filter(|&i| i != club_idx && !CLUBS[i].putter)
+            .map(option)
+            .max_by(|a, b| a.chance.total_cmp(&b.chance));
+        Some(CaddieAdvice {
+            primary,
+            alternative,
+            going_for_green,
+        })
+    }
+
+    // Mirrors `strike` and the roll-out closely enough to count how often
+    // the ball finishes on the green (or in the fairway when laying up).
+    fn success_chance(&self, club_idx: usize, shot: ShotType, going_for_green: bool) -> f32 {
+        let club = CLUBS[club_idx];
+        let aim = self.suggest_aim(club, shot);
+        let (lie_carry, lie_roll, lie_dispersion) = self.lie_modifiers(self.current_surface());
+        let dispersion = self.spread(club, lie_dispersion);
+        let wind = self.wind.vector();
+        let shape = self.selected_shape;
+        let mut rng = StdRng::seed_from_u64(CADDIE_SAMPLE_SEED);
+
+        let successes = (0..CADDIE_SAMPLES)
+            .filter(|_| {
+                let roll: f32 = rng.gen_range(-1.0..1.0);
+                let strike = StrikeQuality::from_roll(roll);
+                let angle = aim + roll * dispersion;
+                let dir = Vec2::new(angle.cos(), angle.sin());
+                let carry = club.carry_yd * shot.carry_mult() * strike.carry_mult() * lie_carry
+                    / self.layout.yards_per_tile;
+                let mut speed = club.rollout_yd
+                    * shot.roll_mult()
+                    * strike.roll_mult()
+                    * lie_roll
+                    * shape.roll_mult()
+                    / self.layout.yards_per_tile
+                    * 2.0;
+
+                let push = wind::carry_push(wind, dir, club.carry_yd / self.layout.yards_per_tile);
+                let curve = carry * shape.swerve();
+                let landing = Vec2::new(
+                    self.ball.x + dir.x * carry + push.x - dir.y * curve,
+                    self.ball.y + dir.y * carry + push.y + dir.x * curve,
+                );
+                let dir = Vec2::new(
+                    dir.x - dir.y * shape.swerve(),
+                    dir.y + dir.x * shape.swerve(),
+                )
+                .normalized();
+                let landed_on = self.terrain.surface(landing.x as i32, landing.y as i32);
+                speed *= self.landing_bounce(landed_on);
+                let drag = self.surface_drag(landed_on);
+                let finish_x = landing.x + (dir.x * speed + wind.x * 0.12) / drag;
+                let finish_y = landing.y + (dir.y * speed + wind.y * 0.12) / drag;
+                if self.out_of_bounds(Vec2::new(finish_x, finish_y)) {
+                    return false;
+                }
+                match self.terrain.surface(finish_x as i32, finish_y as i32) {
+                    Surface::Green => !self.layout.on_false_front(finish_x, finish_y),
+                    Surface::Fringe | Surface::Fairway => !going_for_green,
+                    Surface::Rough | Surface::HeavyRough | Surface::Bunker | Surface::Water => {
+                        false
+                    }
+                }
+            })
+            .count();
+        successes as f32 / CADDIE_SAMPLES as f32
+    }
+
+    // Rolls a die-weighted putt on a few lines either side of the cup and
+    // keeps the one that passes closest, so the ghost line plays the break.
+    fn read_putt(&self, to_pin: f32) -> f32 {
+        if self.layout.contour.tilt.length() == 0.0 || !self.on_green() {
+            return to_pin;
+        }
+        // Straighter lines first, so they win a tie.
+        (0..=PUTT_READ_LINES)
+            .flat_map(|i| [i, -i])
+            .map(|i| to_pin + i as f32 * PUTT_READ_STEP_RAD)
+            .map(|angle| (angle, self.putt_miss_by(angle)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(to_pin, |(angle, _)| angle)
+    }
+
+    fn putt_miss_by(&self, angle: f32) -> f32 {
+        let mut sim = self.simulation();
+        sim.angle = angle;
+        sim.selected_club_idx = self.selected_club_idx;
+        // The cup is taken away so the ball rolls straight past it instead
+        // of dropping or lipping out.
+        sim.hole = Vec2::new(-100.0, -100.0);
+        sim.strike(0.0, Some(self.bot_putt_power()));
+        // Stepped finely so the pass by the cup isn't skipped over.
+        let dt = 0.016;
+        let mut closest = f32::MAX;
+        for _ in 0..BOT_MAX_STEPS_PER_SHOT * 4 {
+            sim.update(dt);
+            let (dx, dy) = (sim.ball.x - self.hole.x, sim.ball.y - self.hole.y);
+            closest = closest.min((dx * dx + dy * dy).sqrt());
+            if !sim.rolling {
+                break;
+            }
+        }
+        closest
+    }
+
+    // Starts from the line that lets the wind carry the ball back onto the
+    // pin, then sweeps either side for the aim whose likely landing spots
+    // stay out of the sand and on the map.
+    fn suggest_aim(&self, club: ClubSpec, shot: ShotType) -> f32 {
+        let to_pin = (self.hole.y - self.ball.y).atan2(self.hole.x - self.ball.x);
+        if club.putter {
+            return self.read_putt(to_pin);
+        }
+
+        let (lie_carry, _, lie_dispersion) = self.lie_modifiers(self.current_surface());
+        let carry = club.carry_yd * shot.carry_mult() * lie_carry / self.layout.yards_per_tile;
+        let wind = self.wind.vector();
+        let to_pin_dir = Vec2::new(to_pin.cos(), to_pin.sin());
+        let target = Vec2::new(
+            self.ball.x + to_pin_dir.x * carry,
+            self.ball.y + to_pin_dir.y * carry,
+        );
+        let push = wind::carry_push(wind, to_pin_dir, club.carry_yd / self.layout.yards_per_tile);
+        // Aimed away from the way the shape bends so it comes back to the pin.
+        let swerve = self.selected_shape.swerve();
+        let base = (target.y - push.y - self.ball.y).atan2(target.x - push.x - self.ball.x)
+            - swerve.atan();
+        let spread = self.spread(club, lie_dispersion);
+
+        let risk = |angle: f32| {
+            let mut risk = 0.0;
+            for fraction in [-1.0, -0.5, 0.0, 0.5, 1.0] {
+                let a = angle + spread * fraction;
+                let dir = Vec2::new(a.cos(), a.sin());
+                let push = wind::carry_push(wind, dir, club.carry_yd / self.layout.yards_per_tile);
+                let x = self.ball.x + dir.x * carry + push.x - dir.y * carry * swerve;
+                let y = self.ball.y + dir.y * carry + push.y + dir.x * carry * swerve;
+                if self.out_of_bounds(Vec2::new(x, y)) {
+                    risk += 8.0;
+                    continue;
+                }
+                let tile = self.terrain.tile(x as i32, y as i32);
+                risk += if tile.surface == Surface::Water {
+                    8.0
+                } else if tile.hazard {
+                    4.0
+                } else if tile.surface == Surface::HeavyRough {
+                    2.0
+                } else if tile.surface == Surface::Rough {
+                    1.0
+                } else {
+                    0.0
+                };
+                if fraction == 0.0 {
+                    risk += ((x - target.x).powi(2) + (y - target.y).powi(2)).sqrt();
+                }
+            }
+            risk
+        };
+
+        // A small lean penalty keeps the wind line when nothing is in play.
+        (-CADDIE_AIM_SWEEP..=CADDIE_AIM_SWEEP)
+            .map(|i| {
+                let angle = wrap_angle_rad(base + i as f32 * CADDIE_AIM_STEP_RAD);
+                (angle, risk(angle) + i.abs() as f32 * 0.05)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(base, |(angle, _)| angle)
+    }
+}
+
+pub fn lie_modifiers(lie: Surface, buried: bool) -> (f32, f32, f32) {
+    match lie {
+        Surface::Bunker if buried => (0.42, 0.3, 0.08),
+        Surface::Rough | Surface::HeavyRough if buried => (0.5, 0.35, 0.06),
+        Surface::Green => (1.0, 1.0, 0.002),
+        Surface::Fringe => (0.97, 0.95, 0.006),
+        Surface::Fairway => (1.0, 1.0, 0.004),
+        Surface::Rough => (0.82, 0.72, 0.028),
+        Surface::HeavyRough => (0.68, 0.55, 0.04),
+        Surface::Bunker | Surface::Water => (0.65, 0.46, 0.045),
     }
-    angle
 }
 
-pub fn terrain_surface(x: i32, y: i32) -> Surface {
-    let xf = x as f32;
-    let yf = y as f32;
+pub fn lie_name(lie: Surface, buried: bool) -> String {
+    match lie {
+        Surface::Bunker if buried => format!("{} — buried", lie.name()),
+        _ if buried => format!("{} — embedded", lie.name()),
+        _ => lie.name().to_string(),
+    }
+}
 
-    let fairway_center = HEIGHT as f32 * 0.5 + (xf / 11.0).sin() * 2.5;
-    let fairway_half_width = 2.8 + xf * 0.04;
-    let distance = (yf - fairway_center).abs();
+fn buried_chance(apex: f32) -> f32 {
+    BURIED_CHANCE * ((apex - BURIED_MIN_APEX) / (DRIVER_APEX - BURIED_MIN_APEX)).clamp(0.0, 1.0)
+}
 
-    let trap_a = ((xf - WIDTH as f32 * 0.38).powi(2) + (yf - HEIGHT as f32 * 0.32).powi(2)).sqrt();
-    let trap_b = ((xf - WIDTH as f32 * 0.66).powi(2) + (yf - HEIGHT as f32 * 0.73).powi(2)).sqrt();
-    let green_dist =
-        ((xf - (WIDTH - 8) as f32).powi(2) + (yf - (HEIGHT / 2 - 5) as f32).powi(2)).sqrt();
+// The point on the straight path from `from` to `to` closest to `point`.
+fn nearest_on_segment(from: Vec2, to: Vec2, point: Vec2) -> Vec2 {
+    let (sx, sy) = (to.x - from.x, to.y - from.y);
+    let len_sq = sx * sx + sy * sy;
+    if len_sq == 0.0 {
+        return from;
+    }
+    let t = (((point.x - from.x) * sx + (point.y - from.y) * sy) / len_sq).clamp(0.0, 1.0);
+    Vec2::new(from.x + sx * t, from.y + sy * t)
+}
 
-    if green_dist < 2.6 {
-        Surface::Green
-    } else if trap_a < 2.8 || trap_b < 2.8 {
-        Surface::Bunker
-    } else if distance < fairway_half_width {
-        Surface::Fairway
-    } else {
-        Surface::Rough
+fn wrap_angle_rad(mut angle: f32) -> f32 {
+    while angle <= -PI {
+        angle += 2.0 * PI;
     }
+    while angle > PI {
+        angle -= 2.0 * PI;
+    }
+    angle
 }
 
-pub fn terrain_char(x: i32, y: i32) -> char {
-    match terrain_surface(x, y) {
+pub fn terrain_char(surface: Surface, x: i32, y: i32) -> char {
+    match surface {
         Surface::Green => {
             if (x + y) % 2 == 0 {
                 '■'
@@ -754,6 +3667,13 @@ pub fn terrain_char(x: i32, y: i32) -> char {
                 '▪'
             }
         }
+        Surface::Fringe => {
+            if (x + y) % 2 == 0 {
+                '▪'
+            } else {
+                '▫'
+            }
+        }
         Surface::Rough => {
             if (x + y) % 3 == 0 {
                 '▪'
@@ -761,6 +3681,13 @@ pub fn terrain_char(x: i32, y: i32) -> char {
                 '·'
             }
         }
+        Surface::HeavyRough => {
+            if (x + y) % 2 == 0 {
+                '"'
+            } else {
+                '▪'
+            }
+        }
         Surface::Bunker => {
             if (x + y) % 5 == 0 {
                 '□'
@@ -768,11 +3695,18 @@ pub fn terrain_char(x: i32, y: i32) -> char {
                 '▫'
             }
         }
+        Surface::Water => {
+            if (x + 2 * y) % 4 == 0 {
+                '≈'
+            } else {
+                '~'
+            }
+        }
     }
 }
 
-pub fn terrain_color(x: i32, y: i32) -> Color {
-    match terrain_surface(x, y) {
+pub fn terrain_color(surface: Surface) -> Color {
+    match surface {
         Surface::Green => Color::Rgb {
             r: 90,
             g: 220,
@@ -783,15 +3717,109 @@ pub fn terrain_color(x: i32, y: i32) -> Color {
             g: 170,
             b: 50,
         },
+        Surface::Fringe => Color::Rgb {
+            r: 70,
+            g: 195,
+            b: 70,
+        },
         Surface::Rough => Color::Rgb {
             r: 30,
             g: 110,
             b: 30,
         },
+        Surface::HeavyRough => Color::Rgb {
+            r: 20,
+            g: 75,
+            b: 25,
+        },
         Surface::Bunker => Color::Rgb {
             r: 192,
             g: 168,
             b: 112,
         },
+        Surface::Water => Color::Rgb {
+            r: 50,
+            g: 110,
+            b: 210,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A ball rolling at `speed` from `back` tiles short of the cup, on a line
+    // that passes `wide` tiles to one side of it, after one step.
+    fn roll_past_cup(speed: f32, back: f32, wide: f32) -> Game {
+        let mut game = Game::new().simulation();
+        let (dir, side) = (Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0));
+        game.ball = Vec2::new(
+            game.hole.x - dir.x * back + side.x * wide,
+            game.hole.y - dir.y * back + side.y * wide,
+        );
+        game.velocity = Vec2::new(dir.x * speed, dir.y * speed);
+        game.rolling = true;
+        game.update(0.033);
+        game
+    }
+
+    // How far a full shot from the first bunker on the hole carries, with
+    // the wind down.
+    fn carry_from_bunker(buried: bool) -> f32 {
+        let mut game = Game::new().simulation();
+        let (width, height) = (game.layout.width, game.layout.height);
+        let sand = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .find(|&(x, y)| game.terrain.surface(x, y) == Surface::Bunker)
+            .expect("the hole has a bunker");
+        game.ball = Vec2::new(sand.0 as f32 + 0.5, sand.1 as f32 + 0.5);
+        game.wind = Wind::new(0.0, 0.0);
+        game.selected_club_idx = CLUBS.iter().position(|c| !c.putter).unwrap();
+        game.buried = buried;
+        game.strike(0.0, None);
+        let air = game.airborne.expect("a full shot is in the air");
+        Vec2::new(air.landing.x - air.start.x, air.landing.y - air.start.y).length()
+    }
+
+    #[test]
+    fn a_buried_ball_comes_out_short() {
+        for _ in 0..20 {
+            let (buried, clean) = (carry_from_bunker(true), carry_from_bunker(false));
+            assert!(buried < clean, "{} tiles buried, {} clean", buried, clean);
+        }
+    }
+
+    #[test]
+    fn nearest_point_is_clamped_to_the_segment() {
+        let from = Vec2::new(0.0, 0.0);
+        let to = Vec2::new(10.0, 0.0);
+        let mid = nearest_on_segment(from, to, Vec2::new(4.0, 3.0));
+        assert_eq!((mid.x, mid.y), (4.0, 0.0));
+        let before = nearest_on_segment(from, to, Vec2::new(-2.0, 1.0));
+        assert_eq!((before.x, before.y), (0.0, 0.0));
+        let after = nearest_on_segment(from, to, Vec2::new(12.0, -1.0));
+        assert_eq!((after.x, after.y), (10.0, 0.0));
+        let still = nearest_on_segment(from, from, Vec2::new(1.0, 1.0));
+        assert_eq!((still.x, still.y), (0.0, 0.0));
+    }
+
+    #[test]
+    fn a_ball_crossing_the_cup_in_one_substep_drops() {
+        // Fast enough that a single substep carries the ball from short of
+        // the cup to well past it.
+        for speed in [20_000.0, 50_000.0, 80_000.0] {
+            for wide in [0.0, 0.3, 0.5] {
+                let game = roll_past_cup(speed, 1.5, wide);
+                assert!(game.hole_done, "missed at {} tiles/s, {} wide", speed, wide);
+                assert_eq!((game.ball.x, game.ball.y), (game.hole.x, game.hole.y));
+            }
+        }
+    }
+
+    #[test]
+    fn a_fast_ball_passing_wide_of_the_cup_rolls_on() {
+        let game = roll_past_cup(20_000.0, 1.5, 1.5);
+        assert!(!game.hole_done);
     }
 }
@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::game::Vec2;
+use crate::storage::{self, Schema};
+
+const GHOST_FILE: &str = "ghosts.txt";
+const SCHEMA: Schema = Schema {
+    name: "ghost rounds",
+    migrations: &[storage::unversioned],
+};
+// The ball's place is taken this often in hole time while it moves.
+const SAMPLE_SECS: f32 = 0.05;
+
+#[derive(Clone, Copy)]
+struct Sample {
+    secs: f32,
+    at: Vec2,
+}
+
+// Where the ball was through one hole, timed by the hole's clock. A ball at
+// rest is kept as its first and latest moments, so waiting over a shot
+// costs two samples however long it takes.
+#[derive(Clone, Default)]
+pub struct GhostHole {
+    pub strokes: u32,
+    samples: Vec<Sample>,
+}
+
+impl GhostHole {
+    fn record(&mut self, secs: f32, at: Vec2) {
+        let n = self.samples.len();
+        let resting = n >= 2 && self.samples[n - 1].at == at && self.samples[n - 2].at == at;
+        if resting {
+            self.samples[n - 1].secs = secs;
+        } else if self
+            .samples
+            .last()
+            .is_none_or(|last| last.at == at || secs - last.secs >= SAMPLE_SECS)
+        {
+            self.samples.push(Sample { secs, at });
+        }
+    }
+
+    // Where the ghost is `secs` into the hole, or nothing once it's holed.
+    pub fn position(&self, secs: f32) -> Option<Vec2> {
+        let next = self.samples.iter().position(|s| s.secs >= secs)?;
+        let Some(prev) = next.checked_sub(1).map(|i| self.samples[i]) else {
+            return Some(self.samples[next].at);
+        };
+        let next = self.samples[next];
+        let t = (secs - prev.secs) / (next.secs - prev.secs).max(f32::EPSILON);
+        Some(Vec2::new(
+            prev.at.x + (next.at.x - prev.at.x) * t,
+            prev.at.y + (next.at.y - prev.at.y) * t,
+        ))
+    }
+
+    pub fn finish_secs(&self) -> f32 {
+        self.samples.last().map_or(0.0, |s| s.secs)
+    }
+}
+
+// A whole round, hole by hole by number.
+#[derive(Clone, Default)]
+pub struct GhostRound {
+    pub to_par: i32,
+    pub holes: BTreeMap<u32, GhostHole>,
+}
+
+impl GhostRound {
+    // More holes beats fewer, then the lower score; a tie keeps the older.
+    fn beats(&self, other: &GhostRound) -> bool {
+        (self.holes.len(), -self.to_par) > (other.holes.len(), -other.to_par)
+    }
+}
+
+// Each course's best round, saved as
+//
+//   course Terminal Links
+//   round -2
+//   hole 7 4 0.00 8.00 24.00 0.05 8.40 23.90 ...
+//
+// with every hole's strokes and then its samples as seconds, x and y.
+#[derive(Default)]
+struct GhostBook {
+    rounds: BTreeMap<String, GhostRound>,
+    path: Option<PathBuf>,
+}
+
+impl GhostBook {
+    fn load() -> Self {
+        let path = storage::data_dir().map(|dir| dir.join(GHOST_FILE));
+        let mut book = Self {
+            path: path.clone(),
+            ..Self::default()
+        };
+        if let Some(text) = path.and_then(|p| storage::read(&p, &SCHEMA).ok().flatten()) {
+            book.parse(&text);
+        }
+        book
+    }
+
+    fn parse(&mut self, text: &str) {
+        let mut current: Option<String> = None;
+        for line in text.lines() {
+            let (key, rest) = line.split_once(' ').unwrap_or((line, ""));
+            if key == "course" {
+                current = Some(rest.to_string());
+                continue;
+            }
+            let Some(course) = current.as_ref() else {
+                continue;
+            };
+            let round = self.rounds.entry(course.clone()).or_default();
+            match key {
+                "round" => round.to_par = rest.parse().unwrap_or_default(),
+                "hole" => {
+                    if let Some((number, hole)) = parse_hole(rest) {
+                        round.holes.insert(number, hole);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut text = String::new();
+        for (course, round) in &self.rounds {
+            text.push_str(&format!("course {}\nround {}\n", course, round.to_par));
+            for (number, hole) in &round.holes {
+                text.push_str(&format!("hole {} {}", number, hole.strokes));
+                for sample in &hole.samples {
+                    text.push_str(&format!(
+                        " {:.2} {:.2} {:.2}",
+                        sample.secs, sample.at.x, sample.at.y
+                    ));
+                }
+                text.push('\n');
+            }
+        }
+        storage::write(path, &SCHEMA, &text)
+    }
+}
+
+fn parse_hole(text: &str) -> Option<(u32, GhostHole)> {
+    let mut fields = text.split_whitespace();
+    let number = fields.next()?.parse().ok()?;
+    let strokes = fields.next()?.parse().ok()?;
+    let numbers: Vec<f32> = fields.map(|f| f.parse()).collect::<Result<_, _>>().ok()?;
+    let samples = numbers
+        .chunks_exact(3)
+        .map(|s| Sample {
+            secs: s[0],
+            at: Vec2::new(s[1], s[2]),
+        })
+        .collect();
+    Some((number, GhostHole { strokes, samples }))
+}
+
+// A course round raced against the best one on record, recorded as it goes
+// so it can take the ghost's place if it beats it.
+pub struct GhostRace {
+    course: String,
+    pub best: Option<GhostRound>,
+    recording: GhostRound,
+}
+
+impl GhostRace {
+    pub fn load(course: &str) -> Self {
+        Self {
+            course: course.to_string(),
+            best: GhostBook::load().rounds.remove(course),
+            recording: GhostRound::default(),
+        }
+    }
+
+    pub fn record(&mut self, hole: u32, secs: f32, at: Vec2) {
+        self.recording
+            .holes
+            .entry(hole)
+            .or_default()
+            .record(secs, at);
+    }
+
+    pub fn finish_hole(&mut self, hole: u32, strokes: u32, secs: f32, at: Vec2) {
+        self.record(hole, secs, at);
+        if let Some(hole) = self.recording.holes.get_mut(&hole) {
+            hole.strokes = strokes;
+        }
+    }
+
+    // Keeps the finished round as the course's ghost if it's the best yet.
+    pub fn finish_round(&mut self, to_par: i32) -> std::io::Result<()> {
+        let mut round = std::mem::take(&mut self.recording);
+        round.to_par = to_par;
+        let mut book = GhostBook::load();
+        if book
+            .rounds
+            .get(&self.course)
+            .is_some_and(|best| !round.beats(best))
+        {
+            return Ok(());
+        }
+        book.rounds.insert(self.course.clone(), round);
+        book.save()
+    }
+
+    pub fn hole(&self, number: u32) -> Option<&GhostHole> {
+        self.best.as_ref()?.holes.get(&number)
+    }
+}
@@ -0,0 +1,81 @@
+use crate::game::{Game, Surface, Vec2};
+
+// Expected strokes are sampled from the middle of each block this many tiles
+// across, the caddie playing the hole out from there this many times.
+const STEP_TILES: i32 = 4;
+const PLAYOUTS: u32 = 8;
+// The colors run from the best spot on the hole to this many strokes worse;
+// anywhere worse than that is as bad as it gets.
+pub const SPREAD_STROKES: f32 = 2.5;
+
+// The caddie's average score to hole out from points all over the hole, for
+// the pin, wind and conditions it was built in. A sample in the water has
+// no value, since the ball can never lie there.
+pub struct HeatMap {
+    cols: i32,
+    rows: i32,
+    strokes: Vec<Option<f32>>,
+    pub best: f32,
+    pub worst: f32,
+}
+
+impl HeatMap {
+    pub fn build(game: &Game) -> Self {
+        let cols = (game.layout.width as u32).div_ceil(STEP_TILES as u32) as i32;
+        let rows = (game.layout.height as u32).div_ceil(STEP_TILES as u32) as i32;
+        let mut strokes = Vec::with_capacity((cols * rows) as usize);
+        for row in 0..rows {
+            for col in 0..cols {
+                let x = (col * STEP_TILES + STEP_TILES / 2).min(game.layout.width - 1);
+                let y = (row * STEP_TILES + STEP_TILES / 2).min(game.layout.height - 1);
+                let at = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                strokes.push(
+                    (game.terrain.surface(x, y) != Surface::Water)
+                        .then(|| game.expected_strokes_from(at, PLAYOUTS)),
+                );
+            }
+        }
+        let known = || strokes.iter().flatten().copied();
+        Self {
+            cols,
+            rows,
+            best: known().reduce(f32::min).unwrap_or(0.0),
+            worst: known().reduce(f32::max).unwrap_or(0.0),
+            strokes,
+        }
+    }
+
+    // Expected strokes from a tile, blended from the samples around it.
+    pub fn strokes_at(&self, wx: i32, wy: i32) -> Option<f32> {
+        // Sample centres sit half a step in, so a tile is between the
+        // samples either side of it in grid units.
+        let gx = (wx as f32 + 0.5) / STEP_TILES as f32 - 0.5;
+        let gy = (wy as f32 + 0.5) / STEP_TILES as f32 - 0.5;
+        let (col, row) = (gx.floor() as i32, gy.floor() as i32);
+        let (fx, fy) = (gx - col as f32, gy - row as f32);
+        let mut total = 0.0;
+        let mut weight = 0.0;
+        for (dc, dr, w) in [
+            (0, 0, (1.0 - fx) * (1.0 - fy)),
+            (1, 0, fx * (1.0 - fy)),
+            (0, 1, (1.0 - fx) * fy),
+            (1, 1, fx * fy),
+        ] {
+            let (c, r) = (
+                (col + dc).clamp(0, self.cols - 1),
+                (row + dr).clamp(0, self.rows - 1),
+            );
+            if let Some(strokes) = self.strokes[(r * self.cols + c) as usize] {
+                total += strokes * w;
+                weight += w;
+            }
+        }
+        (weight > 0.0).then(|| total / weight)
+    }
+
+    // How bad a place `strokes` is on this hole, from 0 at the best sample
+    // to 1 at `SPREAD_STROKES` worse or more.
+    pub fn share(&self, strokes: f32) -> f32 {
+        ((strokes - self.best) / SPREAD_STROKES).clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,700 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    Surface, Vec2, CELL_ASPECT, CLUBS, DEFAULT_YARDS_PER_TILE, HEIGHT, WIDTH, WORLD_HEIGHT,
+};
+
+pub const PAR3_MAX_YD: f32 = 250.0;
+pub const PAR4_MAX_YD: f32 = 470.0;
+pub const BASE_STIMP: f32 = 10.0;
+const TEMPLATE_ATTEMPTS: usize = 8;
+const FALSE_FRONT_ACCEL: f32 = 1.6;
+const BACKSTOP_ACCEL: f32 = 1.2;
+// Fraction of the green radius past the middle where the contours start.
+const CONTOUR_BAND: f32 = 0.55;
+const BACKSTOP_BANK: f32 = 1.5;
+// Kept under what the green's drag holds a resting ball against, so putts
+// break but still stop.
+const MAX_GREEN_TILT: f32 = 0.2;
+// A collar of fringe this many tiles wide rings every green, and the rough
+// turns heavy this far from the fairway, the green and the tee.
+const FRINGE_WIDTH: f32 = 1.0;
+const HEAVY_ROUGH_TILES: f32 = 4.0;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct GreenSpec {
+    pub stimp: f32,
+    pub firmness: f32,
+    pub exposure: f32,
+}
+
+impl GreenSpec {
+    pub fn standard() -> Self {
+        Self {
+            stimp: BASE_STIMP,
+            firmness: 0.5,
+            exposure: 0.5,
+        }
+    }
+
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        Self {
+            stimp: rng.gen_range(8.0..13.0),
+            firmness: rng.gen_range(0.2..0.85),
+            exposure: rng.gen_range(0.1..1.0),
+        }
+    }
+
+    pub fn drag_mult(self) -> f32 {
+        BASE_STIMP / self.stimp.max(4.0)
+    }
+
+    // Players only half-read the pace, so quick greens still run out further.
+    pub fn putt_speed_mult(self) -> f32 {
+        self.drag_mult().sqrt()
+    }
+
+    pub fn bounce_mult(self) -> f32 {
+        0.6 + self.firmness.clamp(0.0, 1.0) * 0.8
+    }
+
+    pub fn caddie_note(self) -> Option<String> {
+        let firmness = if self.firmness > 0.65 {
+            Some("firm")
+        } else if self.firmness < 0.35 {
+            Some("soft")
+        } else {
+            None
+        };
+        let pace = if self.stimp > 11.5 {
+            Some("quick")
+        } else if self.stimp < 8.5 {
+            Some("slow")
+        } else {
+            None
+        };
+
+        match (firmness, pace) {
+            (Some(f), Some(p)) => Some(format!("Greens are {} and {} today", f, p)),
+            (Some(f), None) => Some(format!("Greens are {} today", f)),
+            (None, Some(p)) => Some(format!("Greens are {} today", p)),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct GreenContour {
+    pub false_front: bool,
+    pub backstop: bool,
+    // Downhill pull across the whole putting surface.
+    pub tilt: Vec2,
+}
+
+impl GreenContour {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let pull = rng.gen_range(0.0..MAX_GREEN_TILT);
+        Self {
+            false_front: rng.gen_bool(0.35),
+            backstop: rng.gen_bool(0.35),
+            tilt: Vec2::new(angle.cos() * pull, angle.sin() * pull),
+        }
+    }
+
+    pub fn describe(self) -> Option<&'static str> {
+        match (self.false_front, self.backstop) {
+            (true, true) => Some("false front, backstop"),
+            (true, false) => Some("false front"),
+            (false, true) => Some("backstop"),
+            (false, false) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HoleTemplate {
+    Standard,
+    DriveablePar4,
+    ReachablePar5,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    ReachableInShots(u32),
+    GuardBunkerOnLine,
+    CarryHazardShortOfGreen,
+}
+
+impl HoleTemplate {
+    pub fn name(self) -> &'static str {
+        match self {
+            HoleTemplate::Standard => "Standard",
+            HoleTemplate::DriveablePar4 => "Driveable Par 4",
+            HoleTemplate::ReachablePar5 => "Reachable Par 5",
+        }
+    }
+
+    pub fn par(self) -> Option<u32> {
+        match self {
+            HoleTemplate::Standard => None,
+            HoleTemplate::DriveablePar4 => Some(4),
+            HoleTemplate::ReachablePar5 => Some(5),
+        }
+    }
+
+    // Yardages are tied to the longest clubs so a good strike can get home.
+    pub fn yardage_range(self) -> (f32, f32) {
+        let reach = longest_full_shot_yd();
+        match self {
+            HoleTemplate::Standard => (130.0, 590.0),
+            HoleTemplate::DriveablePar4 => (reach - 8.0, reach + 8.0),
+            HoleTemplate::ReachablePar5 => (reach * 1.8, reach * 1.95),
+        }
+    }
+
+    pub fn constraints(self) -> &'static [Constraint] {
+        match self {
+            HoleTemplate::Standard => &[],
+            HoleTemplate::DriveablePar4 => &[
+                Constraint::ReachableInShots(1),
+                Constraint::GuardBunkerOnLine,
+            ],
+            HoleTemplate::ReachablePar5 => &[
+                Constraint::ReachableInShots(2),
+                Constraint::CarryHazardShortOfGreen,
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Bunker {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+// A ball that finds the water is dropped at `drop`, or back where it was
+// played from when the pond has no drop zone.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Pond {
+    pub center: Vec2,
+    pub radius: f32,
+    pub drop: Option<Vec2>,
+}
+
+impl Pond {
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        ((x - self.center.x).powi(2) + (y - self.center.y).powi(2)).sqrt() < self.radius
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Fairway {
+    pub start_x: f32,
+    pub end_x: f32,
+    pub base_y: f32,
+    pub slope: f32,
+    pub wave_amp: f32,
+    pub wave_period: f32,
+    pub half_width: f32,
+    pub width_growth: f32,
+}
+
+impl Fairway {
+    pub fn center_y(&self, x: f32) -> f32 {
+        self.base_y + self.slope * x + (x / self.wave_period).sin() * self.wave_amp
+    }
+
+    pub fn half_width_at(&self, x: f32) -> f32 {
+        self.half_width + x * self.width_growth
+    }
+
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.start_x && x <= self.end_x && (y - self.center_y(x)).abs() < self.half_width_at(x)
+    }
+
+    // Tiles from the nearest edge of the fairway, zero on it.
+    pub fn distance_outside(&self, x: f32, y: f32) -> f32 {
+        let across = (y - self.center_y(x)).abs() - self.half_width_at(x);
+        (self.start_x - x).max(x - self.end_x).max(across).max(0.0)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HoleLayout {
+    pub width: i32,
+    pub height: i32,
+    pub tee: Vec2,
+    pub pin: Vec2,
+    pub green_center: Vec2,
+    pub par: u32,
+    pub template: HoleTemplate,
+    pub green_radius: f32,
+    pub green: GreenSpec,
+    pub contour: GreenContour,
+    pub fairway: Fairway,
+    pub bunkers: Vec<Bunker>,
+    pub ponds: Vec<Pond>,
+    pub yards_per_tile: f32,
+}
+
+impl HoleLayout {
+    // The original hand-made hole, drawn in screen cells.
+    pub fn classic() -> Self {
+        Self::from_cells(Self {
+            width: WIDTH,
+            height: HEIGHT,
+            tee: Vec2::new(8.0, (HEIGHT / 2) as f32),
+            pin: Vec2::new((WIDTH - 8) as f32, (HEIGHT / 2 - 5) as f32),
+            green_center: Vec2::new((WIDTH - 8) as f32, (HEIGHT / 2 - 5) as f32),
+            par: 4,
+            template: HoleTemplate::Standard,
+            green_radius: 2.6,
+            green: GreenSpec::standard(),
+            contour: GreenContour {
+                false_front: false,
+                backstop: true,
+                tilt: Vec2::new(0.0, 0.08),
+            },
+            fairway: Fairway {
+                start_x: 0.0,
+                end_x: WIDTH as f32,
+                base_y: HEIGHT as f32 * 0.5,
+                slope: 0.0,
+                wave_amp: 2.5,
+                wave_period: 11.0,
+                half_width: 2.8,
+                width_growth: 0.04,
+            },
+            bunkers: vec![
+                Bunker {
+                    center: Vec2::new(WIDTH as f32 * 0.38, HEIGHT as f32 * 0.32),
+                    radius: 2.8,
+                },
+                Bunker {
+                    center: Vec2::new(WIDTH as f32 * 0.66, HEIGHT as f32 * 0.73),
+                    radius: 2.8,
+                },
+            ],
+            ponds: Vec::new(),
+            yards_per_tile: DEFAULT_YARDS_PER_TILE,
+        })
+    }
+
+    // Hand-made holes are laid out in screen cells, columns across and rows
+    // down, as they'd be drawn. Their positions are stretched to the world's
+    // square tiles; radii and the fairway's width are already true distances,
+    // so the circles come out round.
+    pub fn from_cells(mut layout: Self) -> Self {
+        let aspect = CELL_ASPECT as f32;
+        let down = |p: Vec2| Vec2::new(p.x, p.y * aspect);
+        layout.height *= CELL_ASPECT;
+        layout.tee = down(layout.tee);
+        layout.pin = down(layout.pin);
+        layout.green_center = down(layout.green_center);
+        layout.fairway.base_y *= aspect;
+        layout.fairway.slope *= aspect;
+        layout.fairway.wave_amp *= aspect;
+        for bunker in layout.bunkers.iter_mut() {
+            bunker.center = down(bunker.center);
+        }
+        for pond in layout.ponds.iter_mut() {
+            pond.center = down(pond.center);
+            pond.drop = pond.drop.map(down);
+        }
+        layout
+    }
+
+    // Lays out a hole of roughly `yards` from tee to pin at `yards_per_tile`.
+    // The world grows past the default playfield for long holes and keeps the
+    // screen aspect ratio.
+    pub fn generate<R: Rng>(
+        rng: &mut R,
+        yards: f32,
+        par: Option<u32>,
+        yards_per_tile: f32,
+    ) -> Self {
+        let par = par.unwrap_or_else(|| par_for_yardage(yards));
+        let length = yards / yards_per_tile;
+        let k = feature_scale(yards_per_tile);
+
+        let width = WIDTH.max((length + 16.0 * k).ceil() as i32);
+        let height = WORLD_HEIGHT.max(width * WORLD_HEIGHT / WIDTH);
+        let mid_y = height as f32 * 0.5;
+
+        let tee_x = ((width as f32 - length) * 0.5).max(4.0 * k);
+        let tee = Vec2::new(tee_x, mid_y + rng.gen_range(-2.0..2.0) * k);
+        let max_rise = (mid_y - 4.0 * k).min(length * 0.25);
+        let rise = rng.gen_range(-max_rise..max_rise);
+        let run = (length * length - rise * rise).max(1.0).sqrt();
+        let pin = Vec2::new(tee.x + run, tee.y + rise);
+
+        let slope = (pin.y - tee.y) / (pin.x - tee.x).max(1.0);
+        // Par 3s only get a short apron in front of the green.
+        let start_x = if par <= 3 {
+            pin.x - 8.0 * k
+        } else {
+            tee.x + rng.gen_range(4.0..10.0) * k
+        };
+        let fairway = Fairway {
+            start_x,
+            end_x: pin.x,
+            base_y: tee.y - slope * tee.x,
+            slope,
+            wave_amp: if par <= 3 {
+                0.0
+            } else {
+                rng.gen_range(1.0..3.5) * k
+            },
+            wave_period: rng.gen_range(9.0..16.0) * k,
+            half_width: rng.gen_range(2.6..3.6) * k,
+            width_growth: 0.02,
+        };
+
+        let green_radius = rng.gen_range(2.3..3.1) * k;
+        let green = GreenSpec::random(rng);
+        let contour = GreenContour::random(rng);
+        let mut bunkers = Vec::new();
+
+        let greenside = rng.gen_range(1..=2);
+        for i in 0..greenside {
+            let side = if i == 0 { -1.0 } else { 1.0 };
+            let along = rng.gen_range(-2.0..1.5) * k;
+            bunkers.push(Bunker {
+                center: Vec2::new(
+                    pin.x + along,
+                    pin.y + side * (green_radius + rng.gen_range(2.0..3.0) * k),
+                ),
+                radius: rng.gen_range(1.6..2.4) * k,
+            });
+        }
+
+        if par >= 4 {
+            let landing_x = tee.x + rng.gen_range(230.0..265.0) / yards_per_tile;
+            let side = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+            bunkers.push(Bunker {
+                center: Vec2::new(
+                    landing_x,
+                    fairway.center_y(landing_x) + side * (fairway.half_width_at(landing_x) + k),
+                ),
+                radius: rng.gen_range(2.2..3.0) * k,
+            });
+        }
+
+        // Some short holes carry over water to the green, with a drop zone on
+        // the tee side; longer ones may have a pond off the driving zone.
+        let mut ponds = Vec::new();
+        let line = Vec2::new(pin.x - tee.x, pin.y - tee.y).normalized();
+        if par <= 3 && rng.gen_bool(0.4) {
+            let radius = rng.gen_range(2.0..2.8) * k;
+            let back = green_radius + radius + 0.8 * k;
+            let center = Vec2::new(pin.x - line.x * back, pin.y - line.y * back);
+            let drop_back = back + radius + 1.5 * k;
+            ponds.push(Pond {
+                center,
+                radius,
+                drop: Some(Vec2::new(
+                    pin.x - line.x * drop_back,
+                    pin.y - line.y * drop_back,
+                )),
+            });
+        } else if par >= 4 && rng.gen_bool(0.3) {
+            let pond_x = tee.x + rng.gen_range(200.0..240.0) / yards_per_tile;
+            let side = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+            let radius = rng.gen_range(2.2..3.2) * k;
+            ponds.push(Pond {
+                center: Vec2::new(
+                    pond_x,
+                    fairway.center_y(pond_x) + side * (fairway.half_width_at(pond_x) + radius),
+                ),
+                radius,
+                drop: None,
+            });
+        }
+
+        if par >= 5 {
+            let layup_x = pin.x - rng.gen_range(80.0..110.0) / yards_per_tile;
+            let side = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+            bunkers.push(Bunker {
+                center: Vec2::new(
+                    layup_x,
+                    fairway.center_y(layup_x) + side * fairway.half_width_at(layup_x),
+                ),
+                radius: rng.gen_range(2.0..2.8) * k,
+            });
+        }
+
+        Self {
+            width,
+            height,
+            tee,
+            pin,
+            green_center: pin,
+            par,
+            template: HoleTemplate::Standard,
+            green_radius,
+            green,
+            contour,
+            fairway,
+            bunkers,
+            ponds,
+            yards_per_tile,
+        }
+    }
+
+    // Builds a hole around a risk/reward template, regenerating until every
+    // constraint holds. The last attempt is kept if none satisfy them all.
+    pub fn from_template<R: Rng>(rng: &mut R, template: HoleTemplate, yards_per_tile: f32) -> Self {
+        let mut layout = Self::template_attempt(rng, template, yards_per_tile);
+        for _ in 1..TEMPLATE_ATTEMPTS {
+            if layout.satisfies_all() {
+                break;
+            }
+            layout = Self::template_attempt(rng, template, yards_per_tile);
+        }
+        layout
+    }
+
+    fn template_attempt<R: Rng>(rng: &mut R, template: HoleTemplate, yards_per_tile: f32) -> Self {
+        let (min_yd, max_yd) = template.yardage_range();
+        let yards = rng.gen_range(min_yd..max_yd);
+        let mut layout = Self::generate(rng, yards, template.par(), yards_per_tile);
+        layout.template = template;
+        for constraint in template.constraints() {
+            layout.apply_constraint(rng, *constraint);
+        }
+        layout
+    }
+
+    pub fn random<R: Rng>(rng: &mut R, yards_per_tile: f32) -> Self {
+        match rng.gen_range(0..10) {
+            0 | 1 => Self::from_template(rng, HoleTemplate::DriveablePar4, yards_per_tile),
+            2 | 3 => Self::from_template(rng, HoleTemplate::ReachablePar5, yards_per_tile),
+            _ => {
+                let yards = match rng.gen_range(0..3) {
+                    0 => rng.gen_range(130.0..220.0),
+                    1 => rng.gen_range(320.0..450.0),
+                    _ => rng.gen_range(490.0..590.0),
+                };
+                Self::generate(rng, yards, None, yards_per_tile)
+            }
+        }
+    }
+
+    fn line_dir(&self) -> Vec2 {
+        Vec2::new(self.pin.x - self.tee.x, self.pin.y - self.tee.y).normalized()
+    }
+
+    fn apply_constraint<R: Rng>(&mut self, rng: &mut R, constraint: Constraint) {
+        let dir = self.line_dir();
+        let side = Vec2::new(-dir.y, dir.x);
+        let k = feature_scale(self.yards_per_tile);
+
+        match constraint {
+            Constraint::ReachableInShots(_) => {}
+            Constraint::GuardBunkerOnLine => {
+                // Sits on the direct line but leaves a bail-out to one side.
+                let back = self.green_radius + 1.8 * k;
+                let offset = if rng.gen_bool(0.5) { 0.7 } else { -0.7 } * k;
+                self.bunkers.push(Bunker {
+                    center: Vec2::new(
+                        self.pin.x - dir.x * back + side.x * offset,
+                        self.pin.y - dir.y * back + side.y * offset,
+                    ),
+                    radius: rng.gen_range(1.8..2.3) * k,
+                });
+            }
+            Constraint::CarryHazardShortOfGreen => {
+                let back = self.green_radius + rng.gen_range(4.0..6.0) * k;
+                let center = Vec2::new(self.pin.x - dir.x * back, self.pin.y - dir.y * back);
+                for i in -1..=1 {
+                    let spread = i as f32 * 2.6 * k;
+                    self.bunkers.push(Bunker {
+                        center: Vec2::new(center.x + side.x * spread, center.y + side.y * spread),
+                        radius: 1.7 * k,
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn satisfies(&self, constraint: Constraint) -> bool {
+        let k = feature_scale(self.yards_per_tile);
+        match constraint {
+            Constraint::ReachableInShots(shots) => {
+                let reach = longest_full_shot_yd() * shots as f32;
+                self.length_yd() <= reach + self.green_radius * self.yards_per_tile
+            }
+            Constraint::GuardBunkerOnLine => {
+                self.line_hits_bunker(self.green_radius, self.green_radius + 3.0 * k)
+            }
+            Constraint::CarryHazardShortOfGreen => {
+                self.line_hits_bunker(self.green_radius + 2.0 * k, self.green_radius + 8.0 * k)
+            }
+        }
+    }
+
+    pub fn satisfies_all(&self) -> bool {
+        let tee_clear = !matches!(
+            self.surface(self.tee.x as i32, self.tee.y as i32),
+            Surface::Bunker | Surface::Water
+        );
+        tee_clear
+            && self
+                .template
+                .constraints()
+                .iter()
+                .all(|c| self.satisfies(*c))
+    }
+
+    // Walks the tee-to-pin line between `near` and `far` tiles short of the pin.
+    fn line_hits_bunker(&self, near: f32, far: f32) -> bool {
+        let dir = self.line_dir();
+        let mut back = near;
+        while back <= far {
+            let x = (self.pin.x - dir.x * back) as i32;
+            let y = (self.pin.y - dir.y * back) as i32;
+            if self.surface(x, y) == Surface::Bunker {
+                return true;
+            }
+            back += 0.5;
+        }
+        false
+    }
+
+    // Moves the cup away from the middle of the green; `fraction` of the
+    // green radius, in a random direction.
+    pub fn tuck_pin<R: Rng>(&mut self, fraction: f32, rng: &mut R) {
+        let mut angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let offset = self.green_radius * fraction.clamp(0.0, 0.9);
+        // Cups aren't cut on the contours; swing round to flat ground.
+        for _ in 0..4 {
+            self.pin = Vec2::new(
+                self.green_center.x + angle.cos() * offset,
+                self.green_center.y + angle.sin() * offset,
+            );
+            if self.bank_slope(self.pin.x, self.pin.y).length() == 0.0 {
+                break;
+            }
+            angle += std::f32::consts::FRAC_PI_2;
+        }
+    }
+
+    // Everything pulling a rolling ball downhill: the banks, and on the
+    // putting surface its tilt as well.
+    pub fn green_slope(&self, x: f32, y: f32) -> Vec2 {
+        let bank = self.bank_slope(x, y);
+        let (_, dist, _) = self.green_position(x, y);
+        if dist < self.green_radius {
+            Vec2::new(bank.x + self.contour.tilt.x, bank.y + self.contour.tilt.y)
+        } else {
+            bank
+        }
+    }
+
+    // Both contours fall away toward the tee: a false front sheds short balls
+    // back off the green, a backstop bank behind it feeds long ones back
+    // toward the middle.
+    pub fn bank_slope(&self, x: f32, y: f32) -> Vec2 {
+        let (dir, dist, along) = self.green_position(x, y);
+        let accel = if self.on_false_front(x, y) {
+            FALSE_FRONT_ACCEL
+        } else if self.contour.backstop
+            && dist < self.green_radius + BACKSTOP_BANK
+            && along > CONTOUR_BAND
+        {
+            BACKSTOP_ACCEL
+        } else {
+            0.0
+        };
+        Vec2::new(-dir.x * accel, -dir.y * accel)
+    }
+
+    pub fn on_false_front(&self, x: f32, y: f32) -> bool {
+        let (_, dist, along) = self.green_position(x, y);
+        self.contour.false_front && dist < self.green_radius && along < -CONTOUR_BAND
+    }
+
+    // Approach direction, distance from the middle of the green, and how far
+    // along the approach line the point sits in green radii.
+    fn green_position(&self, x: f32, y: f32) -> (Vec2, f32, f32) {
+        let dir = Vec2::new(
+            self.green_center.x - self.tee.x,
+            self.green_center.y - self.tee.y,
+        )
+        .normalized();
+        let (ox, oy) = (x - self.green_center.x, y - self.green_center.y);
+        let dist = (ox * ox + oy * oy).sqrt();
+        let along = (ox * dir.x + oy * dir.y) / self.green_radius;
+        (dir, dist, along)
+    }
+
+    pub fn pond_at(&self, x: f32, y: f32) -> Option<&Pond> {
+        self.ponds.iter().find(|p| p.contains(x, y))
+    }
+
+    pub fn length_yd(&self) -> f32 {
+        let dx = self.pin.x - self.tee.x;
+        let dy = self.pin.y - self.tee.y;
+        (dx * dx + dy * dy).sqrt() * self.yards_per_tile
+    }
+
+    pub fn surface(&self, x: i32, y: i32) -> Surface {
+        let xf = x as f32;
+        let yf = y as f32;
+
+        let green_dist =
+            ((xf - self.green_center.x).powi(2) + (yf - self.green_center.y).powi(2)).sqrt();
+        let in_bunker = self
+            .bunkers
+            .iter()
+            .any(|b| ((xf - b.center.x).powi(2) + (yf - b.center.y).powi(2)).sqrt() < b.radius);
+
+        let tee_dist = ((xf - self.tee.x).powi(2) + (yf - self.tee.y).powi(2)).sqrt();
+        let heavy = self.fairway.distance_outside(xf, yf) > HEAVY_ROUGH_TILES
+            && green_dist > self.green_radius + HEAVY_ROUGH_TILES
+            && tee_dist > HEAVY_ROUGH_TILES;
+
+        if green_dist < self.green_radius {
+            Surface::Green
+        } else if self.ponds.iter().any(|p| p.contains(xf, yf)) {
+            Surface::Water
+        } else if in_bunker {
+            Surface::Bunker
+        } else if green_dist < self.green_radius + FRINGE_WIDTH {
+            Surface::Fringe
+        } else if self.fairway.contains(xf, yf) {
+            Surface::Fairway
+        } else if heavy {
+            Surface::HeavyRough
+        } else {
+            Surface::Rough
+        }
+    }
+}
+
+pub fn longest_full_shot_yd() -> f32 {
+    CLUBS
+        .iter()
+        .filter(|c| !c.putter)
+        .map(|c| c.carry_yd + c.rollout_yd)
+        .fold(0.0, f32::max)
+}
+
+pub fn par_for_yardage(yards: f32) -> u32 {
+    if yards <= PAR3_MAX_YD {
+        3
+    } else if yards <= PAR4_MAX_YD {
+        4
+    } else {
+        5
+    }
+}
+
+// Generated greens, bunkers, ponds and fairways are sized in tiles at the
+// default scale; this stretches them to keep their size in yards at another.
+fn feature_scale(yards_per_tile: f32) -> f32 {
+    DEFAULT_YARDS_PER_TILE / yards_per_tile
+}
@@ -0,0 +1,136 @@
+use std::io::Write;
+
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+
+use crate::camera::Camera;
+use crate::game::{Game, HEIGHT, WIDTH};
+use crate::raster::{self, Canvas, CourseKey, Rgb};
+
+// The terminal splits image data into chunks of this much base64.
+const CHUNK: usize = 4096;
+const COURSE_ID: u32 = 1;
+// The terminal scales images into their cells, so these only set how fine
+// the course and the balls are drawn.
+const CELL_PX: (usize, usize) = (4, 8);
+const SPRITE_PX: (usize, usize) = (12, 24);
+// The course sits under the text, and the balls between the two.
+const COURSE_Z: i32 = -2;
+const SPRITE_Z: i32 = -1;
+
+// Terminals that speak the kitty graphics protocol say so in the
+// environment, over SSH too where TERM comes along.
+pub fn supported() -> bool {
+    std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || std::env::var("TERM").is_ok_and(|term| term == "xterm-kitty")
+        || std::env::var("TERM_PROGRAM")
+            .is_ok_and(|term| matches!(term.as_str(), "ghostty" | "WezTerm"))
+}
+
+// Draws the map as a raster image with sprite balls over it. Images stay
+// stored in the terminal, so each frame only places them again unless the
+// view has changed.
+#[derive(Default)]
+pub struct KittyView {
+    course: Option<CourseKey>,
+    sprites: Vec<Rgb>,
+}
+
+impl KittyView {
+    pub fn draw_course(
+        &mut self,
+        out: &mut impl Write,
+        game: &Game,
+        camera: Camera,
+        tile: impl Fn(i32, i32) -> Rgb,
+        (left, top): (u16, u16),
+    ) -> std::io::Result<()> {
+        let key = CourseKey::of(game, camera);
+        if self.course != Some(key) {
+            let size = (game.layout.width, game.layout.height);
+            transmit(
+                out,
+                COURSE_ID,
+                &raster::course(camera, size, tile, game.hole, CELL_PX),
+            )?;
+            self.course = Some(key);
+        }
+        queue!(out, MoveTo(left, top))?;
+        place(out, COURSE_ID, (WIDTH as u16, HEIGHT as u16), COURSE_Z)
+    }
+
+    // A ball in `color` over the map cell at `cell`.
+    pub fn draw_ball(
+        &mut self,
+        out: &mut impl Write,
+        color: Rgb,
+        cell: (u16, u16),
+    ) -> std::io::Result<()> {
+        let id = match self.sprites.iter().position(|&c| c == color) {
+            Some(i) => COURSE_ID + 1 + i as u32,
+            None => {
+                self.sprites.push(color);
+                let id = COURSE_ID + self.sprites.len() as u32;
+                transmit(out, id, &raster::ball_sprite(color, SPRITE_PX))?;
+                id
+            }
+        };
+        queue!(out, MoveTo(cell.0, cell.1))?;
+        place(out, id, (1, 1), SPRITE_Z)
+    }
+
+    // Frees every image, for the way out.
+    pub fn forget(&mut self, out: &mut impl Write) -> std::io::Result<()> {
+        self.course = None;
+        self.sprites.clear();
+        write!(out, "\x1b_Ga=d,d=A,q=2\x1b\\")
+    }
+}
+
+fn transmit(out: &mut impl Write, id: u32, canvas: &Canvas) -> std::io::Result<()> {
+    let data = base64(&canvas.rgba());
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(CHUNK).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = (i + 1 < chunks.len()) as u8;
+        if i == 0 {
+            write!(
+                out,
+                "\x1b_Ga=t,f=32,s={},v={},i={},q=2,m={};",
+                canvas.width, canvas.height, id, more
+            )?;
+        } else {
+            write!(out, "\x1b_Gm={};", more)?;
+        }
+        out.write_all(chunk)?;
+        write!(out, "\x1b\\")?;
+    }
+    Ok(())
+}
+
+// Scaled to fill `cells` from the cursor, which is left where it was.
+fn place(out: &mut impl Write, id: u32, cells: (u16, u16), z: i32) -> std::io::Result<()> {
+    write!(
+        out,
+        "\x1b_Ga=p,i={},p=1,c={},r={},z={},C=1,q=2\x1b\\",
+        id, cells.0, cells.1, z
+    )
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for group in bytes.chunks(3) {
+        let n = group
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= group.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
@@ -1,65 +1,572 @@
-use std::io::{stdout, Stdout};
+use std::io::{stdout, IsTerminal, Stdout, Write};
+use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::{Duration, Instant};
 
 use crossterm::cursor::{Hide, Show};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{
+    self, DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange,
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+    PushKeyboardEnhancementFlags,
+};
 use crossterm::execute;
+use crossterm::style::Print;
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 
+mod ai;
+mod awards;
+mod bag;
+mod camera;
+mod chart;
+mod conditions;
+mod config;
+mod course;
+mod difficulty;
+mod energy;
+mod footprint;
+mod foursomes;
+mod gallery;
 mod game;
+mod ghost;
+mod heat_map;
+mod hole;
+mod kitty;
+mod match_log;
+mod match_play;
+mod narration;
+mod onboarding;
+mod pressure;
+mod profile;
+mod raster;
+mod recap;
 mod render;
+mod replay;
+mod rules;
+mod save;
+mod screen;
+mod settings;
+mod shadow;
+mod shop;
+mod sixel;
+mod speed_round;
+mod stats;
+mod storage;
+mod terrain;
+mod thumbnail;
+mod tournament;
+mod tutorial;
+mod warm_up;
+mod wind;
 
-use game::{wrap_angle_rad, Game, TICK_MS};
+use ai::AiSkill;
+use conditions::GroundConditions;
+use course::notes::{self, NoteBook};
+use course::pack::{self, Pack};
+use course::{Course, CourseRound, StartOption};
+use game::{Game, StatsPage};
+use kitty::KittyView;
+use raster::Graphics;
+use save::SavedRound;
+use screen::Screen;
+use settings::{HudLayout, Settings, Units};
+use sixel::SixelView;
+use speed_round::SpeedRound;
+use tutorial::TutorialStep;
+
+// More typed keys than this in one read are a paste on a terminal without
+// bracketed paste, not someone playing.
+const PASTE_BURST_KEYS: usize = 8;
 
 fn main() -> std::io::Result<()> {
+    if let Some(dir) =
+        std::env::args().find_map(|arg| arg.strip_prefix("--data-dir=").map(PathBuf::from))
+    {
+        storage::set_root(dir);
+    }
+    if let Some(output) = course_command() {
+        let output = output.map_err(std::io::Error::other)?;
+        print!("{}", output);
+        return Ok(());
+    }
+    if storage::prepare()? {
+        welcome();
+    }
+    let text_mode = std::env::args().any(|arg| arg == "--text");
+    let speed_round = std::env::args().any(|arg| arg == "--speed-round");
+    // The speed round keeps no profile, so there's no one to ask about.
+    let profile = if speed_round && !text_mode {
+        String::new()
+    } else if onboarding::due() {
+        onboarding::run(named_profile().transpose().map_err(std::io::Error::other)?)?
+    } else {
+        profile_from_args().map_err(std::io::Error::other)?
+    };
+    let settings = settings_from_args(config::Config::load().settings());
+    let course_round = course_from_args().map_err(std::io::Error::other)?;
+    let resumed = resume_from_args(course_round.is_some()).map_err(std::io::Error::other)?;
+    let on_course =
+        course_round.is_some() || resumed.as_ref().is_some_and(|(_, round)| round.is_some());
+    if settings.tournament && !on_course {
+        return Err(std::io::Error::other(
+            "--tournament is played over a course; pick one with --course=FILE",
+        ));
+    }
+    if settings.ghost && !on_course {
+        return Err(std::io::Error::other(
+            "--ghost replays your best round of a course; pick one with --course=FILE",
+        ));
+    }
+
     let mut stdout = stdout();
-    setup_terminal(&mut stdout)?;
+    let release_events = setup_terminal(&mut stdout, text_mode)?;
 
-    let mut game = Game::new();
-    let result = run_game_loop(&mut stdout, &mut game);
+    let result = if speed_round && !text_mode {
+        let mut round = SpeedRound::new();
+        round.apply_settings(settings);
+        if std::env::args().any(|arg| arg == "--shadow-rival") {
+            // The speed round keeps no profile, but the rival can still be
+            // built from a named player's stats.
+            let name = named_profile()
+                .and_then(Result::ok)
+                .unwrap_or_else(|| profile::DEFAULT_NAME.to_string());
+            round.set_shadow(shadow::ShadowRival::from_stats(
+                &stats::HoleSideStats::load(&name),
+            ));
+        }
+        run_speed_round_loop(&mut Screen::new(std::io::stdout()), &mut round)
+    } else {
+        let mut game = Game::new();
+        game.settings = settings;
+        game.tutorial = settings.tutorial.then_some(TutorialStep::Aim);
+        game.stats = stats::HoleSideStats::load(&profile);
+        game.profile = profile::Profile::load(&profile);
+        game.gallery = gallery::Gallery::load();
+        game.notes = NoteBook::load();
+        match (resumed, course_round) {
+            (Some((saved, round)), _) => game.resume(saved, round),
+            (None, Some(round)) => game.start_course(round),
+            (None, None) => {
+                game.start_round();
+                game.start_match();
+                game.start_foursomes();
+            }
+        }
+        let mut screen = Screen::new(std::io::stdout());
+        let mut graphics = graphics_from_settings(&settings, text_mode);
+        let result = run_game_loop(
+            &mut screen,
+            &mut game,
+            graphics.as_mut(),
+            text_mode,
+            release_events,
+        );
+        if let Some(graphics) = graphics.as_mut() {
+            graphics.forget(&mut stdout)?;
+        }
+        if let Err(err) = game.profile.save() {
+            storage::notify(format!("Couldn't save your profile: {}.", err));
+        }
+        if let Err(err) = save::store(&game) {
+            storage::notify(format!(
+                "Couldn't save the round to pick up later: {}.",
+                err
+            ));
+        }
+        result
+    };
 
-    restore_terminal(&mut stdout)?;
+    restore_terminal(&mut stdout, text_mode, release_events)?;
+    for notice in storage::take_notices() {
+        eprintln!("{}", notice);
+    }
     result
 }
 
-fn setup_terminal(stdout: &mut Stdout) -> std::io::Result<()> {
-    execute!(stdout, EnterAlternateScreen, Hide)?;
+// Says where things will be kept, the first time the game is run, ahead of
+// the questions that set the game up.
+fn welcome() {
+    let shown =
+        |dir: Option<PathBuf>| dir.map_or("nowhere".to_string(), |d| d.display().to_string());
+    println!("Welcome to Terminal Golf!");
+    println!();
+    println!("Your profile, stats and saved rounds will be kept in");
+    println!("  {}", shown(storage::data_dir()));
+    println!("and debug logs in {}.", shown(storage::log_dir()));
+    println!("Start with --data-dir=DIR to keep everything in one place instead.");
+    println!();
+}
+
+// Kitty images when asked for and the terminal has them, then sixels, which
+// the terminal can't be asked about and are taken on trust.
+fn graphics_from_settings(settings: &Settings, text_mode: bool) -> Option<Graphics> {
+    if text_mode {
+        None
+    } else if settings.kitty && kitty::supported() {
+        Some(Graphics::Kitty(KittyView::default()))
+    } else if settings.sixel {
+        Some(Graphics::Sixel(SixelView::new()))
+    } else {
+        None
+    }
+}
+
+// The flags on top of `settings`, which come from the config file.
+fn settings_from_args(mut settings: Settings) -> Settings {
+    for arg in std::env::args() {
+        match arg.as_str() {
+            "--no-focus-pause" => settings.pause_on_focus_loss = false,
+            "--no-afk-autoplay" => settings.afk_autoplay = false,
+            "--balanced-holes" => settings.balanced_holes = true,
+            "--slope-shading" => settings.slope_shading = true,
+            "--distance-arcs" => settings.distance_arcs = true,
+            "--heat-map" => settings.heat_map = true,
+            "--left-handed" => settings.left_handed = true,
+            "--right-handed" => settings.left_handed = false,
+            "--tutorial" => settings.tutorial = true,
+            "--warm-up" => settings.warm_up = true,
+            "--mud-balls" => settings.mud_balls = true,
+            "--pressure-putts" => settings.pressure_putts = true,
+            "--low-bandwidth" => settings.low_bandwidth = true,
+            "--match-play" => settings.match_play = true,
+            "--foursomes" => settings.foursomes = true,
+            "--tournament" => settings.tournament = true,
+            "--ghost" => settings.ghost = true,
+            "--ai" => settings.ai = Some(AiSkill::default()),
+            "--kitty" => settings.kitty = true,
+            "--sixel" => settings.sixel = true,
+            "--energy-audit" => settings.energy_audit = true,
+            _ => {
+                if let Some(secs) = arg.strip_prefix("--idle-hint=") {
+                    settings.idle_hint_secs = secs.parse().unwrap_or(settings.idle_hint_secs);
+                } else if let Some(ms) = arg.strip_prefix("--tick-ms=") {
+                    if let Ok(ms) = ms.parse() {
+                        settings.set_tick_ms(ms);
+                    }
+                } else if let Some(fps) = arg.strip_prefix("--fps=") {
+                    if let Ok(fps) = fps.parse() {
+                        settings.set_fps_cap(fps);
+                    }
+                } else if let Some(skill) = arg.strip_prefix("--ai=") {
+                    settings.ai = AiSkill::from_arg(skill).or(settings.ai);
+                } else if let Some(units) = arg.strip_prefix("--units=") {
+                    settings.units = Units::from_arg(units).unwrap_or(settings.units);
+                } else if let Some(layout) = arg.strip_prefix("--hud=") {
+                    settings.hud = HudLayout::from_arg(layout).unwrap_or(settings.hud);
+                } else if let Some(preset) = arg.strip_prefix("--course-conditions=") {
+                    settings.course_conditions =
+                        GroundConditions::from_arg(preset).unwrap_or(settings.course_conditions);
+                } else {
+                    course_condition_from_arg(&arg, &mut settings.course_conditions);
+                }
+            }
+        }
+    }
+    settings
+}
+
+// Fine-tunes the course conditions one value at a time, after any preset.
+fn course_condition_from_arg(arg: &str, conditions: &mut GroundConditions) {
+    let Some((name, value)) = arg.split_once('=') else {
+        return;
+    };
+    let target = match name {
+        "--green-firmness" => &mut conditions.green_firmness,
+        "--fairway-firmness" => &mut conditions.fairway_firmness,
+        "--green-speed" => &mut conditions.green_speed,
+        _ => return,
+    };
+    if let Ok(value) = value.parse::<f32>() {
+        *target = value.max(0.0);
+    }
+}
+
+// `--import=PACK` installs a course pack and `--list-courses` shows every
+// course there is to play; both print and exit without starting a game.
+fn course_command() -> Option<Result<String, String>> {
+    for arg in std::env::args() {
+        if let Some(path) = arg.strip_prefix("--import=") {
+            return Some(pack::import(Path::new(path)).map(|pack| {
+                format!(
+                    "Installed {} with {} courses\n{}",
+                    pack.name,
+                    pack.courses.len(),
+                    list_pack(&pack, &NoteBook::load())
+                )
+            }));
+        }
+        if arg == "--list-courses" {
+            let book = NoteBook::load();
+            let mut out = "Built in\n  classic\n".to_string();
+            for pack in pack::installed() {
+                out.push_str(&list_pack(&pack, &book));
+            }
+            return Some(Ok(out));
+        }
+    }
+    None
+}
+
+fn list_pack(pack: &Pack, book: &NoteBook) -> String {
+    let mut out = match &pack.version {
+        Some(version) => format!("{} (v{})\n", pack.name, version),
+        None => format!("{}\n", pack.name),
+    };
+    for (file, course) in &pack.courses {
+        let rated = match book.course(&course.name) {
+            Some(notes) if notes.rating.is_some() || !notes.holes.is_empty() => format!(
+                "  {} {} notes",
+                notes::stars(notes.rating),
+                notes.holes.len()
+            ),
+            _ => String::new(),
+        };
+        out.push_str(&format!(
+            "  --course={}/{}  {} ({} holes){}\n",
+            pack.id,
+            file,
+            course.name,
+            course.holes.len(),
+            rated
+        ));
+    }
+    out
+}
+
+// Whose profile the game is played on: the one named with `--profile=NAME`,
+// new or not, or else asked for when anyone but the first player has one.
+fn profile_from_args() -> Result<String, String> {
+    if let Some(name) = named_profile() {
+        return name;
+    }
+    let names = profile::names();
+    let Some(last) = names.first().filter(|_| names != [profile::DEFAULT_NAME]) else {
+        return Ok(profile::DEFAULT_NAME.to_string());
+    };
+    if !std::io::stdin().is_terminal() {
+        return Ok(last.clone());
+    }
+    println!("Who's playing?");
+    for (i, name) in names.iter().enumerate() {
+        println!("  {}. {}", i + 1, name);
+    }
+    print!("Number, a new name, or Enter for {}: ", last);
+    std::io::stdout().flush().map_err(|err| err.to_string())?;
+    let mut line = String::new();
+    std::io::stdin()
+        .read_line(&mut line)
+        .map_err(|err| err.to_string())?;
+    match line.trim() {
+        "" => Ok(last.clone()),
+        choice => match choice.parse::<usize>() {
+            Ok(n) => names
+                .get(n.wrapping_sub(1))
+                .cloned()
+                .ok_or_else(|| format!("there's no profile {}", n)),
+            Err(_) => profile::check_name(choice),
+        },
+    }
+}
+
+fn named_profile() -> Option<Result<String, String>> {
+    std::env::args().find_map(|arg| arg.strip_prefix("--profile=").map(profile::check_name))
+}
+
+// `--course=FILE` (or a built-in course name) plays a routed course, from
+// the first tee unless `--back-nine` or `--shotgun=HOLE` says otherwise.
+fn course_from_args() -> Result<Option<CourseRound>, String> {
+    let mut path = None;
+    let mut start = StartOption::FirstTee;
+    for arg in std::env::args() {
+        if let Some(file) = arg.strip_prefix("--course=") {
+            path = Some(PathBuf::from(file));
+        } else if arg == "--back-nine" {
+            start = StartOption::BackNineOnly;
+        } else if let Some(hole) = arg.strip_prefix("--shotgun=") {
+            let hole = hole
+                .parse()
+                .map_err(|_| format!("--shotgun needs a hole number, got `{}`", hole))?;
+            start = StartOption::Shotgun(hole);
+        }
+    }
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    CourseRound::new(Course::load(&path)?, start).map(Some)
+}
+
+// The round saved on the way out last time, with `--resume`.
+fn resume_from_args(
+    course_given: bool,
+) -> Result<Option<(SavedRound, Option<CourseRound>)>, String> {
+    if !std::env::args().any(|arg| arg == "--resume") {
+        return Ok(None);
+    }
+    if course_given {
+        return Err(
+            "--resume goes back to the saved round's own course; leave out --course".to_string(),
+        );
+    }
+    save::load()?
+        .ok_or_else(|| "there's no saved round to resume".to_string())
+        .map(Some)
+}
+
+// Returns whether the terminal will report key releases, which hold-to-putt
+// needs; without them a second press releases the putt instead.
+fn setup_terminal(stdout: &mut Stdout, text_mode: bool) -> std::io::Result<bool> {
+    if !text_mode {
+        execute!(stdout, EnterAlternateScreen, Hide)?;
+    }
+    // Saves the shell's title on xterm-likes, to put back on the way out.
+    execute!(stdout, Print("\x1b[22;0t"))?;
     terminal::enable_raw_mode()?;
-    Ok(())
+    execute!(stdout, EnableFocusChange, EnableBracketedPaste)?;
+
+    let release_events = terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if release_events {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+        )?;
+    }
+    Ok(release_events)
 }
 
-fn restore_terminal(stdout: &mut Stdout) -> std::io::Result<()> {
+fn restore_terminal(
+    stdout: &mut Stdout,
+    text_mode: bool,
+    release_events: bool,
+) -> std::io::Result<()> {
+    if release_events {
+        execute!(stdout, PopKeyboardEnhancementFlags)?;
+    }
+    execute!(stdout, DisableBracketedPaste, DisableFocusChange)?;
     terminal::disable_raw_mode()?;
-    execute!(stdout, Show, LeaveAlternateScreen)?;
+    if !text_mode {
+        execute!(stdout, Show, LeaveAlternateScreen)?;
+    }
+    execute!(stdout, Print("\x1b[23;0t"))?;
     Ok(())
 }
 
-fn run_game_loop(stdout: &mut Stdout, game: &mut Game) -> std::io::Result<()> {
+fn run_game_loop(
+    screen: &mut Screen<Stdout>,
+    game: &mut Game,
+    mut graphics: Option<&mut Graphics>,
+    text_mode: bool,
+    release_events: bool,
+) -> std::io::Result<()> {
     let mut last_tick = Instant::now();
+    let mut last_frame: Option<Instant> = None;
+    // A step has happened since the last frame was drawn.
+    let mut stepped = false;
+    let mut last_description = String::new();
 
     loop {
-        while event::poll(Duration::from_millis(0))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
+        for event in read_input()? {
+            match event {
+                Event::FocusLost => game.focus_lost(),
+                Event::FocusGained => game.focus_gained(),
+                Event::Resize(..) => {
+                    screen.repaint();
+                    if let Some(graphics) = graphics.as_deref_mut() {
+                        graphics.resize();
+                    }
+                }
+                _ => {}
+            }
+            if let Event::Key(key) = event {
+                let hit_key = matches!(key.code, KeyCode::Enter | KeyCode::Char(' '));
+                if key.kind == KeyEventKind::Release && hit_key {
+                    game.release_hit();
+                } else if key.kind == KeyEventKind::Press {
+                    let quit_key = matches!(key.code, KeyCode::Esc | KeyCode::Char('q'));
+                    game.note_input();
+                    if game.note_draft.is_some() {
+                        route_note_key(game, key.code);
+                        continue;
+                    }
+                    // Some terminals never report focus coming back.
+                    if game.paused && !quit_key {
+                        game.focus_gained();
+                        continue;
+                    }
+                    if game.replay.is_some() && !quit_key {
+                        game.end_replay();
+                        continue;
+                    }
+                    if game.presentation.is_some() && !quit_key {
+                        game.skip_presentation();
+                        continue;
+                    }
+                    let between_pages = game.between_holes.is_some()
+                        || game.round_card.is_some()
+                        || game.show_leaderboard;
+                    if between_pages && !quit_key {
+                        game.next_hole();
+                        continue;
+                    }
+                    if game.hole_browser.is_some() && route_browser_key(game, key.code) {
+                        continue;
+                    }
+                    if game.target_cursor.is_some() && route_target_key(game, key.code) {
+                        continue;
+                    }
+                    if game.relief.is_some() && route_relief_key(game, key.code) {
+                        continue;
+                    }
+                    if game.stats_page == StatsPage::Gallery && route_gallery_key(game, key.code) {
+                        continue;
+                    }
+                    if game.stats_page == StatsPage::ProShop && route_shop_key(game, key.code) {
+                        continue;
+                    }
+                    if game.stats_page == StatsPage::Scorecard
+                        && route_scorecard_key(game, key.code)
+                    {
+                        continue;
+                    }
                     match key.code {
                         KeyCode::Esc | KeyCode::Char('q') => return Ok(()),
                         KeyCode::Char('r') => game.reset(),
-                        KeyCode::Left | KeyCode::Char('a') => {
-                            if game.can_shoot() {
-                                game.angle = wrap_angle_rad(game.angle - game.aim_step());
-                            }
-                        }
-                        KeyCode::Right | KeyCode::Char('d') => {
-                            if game.can_shoot() {
-                                game.angle = wrap_angle_rad(game.angle + game.aim_step());
-                            }
-                        }
+                        KeyCode::Char('n') => game.next_hole(),
+                        KeyCode::Left | KeyCode::Char('a') => game.adjust_aim(-1),
+                        KeyCode::Right | KeyCode::Char('d') => game.adjust_aim(1),
                         KeyCode::Char('w') | KeyCode::Up => game.cycle_club(1),
                         KeyCode::Char('s') | KeyCode::Down => game.cycle_club(-1),
                         KeyCode::Char('e') => game.cycle_shot_type(),
+                        KeyCode::Char('E') => game.cycle_shot_shape(),
                         KeyCode::Char('c') => game.toggle_auto_caddie(),
-                        KeyCode::Enter | KeyCode::Char(' ') => game.hit_ball(),
+                        KeyCode::Char('f') => game.accept_caddie_aim(),
+                        KeyCode::Char('x') => game.toggle_target_mode(),
+                        KeyCode::Char('b') => game.bank_aim_preset(),
+                        KeyCode::Char('j') => game.cycle_aim_preset(),
+                        KeyCode::Char('o') => game.flip_fringe_preview(),
+                        KeyCode::Char('l') => game.toggle_wind_on_putts(),
+                        KeyCode::Char('y') => game.toggle_pressure_putts(),
+                        KeyCode::Char('t') => game.cycle_theme(),
+                        KeyCode::Tab => game.cycle_hud(),
+                        KeyCode::Char('.') => game.toggle_slope_shading(),
+                        KeyCode::Char(',') => game.toggle_distance_arcs(),
+                        KeyCode::Char('H') => game.toggle_heat_map(),
+                        KeyCode::Char('z') => game.toggle_instant_camera(),
+                        KeyCode::Char('g') => game.cycle_stats_screen(),
+                        KeyCode::Char('h') => game.toggle_help(),
+                        KeyCode::Char('k') => game.cycle_conditions(),
+                        KeyCode::Char('u') => game.cycle_challenge(),
+                        KeyCode::Char('U') => game.declare_unplayable(),
+                        KeyCode::Char('v') => game.toggle_high_contrast(),
+                        KeyCode::Char('m') => game.toggle_reduced_motion(),
+                        KeyCode::Char('B') => game.toggle_low_bandwidth(),
+                        KeyCode::Char('i') => last_description.clear(),
+                        KeyCode::Char('p') => game.toggle_pull_back_putting(),
+                        KeyCode::Enter | KeyCode::Char(' ') => {
+                            if game.putt_charge.is_some() && !release_events {
+                                game.release_hit();
+                            } else {
+                                game.press_hit();
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -68,12 +575,249 @@ fn run_game_loop(stdout: &mut Stdout, game: &mut Game) -> std::io::Result<()> {
 
         let now = Instant::now();
         let dt = now.duration_since(last_tick);
-        if dt.as_millis() >= TICK_MS as u128 {
-            game.update(dt.as_secs_f32());
-            render::draw(stdout, game)?;
+        let tick_ms = game.settings.tick_ms;
+        if dt.as_millis() >= tick_ms as u128 {
+            if !game.paused {
+                game.profile.add_play_time(dt.as_secs_f64());
+                game.update(dt.as_secs_f32());
+            }
             last_tick = now;
+            stepped = true;
+        }
+
+        // Between steps only a moving ball has anything new to show.
+        let moving = game.in_motion() && !game.paused && !text_mode;
+        let frame_ms = if moving {
+            game.settings.motion_frame_ms()
+        } else {
+            game.settings.frame_ms()
+        } as u128;
+        let frame_due =
+            last_frame.is_none_or(|frame| now.duration_since(frame).as_millis() >= frame_ms);
+        if frame_due && (stepped || moving) {
+            screen.set_low_bandwidth(game.settings.low_bandwidth && !text_mode);
+            game.slow_link = screen.slow_link();
+            game.frame_alpha =
+                now.duration_since(last_tick).as_secs_f32() * 1000.0 / tick_ms as f32;
+            let (title, progress) = render::terminal_status(game);
+            screen.report(&title, progress)?;
+            if text_mode {
+                narrate(screen, game, &mut last_description)?;
+            } else {
+                let view = graphics.as_deref_mut();
+                render::draw(screen, game, view.filter(|_| !game.settings.low_bandwidth))?;
+            }
+            last_frame = Some(now);
+            stepped = false;
         } else {
             thread::sleep(Duration::from_millis(1));
         }
     }
 }
+
+// Two players share the keyboard: WASD + E + Space against the arrows + / +
+// Enter. `Q` sits in the left cluster, so only Esc quits here.
+fn run_speed_round_loop(
+    screen: &mut Screen<Stdout>,
+    round: &mut SpeedRound,
+) -> std::io::Result<()> {
+    let mut last_tick = Instant::now();
+    let mut last_frame: Option<Instant> = None;
+
+    loop {
+        for event in read_input()? {
+            if let Event::Key(key) = event {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                if round.viewer.is_some() {
+                    round.close_replay();
+                    continue;
+                }
+                if round.summary.is_some() && route_summary_key(round, key.code) {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('r') => round.rematch(),
+                    KeyCode::Char('n') => round.new_hole(),
+                    KeyCode::Char('m') => round.toggle_summary(),
+                    code => route_speed_round_key(round, code),
+                }
+            }
+        }
+
+        let settings = round.players[0].settings;
+        let now = Instant::now();
+        let dt = now.duration_since(last_tick);
+        if dt.as_millis() >= settings.tick_ms as u128 {
+            round.update(dt.as_secs_f32());
+            let frame_ms = settings.frame_ms() as u128;
+            if last_frame.is_none_or(|frame| now.duration_since(frame).as_millis() >= frame_ms) {
+                screen.set_low_bandwidth(settings.low_bandwidth);
+                screen.report(&render::speed_round_title(round), None)?;
+                render::draw_speed_round(screen, round)?;
+                last_frame = Some(now);
+            }
+            last_tick = now;
+        } else {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+// Drains everything waiting. Pasted text is thrown away whole, whether it
+// arrives bracketed or as a burst of keystrokes.
+fn read_input() -> std::io::Result<Vec<Event>> {
+    let mut events = Vec::new();
+    while event::poll(Duration::from_millis(0))? {
+        match event::read()? {
+            Event::Paste(_) => {}
+            event => events.push(event),
+        }
+    }
+    if events.iter().filter(|e| is_typed_key(e)).count() > PASTE_BURST_KEYS {
+        events.retain(|e| !is_typed_key(e));
+    }
+    Ok(events)
+}
+
+fn is_typed_key(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(_) | KeyCode::Enter | KeyCode::Tab,
+            kind: KeyEventKind::Press,
+            ..
+        })
+    )
+}
+
+// The hole browser takes over browsing keys, Enter and Esc; `Q` still quits.
+fn route_browser_key(game: &mut Game, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Char('a') | KeyCode::Left => game.move_browser_selection(-1),
+        KeyCode::Char('d') | KeyCode::Right => game.move_browser_selection(1),
+        KeyCode::Char(c @ '1'..='9') => {
+            if game.select_browsed_hole(c as usize - '1' as usize) {
+                game.choose_browsed_hole();
+            }
+        }
+        KeyCode::Enter | KeyCode::Char(' ') => game.choose_browsed_hole(),
+        KeyCode::Char('n') => game.open_hole_browser(),
+        KeyCode::Esc => game.close_hole_browser(),
+        _ => return false,
+    }
+    true
+}
+
+// The gallery screen flips through snapshots with the aim keys.
+fn route_gallery_key(game: &mut Game, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Char('a') | KeyCode::Left => game.gallery.browse(-1),
+        KeyCode::Char('d') | KeyCode::Right => game.gallery.browse(1),
+        _ => return false,
+    }
+    true
+}
+
+fn route_shop_key(game: &mut Game, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Char('a') | KeyCode::Left => game.browse_shop(-1),
+        KeyCode::Char('d') | KeyCode::Right => game.browse_shop(1),
+        KeyCode::Enter => game.buy_or_equip_selected(),
+        _ => return false,
+    }
+    true
+}
+
+// On the scorecard the digits rate the course and Enter opens the yardage
+// book note for the hole in play.
+fn route_scorecard_key(game: &mut Game, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Char(c @ '1'..='5') => game.rate_course(c as u8 - b'0'),
+        KeyCode::Enter => game.start_note(),
+        _ => return false,
+    }
+    true
+}
+
+// Everything typed goes into the note until Enter keeps it or Esc drops it.
+fn route_note_key(game: &mut Game, code: KeyCode) {
+    match code {
+        KeyCode::Char(c) => game.type_note(c),
+        KeyCode::Backspace => game.erase_note_char(),
+        KeyCode::Enter => game.finish_note(),
+        KeyCode::Esc => game.cancel_note(),
+        _ => {}
+    }
+}
+
+// While picking a target the movement keys drive the cursor instead of the
+// aim and club; everything else falls through to the normal controls.
+fn route_target_key(game: &mut Game, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Char('a') | KeyCode::Left => game.move_target(-1, 0),
+        KeyCode::Char('d') | KeyCode::Right => game.move_target(1, 0),
+        KeyCode::Char('w') | KeyCode::Up => game.move_target(0, -1),
+        KeyCode::Char('s') | KeyCode::Down => game.move_target(0, 1),
+        _ => return false,
+    }
+    true
+}
+
+// After a penalty or with an embedded ball the number keys pick how to go
+// on; the ball moves as soon as one is picked, and hitting it settles it.
+fn route_relief_key(game: &mut Game, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Char(c @ '1'..='9') => game.choose_relief(c as usize - '1' as usize),
+        _ => return false,
+    }
+    true
+}
+
+// The match summary picks a moment with either player's club keys and plays
+// it back with either hit key.
+fn route_summary_key(round: &mut SpeedRound, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Char('w') | KeyCode::Up => round.move_summary(-1),
+        KeyCode::Char('s') | KeyCode::Down => round.move_summary(1),
+        KeyCode::Enter | KeyCode::Char(' ') => round.replay_selected(),
+        KeyCode::Esc => round.toggle_summary(),
+        _ => return false,
+    }
+    true
+}
+
+fn route_speed_round_key(round: &mut SpeedRound, code: KeyCode) {
+    let player = match code {
+        KeyCode::Char('a' | 'd' | 'w' | 's' | 'e' | ' ') => 0,
+        KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down => 1,
+        KeyCode::Char('/') | KeyCode::Enter => 1,
+        _ => return,
+    };
+    let Some(game) = round.player_mut(player) else {
+        return;
+    };
+    game.note_input();
+
+    match code {
+        KeyCode::Char('a') | KeyCode::Left => game.adjust_aim(-1),
+        KeyCode::Char('d') | KeyCode::Right => game.adjust_aim(1),
+        KeyCode::Char('w') | KeyCode::Up => game.cycle_club(1),
+        KeyCode::Char('s') | KeyCode::Down => game.cycle_club(-1),
+        KeyCode::Char('e') | KeyCode::Char('/') => game.cycle_shot_type(),
+        _ => game.hit_ball(),
+    }
+}
+
+// Raw mode needs explicit carriage returns to keep lines flush left.
+fn narrate(stdout: &mut impl Write, game: &Game, last: &mut String) -> std::io::Result<()> {
+    let description = narration::describe(game);
+    if description != *last {
+        write!(stdout, "{}\r\n", description)?;
+        stdout.flush()?;
+        *last = description;
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use crate::hole::HoleLayout;
+use crate::replay::Replay;
+
+// Putts holed from at least this far count as a swing in the match.
+pub const BIG_PUTT_FT: f32 = 15.0;
+
+#[derive(Clone, Copy)]
+pub enum MomentKind {
+    HoleWon(usize),
+    Halved,
+    BigPutt { player: usize, feet: f32 },
+    Penalty(usize),
+}
+
+impl MomentKind {
+    pub fn describe(self, names: [&str; 2]) -> String {
+        match self {
+            MomentKind::HoleWon(i) => format!("{} wins the hole", names[i]),
+            MomentKind::Halved => "Hole halved".to_string(),
+            MomentKind::BigPutt { player, feet } => {
+                format!("{} drains a {:.0} ft putt", names[player], feet)
+            }
+            MomentKind::Penalty(i) => format!("{} takes a penalty (+1)", names[i]),
+        }
+    }
+}
+
+pub struct MatchMoment {
+    pub hole: usize,
+    pub kind: MomentKind,
+    // Holes player one is up once this has happened.
+    pub standing: i32,
+    pub replay: Option<Replay>,
+    pub layout: HoleLayout,
+}
+
+// Every race in a session is a hole of one match play match; the log keeps
+// the swings in it, each with the ball's last moments to watch again.
+#[derive(Default)]
+pub struct MatchLog {
+    pub moments: Vec<MatchMoment>,
+    pub holes: usize,
+    pub standing: i32,
+}
+
+impl MatchLog {
+    pub fn record(&mut self, kind: MomentKind, replay: Option<Replay>, layout: &HoleLayout) {
+        let hole = self.holes + 1;
+        match kind {
+            MomentKind::HoleWon(0) => self.standing += 1,
+            MomentKind::HoleWon(_) => self.standing -= 1,
+            _ => {}
+        }
+        if matches!(kind, MomentKind::HoleWon(_) | MomentKind::Halved) {
+            self.holes = hole;
+        }
+        self.moments.push(MatchMoment {
+            hole,
+            kind,
+            standing: self.standing,
+            replay,
+            layout: layout.clone(),
+        });
+    }
+
+    // The standing before the first moment and after each one since.
+    pub fn momentum(&self) -> Vec<f32> {
+        std::iter::once(0.0)
+            .chain(self.moments.iter().map(|m| m.standing as f32))
+            .collect()
+    }
+}
+
+pub fn standing_text(standing: i32, names: [&str; 2]) -> String {
+    match standing {
+        0 => "All square".to_string(),
+        up if up > 0 => format!("{} {} UP", names[0], up),
+        down => format!("{} {} UP", names[1], -down),
+    }
+}
@@ -0,0 +1,107 @@
+// A generated-hole match runs this long; on a course it's the round.
+pub const MATCH_HOLES: u32 = 9;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HoleResult {
+    Won,
+    Lost,
+    Halved,
+    // The player couldn't halve any more and picked up.
+    Conceded,
+    // The rival couldn't halve the score the player posted.
+    RivalConceded,
+}
+
+impl HoleResult {
+    pub fn describe(self) -> &'static str {
+        match self {
+            HoleResult::Won => "Hole won",
+            HoleResult::Lost => "Hole lost",
+            HoleResult::Halved => "Hole halved",
+            HoleResult::Conceded => "Hole conceded",
+            HoleResult::RivalConceded => "Rival concedes the hole",
+        }
+    }
+}
+
+// A match against the shadow rival. The sides take turns to play each hole
+// first: the rival posts its score before the player tees off on odd holes
+// and answers the player's on even ones. Whoever can no longer halve a hole
+// gives it up there and then.
+#[derive(Clone)]
+pub struct MatchPlay {
+    pub holes: u32,
+    pub played: u32,
+    // Holes the player is up; negative when down.
+    pub standing: i32,
+    // The rival's score on the hole in play, once it has played it.
+    pub rival: Option<u32>,
+    pub last: Option<HoleResult>,
+}
+
+impl MatchPlay {
+    pub fn new(holes: u32) -> Self {
+        Self {
+            holes,
+            played: 0,
+            standing: 0,
+            rival: None,
+            last: None,
+        }
+    }
+
+    pub fn rival_first(&self) -> bool {
+        self.played.is_multiple_of(2)
+    }
+
+    pub fn to_play(&self) -> u32 {
+        self.holes.saturating_sub(self.played)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.to_play() == 0 || self.standing.unsigned_abs() > self.to_play()
+    }
+
+    // With this many strokes taken and the ball not in, the player can at
+    // best lose the hole by one.
+    pub fn beaten(&self, strokes: u32) -> bool {
+        self.rival.is_some_and(|rival| strokes >= rival)
+    }
+
+    pub fn record(&mut self, result: HoleResult) {
+        self.standing += match result {
+            HoleResult::Won | HoleResult::RivalConceded => 1,
+            HoleResult::Lost | HoleResult::Conceded => -1,
+            HoleResult::Halved => 0,
+        };
+        self.played += 1;
+        self.rival = None;
+        self.last = Some(result);
+    }
+
+    // "2 UP, 5 to play" while it's on, "Won 3 & 2" once it's decided.
+    pub fn status(&self) -> String {
+        let up = self.standing.unsigned_abs();
+        let to_play = self.to_play();
+        match (self.is_over(), self.standing) {
+            (true, 0) => "Match halved".to_string(),
+            (true, s) if to_play == 0 => {
+                format!("{} {} UP", if s > 0 { "Won" } else { "Lost" }, up)
+            }
+            (true, s) => format!(
+                "{} {} & {}",
+                if s > 0 { "Won" } else { "Lost" },
+                up,
+                to_play
+            ),
+            (false, 0) => format!("All square, {} to play", to_play),
+            (false, s) if s > 0 && up == to_play => format!("{} UP, dormie", up),
+            (false, s) => format!(
+                "{} {}, {} to play",
+                up,
+                if s > 0 { "UP" } else { "DOWN" },
+                to_play
+            ),
+        }
+    }
+}
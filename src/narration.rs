@@ -0,0 +1,395 @@
+use crate::game::{Game, ShotShape, StatsPage, Vec2, CLUBS};
+use crate::match_play::HoleResult;
+use crate::warm_up::WARM_UP_BALLS;
+
+// Plain sentences for screen readers: no glyphs, no positioning, one line
+// per state so each change can be read out in order.
+pub fn describe(game: &Game) -> String {
+    if game.paused {
+        return "Paused while the terminal is in the background.".to_string();
+    }
+    if let Some(browser) = &game.hole_browser {
+        let choices: Vec<String> = browser
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let difficulty = c
+                    .difficulty
+                    .map(|d| format!(", plays {}", d.rating().to_lowercase()))
+                    .unwrap_or_default();
+                format!(
+                    "{}: par {}, {}, {}, {}{}.",
+                    i + 1,
+                    c.par,
+                    game.settings.units.spoken(c.layout.length_yd()),
+                    c.layout.template.name().to_lowercase(),
+                    c.conditions.name().to_lowercase(),
+                    difficulty
+                )
+            })
+            .collect();
+        return format!(
+            "Choose the next hole. {} Hole {} selected. Left and right to browse, Enter or a number to play, N for new choices, Esc to cancel.",
+            choices.join(" "),
+            browser.selected + 1
+        );
+    }
+    if game.stats_page == StatsPage::Gallery {
+        return match game.gallery.current() {
+            Some(shot) => format!(
+                "Gallery, snapshot {} of {}. {}: {}. Par {}, {}. Left and right to browse, G to close.",
+                game.gallery.selected + 1,
+                game.gallery.shots.len(),
+                shot.moment.name(),
+                shot.caption,
+                shot.par,
+                game.settings.units.spoken(shot.yards)
+            ),
+            None => "Gallery is empty. Hole-outs, near aces and disasters are saved here. G to close."
+                .to_string(),
+        };
+    }
+    if game.show_help {
+        return "Help: get the ball in the cup in as few strokes as you can. A and D aim, W and S change club, E changes the swing, Shift E shapes it, Space hits. C lets the caddie pick the club and F takes the caddie's line. Press H to close help.".to_string();
+    }
+    if game.replay.is_some() {
+        return "Slow-motion replay of the holed shot. Press any key to skip.".to_string();
+    }
+    if let Some(recap) = &game.between_holes {
+        return format!(
+            "Hole {} done: {}, {} strokes with {} {}. Next is hole {}, par {}, {}. Press any key for the next tee.",
+            recap.number,
+            recap.verdict().to_lowercase(),
+            recap.strokes,
+            recap.putts,
+            if recap.putts == 1 { "putt" } else { "putts" },
+            recap.next_number,
+            recap.next_layout.par,
+            game.settings.units.spoken(recap.next_layout.length_yd())
+        );
+    }
+    if let Some(card) = &game.round_card {
+        let (strokes, par) = card.total();
+        let titles: Vec<&str> = game.round_awards.iter().map(|award| award.title).collect();
+        let earned = match titles.as_slice() {
+            [] => String::new(),
+            titles => format!(" Titles earned: {}.", titles.join(", ")),
+        };
+        return format!(
+            "Final card for {}: {} strokes over {} holes, {}.{} Press any key to start a new round.",
+            card.course,
+            strokes,
+            card.order.len(),
+            to_par_phrase(strokes as i32 - par as i32).to_lowercase(),
+            earned
+        );
+    }
+    if let Some(tournament) = game.tournament.as_ref().filter(|_| game.show_leaderboard) {
+        let leaders: Vec<String> = tournament
+            .leaderboard()
+            .into_iter()
+            .take(3)
+            .map(|(place, e)| {
+                format!(
+                    "{} {} at {}",
+                    place,
+                    e.name,
+                    to_par_phrase(e.total()).to_lowercase()
+                )
+            })
+            .collect();
+        let you = if tournament.player().missed_cut {
+            "You missed the cut".to_string()
+        } else {
+            format!(
+                "You are {} at {}",
+                tournament.player_place(),
+                to_par_phrase(tournament.player().total()).to_lowercase()
+            )
+        };
+        return format!(
+            "Leaderboard after round {} of {}: {}. {}. Press any key to go on.",
+            tournament.rounds_played(),
+            tournament.course,
+            leaders.join(", "),
+            you
+        );
+    }
+    if game.hole_done {
+        let jarred = if game.jarred {
+            "Jarred it straight from the air! "
+        } else {
+            ""
+        };
+        let round_total = game
+            .course_round
+            .as_ref()
+            .map(|round| {
+                let (strokes, par) = round.card.total();
+                format!(
+                    " Round total {}, {} through {}.",
+                    strokes,
+                    to_par_phrase(strokes as i32 - par as i32).to_lowercase(),
+                    round.card.holes_played()
+                )
+            })
+            .unwrap_or_default();
+        let result = game.match_play.as_ref().and_then(|m| m.last);
+        let match_status = game
+            .match_play
+            .as_ref()
+            .zip(result)
+            .map(|(m, result)| format!(" {}. Match: {}.", result.describe(), m.status()))
+            .unwrap_or_default();
+        let holed = if result == Some(HoleResult::Conceded) {
+            format!(
+                "Picked up after {} strokes on a par {}.",
+                game.strokes, game.par
+            )
+        } else {
+            format!(
+                "{}Holed out in {} strokes on a par {}. {}.",
+                jarred,
+                game.strokes,
+                game.par,
+                score_phrase(game)
+            )
+        };
+        let next = match &game.course_round {
+            Some(round) if round.is_last_hole() => "Press N for the final card.",
+            Some(_) => "The next tee is coming up; press N to go now.",
+            None if game.match_play.is_some() => "Press N for the next hole.",
+            None => "Press R to replay or N for a new hole.",
+        };
+        return format!("{}{}{} {}", holed, match_status, round_total, next);
+    }
+    if game.airborne.is_some() {
+        return match game.strike_quality {
+            Some(strike) => format!("{} strike. Ball in the air.", strike.name()),
+            None => "Ball in the air.".to_string(),
+        };
+    }
+    if game.rolling {
+        return format!("Ball rolling on the {}.", game.current_surface().name());
+    }
+    if let Some(presentation) = &game.presentation {
+        return format!("{}.", presentation.summary);
+    }
+    if game.pressure.is_some() {
+        return "Pressure putt. Press space again to stop the steadiness meter in the middle."
+            .to_string();
+    }
+
+    let club = game.current_club();
+    let swing = if club.putter {
+        String::new()
+    } else {
+        match game.selected_shape {
+            ShotShape::Straight => format!(", {} swing", game.selected_shot.name()),
+            shape => format!(
+                ", {} swing, {}",
+                game.selected_shot.name(),
+                shape.name().to_lowercase()
+            ),
+        }
+    };
+
+    let target = game
+        .target_distance_yd()
+        .map(|yd| {
+            format!(
+                "Target mode, target {} away; arrows move it, X exits. ",
+                game.settings.units.spoken(yd)
+            )
+        })
+        .unwrap_or_default();
+    let conditions = if game.strokes == 0 {
+        let contour = game
+            .layout
+            .contour
+            .describe()
+            .map(|c| format!(" The green has a {}.", c))
+            .unwrap_or_default();
+        let bag = match game.bag.rule_label() {
+            Some(rule) if game.bag.is_full() => format!(" Challenge: {}.", rule),
+            Some(rule) => format!(" This hole you may only use {}.", rule),
+            None => String::new(),
+        };
+        let difficulty = game
+            .difficulty
+            .map(|d| format!(" This hole plays {}.", d.rating().to_lowercase()))
+            .unwrap_or_default();
+        let course_hole = match (&game.warm_up, &game.course_round) {
+            (Some(warm_up), _) => format!(
+                "Warm-up range, ball {} of {}. Press N to go to the first tee. ",
+                (warm_up.balls_hit + 1).min(WARM_UP_BALLS),
+                WARM_UP_BALLS
+            ),
+            (None, Some(round)) => {
+                let form = game
+                    .day_form
+                    .filter(|_| round.position == 0)
+                    .map(|form| format!(" {}.", form.verdict()))
+                    .unwrap_or_default();
+                let note = game
+                    .hole_note()
+                    .map(|note| format!(" Your note: {}.", note))
+                    .unwrap_or_default();
+                format!(
+                    "Hole {} of {}, {}.{}{} ",
+                    round.position + 1,
+                    round.order.len(),
+                    round.current().name,
+                    form,
+                    note
+                )
+            }
+            (None, None) => String::new(),
+        };
+        format!(
+            "{}{} conditions.{}{}{} ",
+            course_hole,
+            game.conditions.name(),
+            contour,
+            bag,
+            difficulty
+        )
+    } else {
+        String::new()
+    };
+
+    let caddie = match game.caddie_aim_offset_deg().map(|deg| deg.round() as i32) {
+        Some(deg) if deg > 0 => format!(" Caddie suggests {} degrees right, press F.", deg),
+        Some(deg) if deg < 0 => format!(" Caddie suggests {} degrees left, press F.", -deg),
+        _ => String::new(),
+    };
+
+    let odds = game
+        .caddie_advice
+        .map(|advice| {
+            let target = if advice.going_for_green {
+                "the green"
+            } else {
+                "short grass"
+            };
+            let mut phrase = format!(
+                " {} gives {:.0} percent to finish on {}",
+                CLUBS[advice.primary.club_idx].name,
+                advice.primary.chance * 100.0,
+                target
+            );
+            if let Some(alt) = advice.alternative {
+                phrase.push_str(&format!(
+                    ", {} {:.0} percent",
+                    CLUBS[alt.club_idx].name,
+                    alt.chance * 100.0
+                ));
+            }
+            phrase.push('.');
+            phrase
+        })
+        .unwrap_or_default();
+
+    let hazard = game
+        .hazard_warning
+        .map(|w| {
+            format!(
+                " Warning, {:.0} percent of landings find the {}.",
+                w.share * 100.0,
+                w.hazard
+            )
+        })
+        .unwrap_or_default();
+    let fringe = game
+        .fringe
+        .map(|f| {
+            format!(
+                " From here a putt holes {:.0} percent and leaves {:.0} feet; a {} {} holes {:.0} percent and leaves {:.0} feet. Press O to switch.",
+                f.putt.make_rate * 100.0,
+                f.putt.leave_ft,
+                CLUBS[f.chip.club_idx].name,
+                f.chip.shot.name(),
+                f.chip.make_rate * 100.0,
+                f.chip.leave_ft
+            )
+        })
+        .unwrap_or_default();
+
+    let idle = match game.tutorial {
+        Some(step) => format!(
+            " {}.",
+            step.hint(game.on_green(), game.settings.pull_back_putting)
+        ),
+        None if game.idle_hint_due() => " Press H for help.".to_string(),
+        None => String::new(),
+    };
+
+    format!(
+        "{}{}You are {} from the pin, {} lie, wind {}. {}{}, plays {}. Aim {}.{}{}{}{} Stroke {}, par {}.{}",
+        target,
+        conditions,
+        game.settings.units.spoken(game.distance_to_hole_yd()),
+        game.current_surface().name().to_lowercase(),
+        wind_phrase(game),
+        club.name,
+        swing,
+        game.settings.units.spoken(game.selected_shot_distance_yd()),
+        aim_phrase(game.aim_error_rad().to_degrees()),
+        hazard,
+        caddie,
+        odds,
+        fringe,
+        game.strokes + 1,
+        game.par,
+        idle
+    )
+}
+
+// Told against the line to the pin: across it, into or behind the player,
+// or both when it quarters.
+fn wind_phrase(game: &Game) -> String {
+    let mph = game.wind.mph();
+    if mph < 0.5 {
+        return "calm".to_string();
+    }
+    let wind = game.wind.vector().normalized();
+    let line = Vec2::new(game.hole.x - game.ball.x, game.hole.y - game.ball.y).normalized();
+    let along = wind.x * line.x + wind.y * line.y;
+    let across = wind.y * line.x - wind.x * line.y;
+    let mut parts = Vec::new();
+    if across > 0.3 {
+        parts.push("left to right");
+    } else if across < -0.3 {
+        parts.push("right to left");
+    }
+    if along > 0.3 {
+        parts.push("helping");
+    } else if along < -0.3 {
+        parts.push("into you");
+    }
+    format!("{:.0} mph {}", mph, parts.join(" and "))
+}
+
+fn aim_phrase(error_deg: f32) -> String {
+    let rounded = error_deg.round() as i32;
+    if rounded == 0 {
+        "straight at the pin".to_string()
+    } else if rounded > 0 {
+        format!("{} degrees right of the pin", rounded)
+    } else {
+        format!("{} degrees left of the pin", -rounded)
+    }
+}
+
+fn score_phrase(game: &Game) -> String {
+    to_par_phrase(game.strokes as i32 - game.par as i32)
+}
+
+fn to_par_phrase(score: i32) -> String {
+    match score {
+        0 => "Even par".to_string(),
+        s if s < 0 => format!("{} under par", -s),
+        s => format!("{} over par", s),
+    }
+}
@@ -0,0 +1,133 @@
+use std::io::{stdin, stdout, IsTerminal, Write};
+
+use crossterm::execute;
+use crossterm::style::{Print, ResetColor, SetBackgroundColor};
+
+use crate::config::Config;
+use crate::game::{terrain_color, Surface};
+use crate::profile::{self, Profile};
+use crate::settings::Units;
+
+// The first launch in a terminal, before anyone has a profile, or any launch
+// with `--setup`.
+pub fn due() -> bool {
+    stdin().is_terminal()
+        && (profile::names().is_empty() || std::env::args().any(|arg| arg == "--setup"))
+}
+
+// A few questions before the game takes over the terminal: who's playing and
+// how they'd like it set up. The answers are saved as the player's profile
+// and the config file, and the name comes back to play under. A name given
+// with `--profile` isn't asked for again.
+pub fn run(name: Option<String>) -> std::io::Result<String> {
+    let mut config = Config::load();
+    println!("A few questions to set the game up. Enter takes the answer in brackets.");
+    println!();
+    let name = match name {
+        Some(name) => name,
+        None => {
+            let last = profile::names().into_iter().next();
+            ask_name(last.as_deref().unwrap_or(profile::DEFAULT_NAME))?
+        }
+    };
+
+    let hand = ask(
+        "Do you play right- or left-handed? (r/l)",
+        &['r', 'l'],
+        if config.left_handed { 'l' } else { 'r' },
+    )?;
+    config.left_handed = hand == 'l';
+
+    let units = ask(
+        "Distances in yards or meters? (y/m)",
+        &['y', 'm'],
+        if config.units == Units::Meters {
+            'm'
+        } else {
+            'y'
+        },
+    )?;
+    config.units = if units == 'm' {
+        Units::Meters
+    } else {
+        Units::Yards
+    };
+
+    println!();
+    let mut out = stdout();
+    for surface in [Surface::Rough, Surface::Fairway, Surface::Green] {
+        execute!(
+            out,
+            Print("  "),
+            SetBackgroundColor(terrain_color(surface)),
+            Print("        "),
+            ResetColor
+        )?;
+    }
+    println!();
+    let seen = ask("Can you see three distinct greens? (y/n)", &['y', 'n'], 'y')?;
+    config.high_contrast = seen == 'n';
+    if config.high_contrast {
+        println!("High-contrast colors are on. V switches them during play.");
+    }
+
+    println!();
+    println!("Putts are struck with Space: tap it, or hold it to draw the putter back");
+    println!("and let go to strike.");
+    let putting = ask(
+        "Tap or hold? (t/h)",
+        &['t', 'h'],
+        if config.pull_back_putting { 'h' } else { 't' },
+    )?;
+    config.pull_back_putting = putting == 'h';
+
+    let tutorial = ask(
+        "Show the controls a step at a time on your first hole? (y/n)",
+        &['y', 'n'],
+        'y',
+    )?;
+    config.tutorial = tutorial == 'y';
+
+    Profile::load(&name).save()?;
+    config.save()?;
+    println!();
+    println!("All set, {}. Run with --setup to answer these again.", name);
+    Ok(name)
+}
+
+fn ask_name(default: &str) -> std::io::Result<String> {
+    loop {
+        let Some(answer) = read_answer(&format!("What's your name? [{}]", default))? else {
+            return Ok(default.to_string());
+        };
+        match profile::check_name(&answer) {
+            Ok(name) => return Ok(name),
+            Err(err) => println!("Sorry, {}.", err),
+        }
+    }
+}
+
+// Asks until the answer starts with one of `choices`; a blank answer takes
+// `default`.
+fn ask(question: &str, choices: &[char], default: char) -> std::io::Result<char> {
+    loop {
+        let Some(answer) = read_answer(&format!("{} [{}]", question, default))? else {
+            return Ok(default);
+        };
+        match answer.to_lowercase().chars().next() {
+            Some(choice) if choices.contains(&choice) => return Ok(choice),
+            _ => {}
+        }
+    }
+}
+
+// The trimmed line typed after `prompt`, or nothing for a blank line or the
+// end of input.
+fn read_answer(prompt: &str) -> std::io::Result<Option<String>> {
+    print!("{} ", prompt);
+    stdout().flush()?;
+    let mut line = String::new();
+    stdin().read_line(&mut line)?;
+    let answer = line.trim();
+    Ok((!answer.is_empty()).then(|| answer.to_string()))
+}
@@ -0,0 +1,70 @@
+pub const PRESSURE_PUTT_MAX_YD: f32 = 10.0;
+pub const SWEET_SPOT: f32 = 0.12;
+pub const MAX_MISS_RAD: f32 = 0.1;
+
+#[derive(Clone, Copy)]
+pub struct PressureMeter {
+    pub elapsed: f32,
+    pub period: f32,
+}
+
+impl PressureMeter {
+    pub fn new(period: f32) -> Self {
+        Self {
+            elapsed: 0.0,
+            period,
+        }
+    }
+
+    pub fn update(&mut self, dt_secs: f32) {
+        self.elapsed += dt_secs;
+    }
+
+    // Triangle wave sweeping 0 -> 1 -> 0 once per period.
+    pub fn position(&self) -> f32 {
+        let t = (self.elapsed / self.period.max(0.05)).fract();
+        if t < 0.5 {
+            t * 2.0
+        } else {
+            2.0 - t * 2.0
+        }
+    }
+
+    pub fn error(&self) -> f32 {
+        self.position() - 0.5
+    }
+
+    // Positive offsets open the face (push), negative close it (pull).
+    pub fn miss_offset_rad(&self) -> f32 {
+        let error = self.error();
+        let beyond = (error.abs() - SWEET_SPOT).max(0.0) / (0.5 - SWEET_SPOT);
+        beyond * MAX_MISS_RAD * error.signum()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PressureResult {
+    Steady,
+    Pushed,
+    Pulled,
+}
+
+impl PressureResult {
+    pub fn from_offset(offset: f32) -> Self {
+        if offset > 0.0 {
+            PressureResult::Pushed
+        } else if offset < 0.0 {
+            PressureResult::Pulled
+        } else {
+            PressureResult::Steady
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PressureResult::Steady => "steady stroke",
+            PressureResult::Pushed => "pushed it",
+            PressureResult::Pulled => "pulled it",
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::awards::{self, Award};
+use crate::shop::{self, Cosmetic, Slot};
+use crate::storage::{self, Schema};
+
+// The first player's profile keeps the file it had before there were names;
+// everyone else's is in `profiles/`, by name.
+const PROFILE_FILE: &str = "profile.txt";
+const SCHEMA: Schema = Schema {
+    name: "profile",
+    migrations: &[storage::unversioned],
+};
+pub const PROFILES_DIR: &str = "profiles";
+pub const DEFAULT_NAME: &str = "Player";
+const NAME_MAX_CHARS: usize = 20;
+// Only the most recent rounds are kept.
+const HISTORY_ROUNDS: usize = 20;
+
+// Lifetime totals for one named player. Play time is wall-clock time with
+// the game open, accumulated by the main loop; the session total is never
+// saved.
+#[derive(Clone, Default)]
+pub struct Profile {
+    pub name: String,
+    pub play_secs: f64,
+    pub session_secs: f64,
+    pub rounds: u32,
+    pub holes: u32,
+    pub aces: u32,
+    pub total_strokes: u32,
+    // The fewest strokes taken on each course hole, and on each par of
+    // generated hole, by `Game::hole_key`.
+    pub best_holes: BTreeMap<String, u32>,
+    // Everything ever earned, and what's left of it after the shop.
+    pub career_earnings: u32,
+    pub balance: u32,
+    // Oldest first.
+    pub history: Vec<RoundRecord>,
+    unlocked: Vec<&'static str>,
+    equipped: Vec<&'static str>,
+    path: Option<PathBuf>,
+}
+
+#[derive(Clone)]
+pub struct RoundRecord {
+    pub holes: u32,
+    pub strokes: u32,
+    pub to_par: i32,
+    pub titles: Vec<&'static Award>,
+}
+
+impl Profile {
+    pub fn load(name: &str) -> Self {
+        let path = storage::data_dir().map(|dir| {
+            if name == DEFAULT_NAME {
+                dir.join(PROFILE_FILE)
+            } else {
+                dir.join(PROFILES_DIR).join(format!("{}.txt", name))
+            }
+        });
+        let mut profile = Self {
+            name: name.to_string(),
+            path: path.clone(),
+            ..Self::default()
+        };
+        if let Some(text) = path.and_then(|p| storage::read(&p, &SCHEMA).ok().flatten()) {
+            profile.parse(&text);
+        }
+        profile
+    }
+
+    fn parse(&mut self, text: &str) {
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["play_secs", secs] => self.play_secs = secs.parse().unwrap_or(0.0),
+                ["rounds", n] => self.rounds = n.parse().unwrap_or(0),
+                ["holes", n] => self.holes = n.parse().unwrap_or(0),
+                ["aces", n] => self.aces = n.parse().unwrap_or(0),
+                ["strokes", n] => self.total_strokes = n.parse().unwrap_or(0),
+                ["career_earnings", n] => self.career_earnings = n.parse().unwrap_or(0),
+                ["balance", n] => self.balance = n.parse().unwrap_or(0),
+                ["best", strokes, hole @ ..] => {
+                    if let Ok(strokes) = strokes.parse() {
+                        self.best_holes.insert(hole.join(" "), strokes);
+                    }
+                }
+                ["round", holes, strokes, to_par, titles @ ..] => {
+                    self.history.push(RoundRecord {
+                        holes: holes.parse().unwrap_or(0),
+                        strokes: strokes.parse().unwrap_or(0),
+                        to_par: to_par.parse().unwrap_or(0),
+                        titles: titles.iter().filter_map(|key| awards::find(key)).collect(),
+                    });
+                }
+                ["unlocked", key] => {
+                    if let Some(item) = shop::find(key) {
+                        self.unlocked.push(item.key);
+                    }
+                }
+                ["equipped", key] => {
+                    if let Some(item) = shop::find(key) {
+                        self.equip(item);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut text = format!(
+            "play_secs {:.0}\nrounds {}\nholes {}\naces {}\nstrokes {}\ncareer_earnings {}\nbalance {}\n",
+            self.play_secs,
+            self.rounds,
+            self.holes,
+            self.aces,
+            self.total_strokes,
+            self.career_earnings,
+            self.balance
+        );
+        for (hole, strokes) in &self.best_holes {
+            text.push_str(&format!("best {} {}\n", strokes, hole));
+        }
+        for round in &self.history {
+            text.push_str(&format!(
+                "round {} {} {}",
+                round.holes, round.strokes, round.to_par
+            ));
+            for award in &round.titles {
+                text.push_str(&format!(" {}", award.key));
+            }
+            text.push('\n');
+        }
+        for key in &self.unlocked {
+            text.push_str(&format!("unlocked {}\n", key));
+        }
+        for key in &self.equipped {
+            text.push_str(&format!("equipped {}\n", key));
+        }
+        storage::write(path, &SCHEMA, &text)
+    }
+
+    pub fn add_play_time(&mut self, secs: f64) {
+        self.play_secs += secs;
+        self.session_secs += secs;
+    }
+
+    pub fn record_hole(&mut self, strokes: u32, hole: Option<String>) {
+        self.holes += 1;
+        self.total_strokes += strokes;
+        if strokes == 1 {
+            self.aces += 1;
+        }
+        if let Some(hole) = hole {
+            let best = self.best_holes.entry(hole).or_insert(strokes);
+            *best = (*best).min(strokes);
+        }
+    }
+
+    pub fn record_round(&mut self, round: RoundRecord) {
+        self.rounds += 1;
+        self.history.push(round);
+        let excess = self.history.len().saturating_sub(HISTORY_ROUNDS);
+        self.history.drain(..excess);
+    }
+
+    pub fn earn(&mut self, amount: u32) {
+        self.career_earnings += amount;
+        self.balance += amount;
+    }
+
+    pub fn owns(&self, item: &Cosmetic) -> bool {
+        item.price == 0 || self.unlocked.contains(&item.key)
+    }
+
+    pub fn equipped(&self, slot: Slot) -> &'static Cosmetic {
+        self.equipped
+            .iter()
+            .filter_map(|key| shop::find(key))
+            .find(|item| item.slot() == slot)
+            .unwrap_or_else(|| shop::default_for(slot))
+    }
+
+    fn equip(&mut self, item: &'static Cosmetic) {
+        self.equipped
+            .retain(|key| shop::find(key).is_some_and(|worn| worn.slot() != item.slot()));
+        self.equipped.push(item.key);
+    }
+
+    // Equips an item already owned, or buys it first if the balance covers
+    // it. False when it can't be afforded.
+    pub fn buy_or_equip(&mut self, item: &'static Cosmetic) -> bool {
+        if !self.owns(item) {
+            if self.balance < item.price {
+                return false;
+            }
+            self.balance -= item.price;
+            self.unlocked.push(item.key);
+        }
+        self.equip(item);
+        true
+    }
+
+    pub fn average_strokes(&self) -> Option<f32> {
+        (self.holes > 0).then(|| self.total_strokes as f32 / self.holes as f32)
+    }
+}
+
+// Everyone with a profile, the last to play first.
+pub fn names() -> Vec<String> {
+    let Some(dir) = storage::data_dir() else {
+        return Vec::new();
+    };
+    let mut found: Vec<(std::time::SystemTime, String)> = fs::read_dir(dir.join(PROFILES_DIR))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_stem()?.to_str()?.to_string();
+            (path.extension()? == "txt").then_some((path, name))
+        })
+        .chain(Some((dir.join(PROFILE_FILE), DEFAULT_NAME.to_string())))
+        .filter_map(|(path, name)| Some((fs::metadata(path).ok()?.modified().ok()?, name)))
+        .collect();
+    found.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    found.into_iter().map(|(_, name)| name).collect()
+}
+
+// A name has to make a file name as it is: a letter first, then letters,
+// digits, spaces, dashes and underscores.
+pub fn check_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    let fits = name.chars().next().is_some_and(char::is_alphabetic)
+        && name.chars().count() <= NAME_MAX_CHARS
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'));
+    if fits {
+        Ok(name.to_string())
+    } else {
+        Err(format!(
+            "a profile name is up to {} letters, digits, spaces, dashes and underscores, starting with a letter; got `{}`",
+            NAME_MAX_CHARS, name
+        ))
+    }
+}
+
+// "3h 05m" once past the hour, "12m 40s" below it.
+pub fn format_duration(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m {:02}s", minutes, seconds)
+    }
+}
@@ -0,0 +1,217 @@
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::camera::Camera;
+use crate::game::{Game, Vec2, CELL_ASPECT, HEIGHT, WIDTH};
+use crate::kitty::KittyView;
+use crate::sixel::SixelView;
+
+pub type Rgb = (u8, u8, u8);
+
+const OFF_MAP: Rgb = (12, 12, 12);
+const CUP: Rgb = (20, 20, 20);
+const POLE: Rgb = (235, 235, 235);
+const FLAG: Rgb = (220, 40, 40);
+
+// Pixels in rows from the top left; `None` is see-through.
+pub struct Canvas {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Option<Rgb>>,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize, fill: Option<Rgb>) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    pub fn put(&mut self, x: i32, y: i32, color: Rgb) {
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            self.pixels[y as usize * self.width + x as usize] = Some(color);
+        }
+    }
+
+    // A filled ellipse, `ry` tall and `rx` wide, centred in pixels.
+    pub fn disc(&mut self, (cx, cy): (f32, f32), (rx, ry): (f32, f32), color: Rgb) {
+        for y in (cy - ry).floor() as i32..=(cy + ry).ceil() as i32 {
+            for x in (cx - rx).floor() as i32..=(cx + rx).ceil() as i32 {
+                let dx = (x as f32 + 0.5 - cx) / rx;
+                let dy = (y as f32 + 0.5 - cy) / ry;
+                if dx * dx + dy * dy <= 1.0 {
+                    self.put(x, y, color);
+                }
+            }
+        }
+    }
+
+    // Four bytes a pixel, see-through pixels with no alpha.
+    pub fn rgba(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| match pixel {
+                Some((r, g, b)) => [*r, *g, *b, 255],
+                None => [0; 4],
+            })
+            .collect()
+    }
+}
+
+// What a course image was drawn from; the backends only draw a new one
+// when this changes.
+#[derive(Clone, Copy, PartialEq)]
+pub struct CourseKey {
+    terrain: usize,
+    cup: (u32, u32),
+    camera: (u32, u32, u32),
+    look: (bool, bool, bool),
+}
+
+impl CourseKey {
+    pub fn of(game: &Game, camera: Camera) -> Self {
+        Self {
+            terrain: Rc::as_ptr(&game.terrain) as usize,
+            cup: (game.hole.x.to_bits(), game.hole.y.to_bits()),
+            camera: (
+                camera.left.to_bits(),
+                camera.top.to_bits(),
+                camera.zoom.to_bits(),
+            ),
+            look: (
+                game.settings.high_contrast,
+                game.settings.slope_shading,
+                game.settings.heat_map && game.heat_map.is_some(),
+            ),
+        }
+    }
+}
+
+// Where a world point falls on a course canvas seen through `camera`, with
+// a cell's height spanning `CELL_ASPECT` tiles.
+fn to_pixels(camera: Camera, p: Vec2, (cw, ch): (usize, usize)) -> (f32, f32) {
+    (
+        (p.x - camera.left) * camera.zoom * cw as f32,
+        (p.y - camera.top) * camera.zoom * ch as f32 / CELL_ASPECT as f32,
+    )
+}
+
+// The map area as `camera` sees it at `cell` pixels a map cell, each pixel
+// the color `tile` gives the world tile under it, with the cup and flag on
+// top. Anything off the hole is left dark.
+pub fn course(
+    camera: Camera,
+    (width, height): (i32, i32),
+    tile: impl Fn(i32, i32) -> Rgb,
+    cup: Vec2,
+    cell: (usize, usize),
+) -> Canvas {
+    let (cw, ch) = cell;
+    let mut canvas = Canvas::new(WIDTH as usize * cw, HEIGHT as usize * ch, Some(OFF_MAP));
+    for py in 0..canvas.height {
+        let rows = py as f32 / ch as f32 * CELL_ASPECT as f32;
+        let wy = (camera.top + rows / camera.zoom).floor() as i32;
+        for px in 0..canvas.width {
+            let wx = (camera.left + px as f32 / cw as f32 / camera.zoom).floor() as i32;
+            if wx >= 0 && wy >= 0 && wx < width && wy < height {
+                canvas.pixels[py * canvas.width + px] = Some(tile(wx, wy));
+            }
+        }
+    }
+
+    let (cx, cy) = to_pixels(camera, Vec2::new(cup.x + 0.5, cup.y + 0.5), cell);
+    let scale = camera.zoom.max(1.0);
+    canvas.disc((cx, cy), (1.5 * scale, 1.5 * scale), CUP);
+    let pole = (ch as f32 * 2.0 * scale) as i32;
+    for y in 1..=pole {
+        canvas.put(cx as i32, cy as i32 - y, POLE);
+    }
+    let flag = (ch as f32 * scale) as i32;
+    for y in 0..flag {
+        let reach = (flag - y).min(y + 1);
+        for x in 1..=reach {
+            canvas.put(cx as i32 + x, cy as i32 - pole + y, FLAG);
+        }
+    }
+    canvas
+}
+
+// A ball filling a cell `w` by `h` pixels, lit from the top left, on a
+// clear background.
+pub fn ball_sprite(color: Rgb, (w, h): (usize, usize)) -> Canvas {
+    let mut canvas = Canvas::new(w, h, None);
+    let centre = (w as f32 / 2.0, h as f32 / 2.0);
+    let radius = w.min(h) as f32 * 0.42;
+    let shade = |c: u8, f: f32| (c as f32 * f).min(255.0) as u8;
+    canvas.disc(
+        centre,
+        (radius, radius),
+        (
+            shade(color.0, 0.7),
+            shade(color.1, 0.7),
+            shade(color.2, 0.7),
+        ),
+    );
+    canvas.disc(
+        (centre.0 - radius * 0.15, centre.1 - radius * 0.15),
+        (radius * 0.8, radius * 0.8),
+        color,
+    );
+    canvas.disc(
+        (centre.0 - radius * 0.4, centre.1 - radius * 0.4),
+        (radius * 0.25, radius * 0.25),
+        (255, 255, 255),
+    );
+    canvas
+}
+
+// The image backends the map can be drawn with in place of characters.
+pub enum Graphics {
+    Kitty(KittyView),
+    Sixel(SixelView),
+}
+
+impl Graphics {
+    pub fn draw_course(
+        &mut self,
+        out: &mut impl Write,
+        game: &Game,
+        camera: Camera,
+        tile: impl Fn(i32, i32) -> Rgb,
+        origin: (u16, u16),
+    ) -> std::io::Result<()> {
+        match self {
+            Graphics::Kitty(view) => view.draw_course(out, game, camera, tile, origin),
+            Graphics::Sixel(view) => view.draw_course(out, game, camera, tile, origin),
+        }
+    }
+
+    pub fn draw_ball(
+        &mut self,
+        out: &mut impl Write,
+        color: Rgb,
+        cell: (u16, u16),
+    ) -> std::io::Result<()> {
+        match self {
+            Graphics::Kitty(view) => view.draw_ball(out, color, cell),
+            Graphics::Sixel(view) => view.draw_ball(out, color, cell),
+        }
+    }
+
+    pub fn resize(&mut self) {
+        if let Graphics::Sixel(view) = self {
+            view.resize();
+        }
+    }
+
+    // Kitty keeps images after the game has gone; sixels are only ever on
+    // the screen.
+    pub fn forget(&mut self, out: &mut impl Write) -> std::io::Result<()> {
+        match self {
+            Graphics::Kitty(view) => view.forget(out),
+            Graphics::Sixel(_) => Ok(()),
+        }
+    }
+}
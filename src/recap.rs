@@ -0,0 +1,80 @@
+use crate::game::{Game, Surface, Vec2};
+use crate::hole::HoleLayout;
+
+// The page between two holes of a course round: how the hole just finished
+// went, and a look down the next one. It stays up until a key is pressed.
+pub struct HoleRecap {
+    pub number: u32,
+    pub name: String,
+    pub layout: HoleLayout,
+    pub strokes: u32,
+    pub putts: u32,
+    pub penalties: u32,
+    pub longest_yd: f32,
+    // Where each shot was played from, then the cup.
+    pub path: Vec<Vec2>,
+    pub next_number: u32,
+    pub next_name: String,
+    pub next_layout: HoleLayout,
+}
+
+impl HoleRecap {
+    pub fn of(game: &Game) -> Option<Self> {
+        let round = game.course_round.as_ref()?;
+        let hole = round.current();
+        let next = round.next()?;
+        let path: Vec<Vec2> = game
+            .shot_history
+            .iter()
+            .map(|shot| shot.from)
+            .chain(std::iter::once(game.ball))
+            .collect();
+        let longest_yd = game
+            .shot_history
+            .iter()
+            .zip(path.iter().skip(1))
+            .filter(|(shot, _)| !shot.putter)
+            .map(|(shot, to)| {
+                Vec2::new(to.x - shot.from.x, to.y - shot.from.y).length()
+                    * game.layout.yards_per_tile
+            })
+            .fold(0.0, f32::max);
+        Some(Self {
+            number: hole.number,
+            name: hole.name.clone(),
+            layout: game.layout.clone(),
+            strokes: game.strokes,
+            putts: game
+                .shot_history
+                .iter()
+                .filter(|shot| shot.putter && shot.lie == Surface::Green)
+                .count() as u32,
+            penalties: game.penalty_strokes,
+            longest_yd,
+            path,
+            next_number: next.number,
+            next_name: next.name.clone(),
+            next_layout: next.layout.clone(),
+        })
+    }
+
+    pub fn verdict(&self) -> &'static str {
+        score_name(self.strokes, self.layout.par)
+    }
+}
+
+pub fn score_name(strokes: u32, par: u32) -> &'static str {
+    if strokes == 1 {
+        return "Hole in one";
+    }
+    match strokes as i32 - par as i32 {
+        i32::MIN..=-3 => "Albatross",
+        -2 => "Eagle",
+        -1 => "Birdie",
+        0 => "Par",
+        1 => "Bogey",
+        2 => "Double bogey",
+        3 => "Triple bogey",
+        _ => "Quadruple bogey or worse",
+    }
+}
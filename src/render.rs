@@ -1,226 +1,2384 @@
 use std::f32::consts::PI;
-use std::io::{Stdout, Write};
+use std::io::Write;
 
 use crossterm::cursor::MoveTo;
 use crossterm::queue;
-use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
-use crossterm::terminal::{Clear, ClearType};
+use crossterm::style::{
+    Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use crossterm::terminal::{self, Clear, ClearType};
 
-use crate::game::{terrain_char, terrain_color, Game, HEIGHT, WIDTH};
+use crate::awards::Award;
+use crate::bag::Challenge;
+use crate::camera::Camera;
+use crate::chart;
+use crate::course::notes;
+use crate::course::StartOption;
+use crate::foursomes::PARTNERS;
+use crate::gallery::Cell;
+use crate::game::{
+    lie_name, terrain_char, terrain_color, BallView, Game, StatsPage, Surface, TrailPoint, Vec2,
+    AIM_PRESET_LIMIT, AUTO_ADVANCE_SECS, CELEBRATION_SECS, CELL_ASPECT, CLUBS, HEIGHT, WIDTH,
+};
+use crate::hole::{HoleLayout, HoleTemplate};
+use crate::match_log::{standing_text, MomentKind};
+use crate::pressure::SWEET_SPOT;
+use crate::profile::format_duration;
+use crate::raster::Graphics;
+use crate::raster::Rgb;
+use crate::screen::Progress;
+use crate::settings::{HudLayout, Theme};
+use crate::shop::{self, Look, Slot};
+use crate::speed_round::{SpeedResult, SpeedRound};
+use crate::stats::{ApproachMiss, APPROACH_BUCKETS, PUTT_BUCKETS};
+use crate::thumbnail::{thumbnail, Pixel};
+use crate::tournament::{CUT_AFTER, TOURNAMENT_ROUNDS};
+use crate::tutorial::TutorialStep;
+use crate::warm_up::WARM_UP_BALLS;
 
-pub fn draw(stdout: &mut Stdout, game: &Game) -> std::io::Result<()> {
+// What the side panel needs before it's worth drawing: room for its longest
+// lines beside the map, and enough rows to get down to the shot details.
+const SIDE_PANEL_MIN_COLS: u16 = WIDTH as u16 + 2 + 30;
+const SIDE_PANEL_MIN_ROWS: u16 = 41;
+// Rows above everything else, kept for the hole header.
+const HEADER_ROWS: u16 = 1;
+// Slope shading draws an arrow on green tiles tilted more than the first,
+// and is at its darkest or brightest from the second on.
+const SLOPE_READ_MIN: f32 = 0.04;
+const MAX_SHADE_SLOPE: f32 = 0.2;
+// Distance arcs to the pin in the full view, each swept this far either side
+// of the line back to the tee.
+const DISTANCE_ARCS_YD: [f32; 3] = [200.0, 150.0, 100.0];
+const ARC_HALF_ANGLE: f32 = 0.5;
+
+#[derive(Clone, Copy)]
+struct BallStyle {
+    ball: Color,
+    aim: Color,
+}
+
+const PLAYER_STYLE: BallStyle = BallStyle {
+    ball: Color::White,
+    aim: Color::Yellow,
+};
+
+const RIVAL_STYLE: BallStyle = BallStyle {
+    ball: Color::Magenta,
+    aim: Color::Red,
+};
+
+// The player's ball and aim line in the palette bought from the pro shop;
+// high contrast keeps the stock colors.
+fn player_style(game: &Game) -> BallStyle {
+    match game.profile.equipped(Slot::Palette).look {
+        Look::Palette { ball, aim } if !game.settings.high_contrast => BallStyle {
+            ball: rgb(ball),
+            aim: rgb(aim),
+        },
+        _ => PLAYER_STYLE,
+    }
+}
+
+fn ball_glyph(game: &Game) -> char {
+    match game.profile.equipped(Slot::Ball).look {
+        Look::Ball(glyph) => glyph,
+        _ => '●',
+    }
+}
+
+fn rgb((r, g, b): (u8, u8, u8)) -> Color {
+    Color::Rgb { r, g, b }
+}
+
+pub fn draw(
+    stdout: &mut impl Write,
+    game: &Game,
+    graphics: Option<&mut Graphics>,
+) -> std::io::Result<()> {
     queue!(stdout, MoveTo(0, 0), Clear(ClearType::All))?;
+    if game.paused {
+        queue!(stdout, SetAttribute(Attribute::Dim))?;
+    }
 
-    if game.on_green() {
-        draw_zoomed_course(stdout, game)?;
-    } else {
-        draw_full_course(stdout, game)?;
+    match game.stats_page {
+        _ if game.show_help => draw_help_screen(stdout)?,
+        _ if game.hole_browser.is_some() => draw_hole_browser(stdout, game)?,
+        _ if game.between_holes.is_some() => draw_between_holes(stdout, game)?,
+        _ if game.round_card.is_some() => draw_round_card(stdout, game)?,
+        _ if game.show_leaderboard => draw_leaderboard(stdout, game)?,
+        _ if game.replay.is_some() => draw_replay(stdout, game)?,
+        StatsPage::Closed => draw_course(stdout, game, view_camera(game), graphics)?,
+        StatsPage::HoleSide => draw_stats_screen(stdout, game)?,
+        StatsPage::Lifetime => draw_lifetime_screen(stdout, game)?,
+        StatsPage::Scorecard => draw_scorecard(stdout, game)?,
+        StatsPage::Gallery => draw_gallery(stdout, game)?,
+        StatsPage::ProShop => draw_pro_shop(stdout, game)?,
     }
 
-    draw_hud(stdout, game)?;
+    match hud_layout(game) {
+        HudLayout::Broadcast => draw_broadcast_bar(stdout, game)?,
+        _ => {
+            draw_hud(stdout, game)?;
+            draw_pace_footer(stdout, game)?;
+        }
+    }
+    draw_header(stdout, game)?;
+    if game.paused {
+        draw_pause_banner(stdout)?;
+    }
     queue!(stdout, ResetColor)?;
     stdout.flush()?;
     Ok(())
 }
 
-fn draw_full_course(stdout: &mut Stdout, game: &Game) -> std::io::Result<()> {
-    for y in 0..HEIGHT {
-        for x in 0..WIDTH {
-            draw_tile(stdout, x, y, x, y)?;
+// Everything but the header is drawn below it.
+fn at(x: u16, y: u16) -> MoveTo {
+    MoveTo(x, y + HEADER_ROWS)
+}
+
+// The hole in play, whichever screen is up: number, par, yardage, name and
+// the day's conditions.
+fn draw_header(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let mut parts: Vec<String> = Some(game.profile.name.clone())
+        .filter(|name| !name.is_empty())
+        .into_iter()
+        .collect();
+    parts.extend(match (&game.warm_up, &game.course_round) {
+        (Some(_), _) => Some("Driving range".to_string()),
+        (None, Some(round)) => Some(format!("Hole {}", round.current().number)),
+        (None, None) => None,
+    });
+    parts.push(format!("Par {}", game.par));
+    parts.push(game.settings.units.short(game.layout.length_yd()));
+    let name = match &game.course_round {
+        Some(round) if game.warm_up.is_none() => Some(round.current().name.clone()),
+        _ if game.layout.template != HoleTemplate::Standard => {
+            Some(game.layout.template.name().to_string())
         }
-    }
-    draw_entities(stdout, game, 0, 0, 1)?;
-    Ok(())
+        _ => None,
+    };
+    parts.extend(name);
+    parts.push(game.conditions.name().to_string());
+    let header = format!(" {} ", parts.join(" · "));
+    let width = terminal::size().map_or(WIDTH as u16, |(cols, _)| cols) as usize;
+    let header: String = header.chars().take(width).collect();
+    queue!(
+        stdout,
+        MoveTo(0, 0),
+        SetForegroundColor(hud_color(game)),
+        SetAttribute(Attribute::Reverse),
+        Print(format!("{:<width$}", header, width = WIDTH as usize)),
+        SetAttribute(Attribute::NoReverse)
+    )
 }
 
-fn draw_zoomed_course(stdout: &mut Stdout, game: &Game) -> std::io::Result<()> {
-    let zoom = 2_i32;
-    let view_w = WIDTH / zoom;
-    let view_h = HEIGHT / zoom;
+// Auto falls back to the broadcast line when the side panel would be cut
+// off: on terminals too narrow to show it beside the map, or too short to
+// reach the shot details.
+fn hud_layout(game: &Game) -> HudLayout {
+    match game.settings.hud {
+        HudLayout::Auto => match terminal::size() {
+            Ok((cols, rows)) if cols < SIDE_PANEL_MIN_COLS || rows < SIDE_PANEL_MIN_ROWS => {
+                HudLayout::Broadcast
+            }
+            _ => HudLayout::Full,
+        },
+        layout => layout,
+    }
+}
 
-    let center_x = ((game.ball.x + game.hole.x) * 0.5).round() as i32;
-    let center_y = ((game.ball.y + game.hole.y) * 0.5).round() as i32;
+// Mid-transition the camera eases from the previous view to the current one.
+fn view_camera(game: &Game) -> Camera {
+    let target = Camera::for_view(game, game.view_zoomed);
+    match game.camera_blend {
+        Some(t) => {
+            let from = Camera::for_view(game, !game.view_zoomed);
+            let eased = t * t * (3.0 - 2.0 * t);
+            Camera::lerp(from, target, eased)
+        }
+        None => target,
+    }
+}
 
-    let left = (center_x - view_w / 2).clamp(0, WIDTH - view_w);
-    let top = (center_y - view_h / 2).clamp(0, HEIGHT - view_h);
+pub fn draw_speed_round(stdout: &mut impl Write, round: &SpeedRound) -> std::io::Result<()> {
+    queue!(stdout, MoveTo(0, 0), Clear(ClearType::All))?;
 
-    for sy in 0..HEIGHT {
-        for sx in 0..WIDTH {
-            let wx = left + sx / zoom;
-            let wy = top + sy / zoom;
-            draw_tile(stdout, sx, sy, wx, wy)?;
+    let course = &round.players[0];
+    if let Some(viewer) = &round.viewer {
+        draw_replay(stdout, viewer)?;
+    } else if let Some(selected) = round.summary {
+        draw_match_summary(stdout, round, selected)?;
+    } else {
+        let camera = Camera::full(course);
+        draw_terrain(stdout, course, camera)?;
+        for (i, player) in round.players.iter().enumerate() {
+            let style = if i == 0 { PLAYER_STYLE } else { RIVAL_STYLE };
+            draw_entities(stdout, player, camera, style, None)?;
         }
     }
 
-    draw_entities(stdout, game, left, top, zoom)?;
+    draw_speed_round_hud(stdout, round)?;
+    draw_header(stdout, round.viewer.as_ref().unwrap_or(course))?;
+    queue!(stdout, ResetColor)?;
+    stdout.flush()?;
     Ok(())
 }
 
-fn draw_tile(stdout: &mut Stdout, sx: i32, sy: i32, wx: i32, wy: i32) -> std::io::Result<()> {
-    let tile = terrain_char(wx, wy);
-    let color = terrain_color(wx, wy);
+// The match so far: who is up, a sparkline of the standing after every
+// moment (higher is better for P1), and the moments themselves.
+fn draw_match_summary(
+    stdout: &mut impl Write,
+    round: &SpeedRound,
+    selected: usize,
+) -> std::io::Result<()> {
+    let log = &round.log;
+    let names = round.names();
     queue!(
         stdout,
-        MoveTo(sx as u16, sy as u16),
-        SetForegroundColor(color),
-        Print(tile)
+        at(1, 1),
+        SetForegroundColor(Color::Cyan),
+        Print(format!(
+            "MATCH SUMMARY - {} after {} {} (M to close)",
+            standing_text(log.standing, names),
+            log.holes,
+            if log.holes == 1 { "hole" } else { "holes" }
+        )),
+        at(1, 3),
+        Print("Momentum ")
+    )?;
+    let momentum = log.momentum();
+    let skip = momentum.len().saturating_sub(WIDTH as usize - 12);
+    for (i, level) in chart::sparkline(&momentum).chars().enumerate().skip(skip) {
+        let color = if i == selected + 1 {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        queue!(stdout, SetForegroundColor(color), Print(level))?;
+    }
+
+    let rows = HEIGHT as usize - 8;
+    let first = (selected + 1).saturating_sub(rows);
+    for (row, (i, moment)) in log
+        .moments
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(rows)
+        .enumerate()
+    {
+        let cursor = if i == selected { '>' } else { ' ' };
+        let color = match (i == selected, moment.kind) {
+            (true, _) => Color::Yellow,
+            (false, MomentKind::Penalty(_)) => Color::Red,
+            (false, MomentKind::HoleWon(_) | MomentKind::Halved) => Color::White,
+            (false, MomentKind::BigPutt { .. }) => Color::Green,
+        };
+        let replay = if moment.replay.is_some() {
+            "replay"
+        } else {
+            ""
+        };
+        queue!(
+            stdout,
+            at(1, row as u16 + 5),
+            SetForegroundColor(color),
+            Print(format!(
+                "{} Hole {:<2} {:<32} {:<16} {}",
+                cursor,
+                moment.hole,
+                moment.kind.describe(names),
+                standing_text(moment.standing, names),
+                replay
+            ))
+        )?;
+    }
+    queue!(
+        stdout,
+        at(1, (HEIGHT - 2) as u16),
+        SetForegroundColor(Color::DarkGrey),
+        Print("W/S or ^/v choose a moment, Space or Enter replays it")
+    )
+}
+
+fn draw_course(
+    stdout: &mut impl Write,
+    game: &Game,
+    camera: Camera,
+    mut graphics: Option<&mut Graphics>,
+) -> std::io::Result<()> {
+    match graphics.as_deref_mut() {
+        Some(view) => view.draw_course(
+            stdout,
+            game,
+            camera,
+            |wx, wy| tile_rgb(game, camera, wx, wy),
+            (0, HEADER_ROWS),
+        )?,
+        None => draw_terrain(stdout, game, camera)?,
+    }
+    if camera.zoom <= 1.0 {
+        if game.settings.distance_arcs {
+            draw_distance_arcs(stdout, game, camera)?;
+        }
+        draw_shot_markers(stdout, game, camera)?;
+    }
+    if let Some(landing) = game.presentation.as_ref().and_then(|p| p.landing) {
+        draw_landing_mark(stdout, landing, camera)?;
+    }
+    if let Some(ghost) = game
+        .ghost_hole()
+        .and_then(|(hole, secs)| hole.position(secs))
+    {
+        if let Some(cell) = camera.world_to_screen(ghost.x, ghost.y) {
+            draw_ball(stdout, cell, Color::DarkGrey, '●', graphics.as_deref_mut())?;
+        }
+    }
+    if let Some(ai) = &game.ai {
+        draw_ai_ball(
+            stdout,
+            &ai.game,
+            camera,
+            game.frame_alpha,
+            graphics.as_deref_mut(),
+        )?;
+    }
+    draw_entities(stdout, game, camera, player_style(game), graphics)?;
+    if game.relief.is_some() && game.can_shoot() {
+        draw_relief_menu(stdout, game)?;
+    }
+    if game.strokes == 0 {
+        draw_bag_banner(stdout, game)?;
+    }
+    if let Some(warm_up) = game.warm_up.as_ref().filter(|w| !w.misses.is_empty()) {
+        draw_range_dispersion(stdout, &warm_up.misses)?;
+    }
+    if game.slow_link && !game.settings.low_bandwidth {
+        draw_slow_link_hint(stdout)?;
+    } else if let Some(step) = game.tutorial.filter(|_| game.can_shoot()) {
+        draw_tutorial_hint(stdout, game, step)?;
+    } else if game.idle_hint_due() {
+        draw_idle_hint(stdout, game)?;
+    }
+    Ok(())
+}
+
+// The ways the rules allow play to go on, in a box over the top of the map.
+fn draw_relief_menu(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let Some(choice) = &game.relief else {
+        return Ok(());
+    };
+    let title = format!(" Relief: 1-{} to pick, then play ", choice.options.len());
+    let width = title.chars().count();
+    queue!(
+        stdout,
+        at(1, 1),
+        SetForegroundColor(Color::Black),
+        SetBackgroundColor(Color::White),
+        Print(&title)
     )?;
+    for (i, option) in choice.options.iter().enumerate() {
+        let line = format!(
+            " {} {} {:<20} +{}",
+            if i == choice.chosen { '>' } else { ' ' },
+            i + 1,
+            option.relief.name(),
+            option.penalty
+        );
+        let background = if i == choice.chosen {
+            Color::Yellow
+        } else {
+            Color::White
+        };
+        queue!(
+            stdout,
+            at(1, 2 + i as u16),
+            SetBackgroundColor(background),
+            Print(format!("{:<width$}", line, width = width))
+        )?;
+    }
+    queue!(stdout, SetBackgroundColor(Color::Reset))
+}
+
+fn draw_landing_mark(
+    stdout: &mut impl Write,
+    landing: Vec2,
+    camera: Camera,
+) -> std::io::Result<()> {
+    if let Some((sx, sy)) = camera.world_to_screen(landing.x, landing.y) {
+        queue!(
+            stdout,
+            at(sx as u16, sy as u16),
+            SetForegroundColor(Color::Yellow),
+            Print('x')
+        )?;
+    }
     Ok(())
 }
 
-fn draw_entities(
-    stdout: &mut Stdout,
+// The holed shot's last moments from the green camera, the ball leaving a
+// trail of where it has been so far.
+fn draw_replay(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let Some(replay) = &game.replay else {
+        return Ok(());
+    };
+    let camera = Camera::green(game);
+    draw_terrain(stdout, game, camera)?;
+    if let Some((hx, hy)) = camera.world_to_screen(game.hole.x, game.hole.y) {
+        queue!(
+            stdout,
+            at(hx as u16, hy as u16),
+            SetForegroundColor(cup_color(game)),
+            Print('◉')
+        )?;
+    }
+
+    let shown = replay.shown();
+    if let Some((now, past)) = shown.split_last() {
+        for frame in past {
+            if let Some((sx, sy)) = camera.world_to_screen(frame.ball.x, frame.ball.y) {
+                queue!(
+                    stdout,
+                    at(sx as u16, sy as u16),
+                    SetForegroundColor(Color::Grey),
+                    Print('.')
+                )?;
+            }
+        }
+        if now.height > 0.0 {
+            if let Some((gx, gy)) = camera.world_to_screen(now.ball.x, now.ball.y) {
+                queue!(
+                    stdout,
+                    at(gx as u16, gy as u16),
+                    SetForegroundColor(Color::DarkGrey),
+                    Print('◌')
+                )?;
+            }
+        }
+        let air_y = (now.ball.y - now.height).max(0.0);
+        if let Some((bx, by)) = camera.world_to_screen(now.ball.x, air_y) {
+            queue!(
+                stdout,
+                at(bx as u16, by as u16),
+                SetForegroundColor(player_style(game).ball),
+                Print(ball_glyph(game))
+            )?;
+        }
+    }
+
+    let banner = " SLOW-MO REPLAY (any key skips) ";
+    queue!(
+        stdout,
+        at((WIDTH as usize - banner.len()) as u16 / 2, 0),
+        SetForegroundColor(Color::Black),
+        SetBackgroundColor(Color::Yellow),
+        Print(banner),
+        SetBackgroundColor(Color::Reset)
+    )
+}
+
+// Pulses along the bottom of the map once the player has stood over the
+// ball for a while without touching anything.
+fn draw_idle_hint(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let hint = " Press H for help ";
+    let bright = !game.settings.ambient_motion() || (game.idle_secs * 2.0) as i32 % 2 == 0;
+    let color = if bright {
+        Color::Yellow
+    } else {
+        Color::DarkYellow
+    };
+    queue!(
+        stdout,
+        at((WIDTH as usize - hint.len()) as u16 / 2, HEIGHT as u16 - 1),
+        SetForegroundColor(Color::Black),
+        SetBackgroundColor(color),
+        Print(hint),
+        SetBackgroundColor(Color::Reset)
+    )
+}
+
+// The tutorial's next step, along the bottom of the map while the ball
+// waits to be played.
+fn draw_tutorial_hint(
+    stdout: &mut impl Write,
     game: &Game,
-    left: i32,
-    top: i32,
-    zoom: i32,
+    step: TutorialStep,
 ) -> std::io::Result<()> {
-    for (i, p) in game.trail.iter().enumerate() {
-        if let Some((sx, sy)) = world_to_screen(p.x, p.y, left, top, zoom) {
-            let fade = i as f32 / (game.trail.len().max(1) as f32);
-            let ch = if fade < 0.34 {
-                'o'
-            } else if fade < 0.68 {
-                '*'
-            } else {
-                '.'
-            };
-            let shade = (210.0 - fade * 130.0) as u8;
+    let hint = format!(
+        " {} ",
+        step.hint(game.on_green(), game.settings.pull_back_putting)
+    );
+    queue!(
+        stdout,
+        at(
+            (WIDTH as usize).saturating_sub(hint.chars().count()) as u16 / 2,
+            HEIGHT as u16 - 1
+        ),
+        SetForegroundColor(Color::Black),
+        SetBackgroundColor(Color::Cyan),
+        Print(hint),
+        SetBackgroundColor(Color::Reset)
+    )
+}
+
+// Frames are taking too long to get to the terminal.
+fn draw_slow_link_hint(stdout: &mut impl Write) -> std::io::Result<()> {
+    let hint = " Slow link? Shift+B: low-bandwidth mode ";
+    queue!(
+        stdout,
+        at((WIDTH as usize - hint.len()) as u16 / 2, HEIGHT as u16 - 1),
+        SetForegroundColor(Color::Black),
+        SetBackgroundColor(Color::Yellow),
+        Print(hint),
+        SetBackgroundColor(Color::Reset)
+    )
+}
+
+fn pixel_color(game: &Game, pixel: Pixel) -> Color {
+    match pixel {
+        Pixel::Ground(surface) => tile_color(game, surface),
+        Pixel::Tee => Color::White,
+        Pixel::Pin => Color::Red,
+    }
+}
+
+fn draw_thumbnail(
+    stdout: &mut impl Write,
+    game: &Game,
+    layout: &HoleLayout,
+    (left, top): (u16, u16),
+    (width, height): (usize, usize),
+) -> std::io::Result<()> {
+    for (row, cells) in thumbnail(layout, width, height).iter().enumerate() {
+        queue!(stdout, at(left, top + row as u16))?;
+        for (upper, lower) in cells {
             queue!(
                 stdout,
-                MoveTo(sx as u16, sy as u16),
-                SetForegroundColor(Color::Rgb {
-                    r: shade,
-                    g: shade,
-                    b: shade,
-                }),
-                Print(ch)
+                SetForegroundColor(pixel_color(game, *upper)),
+                SetBackgroundColor(pixel_color(game, *lower)),
+                Print('▀')
+            )?;
+        }
+        queue!(stdout, SetBackgroundColor(Color::Reset))?;
+    }
+    Ok(())
+}
+
+// The hole just finished with the ball's path over it, the round so far, and
+// the next hole waiting.
+fn draw_between_holes(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let Some(recap) = &game.between_holes else {
+        return Ok(());
+    };
+    let (thumb_w, thumb_h) = (34, 14);
+    queue!(
+        stdout,
+        at(1, 0),
+        SetForegroundColor(Color::Cyan),
+        Print(format!(
+            "HOLE {} \"{}\" - {}",
+            recap.number,
+            recap.name,
+            recap.verdict()
+        ))
+    )?;
+
+    draw_thumbnail(stdout, game, &recap.layout, (1, 2), (thumb_w, thumb_h))?;
+    // The thumbnail packs two map rows into each cell, like `thumbnail`.
+    let scale_x = recap.layout.width as f32 / thumb_w as f32;
+    let scale_y = recap.layout.height as f32 / (thumb_h * 2) as f32;
+    let cell = |p: Vec2| {
+        let (x, y) = ((p.x / scale_x) as usize, (p.y / scale_y) as usize / 2);
+        (x < thumb_w && y < thumb_h).then_some((1 + x as u16, 2 + y as u16))
+    };
+    for pair in recap.path.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        let steps = (Vec2::new(to.x - from.x, to.y - from.y).length() / scale_x).ceil() as usize;
+        for i in 1..steps.max(1) {
+            let t = i as f32 / steps as f32;
+            let p = Vec2::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+            if let Some((x, y)) = cell(p) {
+                queue!(
+                    stdout,
+                    at(x, y),
+                    SetForegroundColor(Color::White),
+                    Print('·')
+                )?;
+            }
+        }
+    }
+    for &spot in &recap.path[..recap.path.len() - 1] {
+        if let Some((x, y)) = cell(spot) {
+            queue!(
+                stdout,
+                at(x, y),
+                SetForegroundColor(Color::White),
+                Print('●')
             )?;
         }
     }
 
-    if let Some((hx, hy)) = world_to_screen(game.hole.x, game.hole.y, left, top, zoom) {
+    let right = thumb_w as u16 + 4;
+    let round_total = game.course_round.as_ref().map(|round| {
+        let (strokes, par) = round.card.total();
+        let holes = round.card.holes_played();
+        format!(
+            "Round: {} ({} thru {})",
+            strokes,
+            to_par_label(strokes as i32 - par as i32),
+            holes
+        )
+    });
+    let next_note = game
+        .course_round
+        .as_ref()
+        .and_then(|round| game.notes.note(&round.course.name, recap.next_number))
+        .map(|note| {
+            format!("Your note: {}", note)
+                .chars()
+                .take(WIDTH as usize - right as usize)
+                .collect()
+        })
+        .unwrap_or_default();
+    let lines = [
+        (
+            format!("{} on a par {}", recap.strokes, recap.layout.par),
+            Color::Yellow,
+        ),
+        (format!("Putts: {}", recap.putts), Color::White),
+        (format!("Penalties: {}", recap.penalties), Color::White),
+        (
+            format!(
+                "Longest shot: {}",
+                game.settings.units.short(recap.longest_yd)
+            ),
+            Color::White,
+        ),
+        (String::new(), Color::White),
+        (round_total.unwrap_or_default(), Color::Green),
+        (String::new(), Color::White),
+        (
+            format!("NEXT: Hole {} \"{}\"", recap.next_number, recap.next_name),
+            Color::Cyan,
+        ),
+        (
+            format!(
+                "Par {}  {}",
+                recap.next_layout.par,
+                game.settings.units.short(recap.next_layout.length_yd())
+            ),
+            Color::White,
+        ),
+        (next_note, Color::Magenta),
+    ];
+    for (row, (line, color)) in lines.iter().enumerate() {
         queue!(
             stdout,
-            MoveTo(hx as u16, hy as u16),
-            SetForegroundColor(Color::Blue),
-            Print('◉')
+            at(right, 2 + row as u16),
+            SetForegroundColor(*color),
+            Print(line)
+        )?;
+    }
+    draw_thumbnail(stdout, game, &recap.next_layout, (right, 13), (28, 7))?;
+    queue!(
+        stdout,
+        at(1, (HEIGHT - 2) as u16),
+        SetForegroundColor(Color::DarkGrey),
+        Print("Any key: to the next tee")
+    )
+}
+
+// "5/9", or "-" with no chances at it.
+fn out_of(made: u32, chances: u32) -> String {
+    if chances == 0 {
+        "-".to_string()
+    } else {
+        format!("{}/{}", made, chances)
+    }
+}
+
+// The card as it was signed after the last hole: every hole in the order
+// played, nine to a block, with the round's standing against par after each.
+fn draw_round_card(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let Some(card) = &game.round_card else {
+        return Ok(());
+    };
+    queue!(
+        stdout,
+        at(1, 0),
+        SetForegroundColor(Color::Cyan),
+        Print(format!("FINAL CARD - {}", card.course))
+    )?;
+
+    let running = card.running();
+    let mut top = 2;
+    for block in running.chunks(9) {
+        let cell = |value: String| format!("{:>4}", value);
+        let mut holes = format!("{:<7}", "Hole");
+        let mut pars = format!("{:<7}", "Par");
+        let mut scores = format!("{:<7}", "Score");
+        let mut standings = format!("{:<7}", "To par");
+        for (line, standing) in block {
+            holes.push_str(&cell(line.number.to_string()));
+            pars.push_str(&cell(line.par.to_string()));
+            scores.push_str(&cell(
+                line.strokes.map_or("-".to_string(), |s| s.to_string()),
+            ));
+            standings.push_str(&cell(standing.map_or("-".to_string(), to_par_label)));
+        }
+        let played = block.iter().filter_map(|(line, _)| line.strokes);
+        holes.push_str(&format!("{:>6}", "Tot"));
+        pars.push_str(&format!(
+            "{:>6}",
+            block.iter().map(|(line, _)| line.par).sum::<u32>()
+        ));
+        scores.push_str(&format!("{:>6}", played.sum::<u32>()));
+        for (i, (line, color)) in [
+            (holes, Color::Cyan),
+            (pars, Color::White),
+            (scores, Color::Yellow),
+            (standings, Color::Green),
+        ]
+        .iter()
+        .enumerate()
+        {
+            queue!(
+                stdout,
+                at(1, top + i as u16),
+                SetForegroundColor(*color),
+                Print(line)
+            )?;
+        }
+        top += 5;
+    }
+
+    let tally = &game.round_tally;
+    let putts_a_hole = tally.putts as f32 / tally.holes.max(1) as f32;
+    queue!(
+        stdout,
+        at(1, top + 1),
+        SetForegroundColor(Color::White),
+        Print(format!(
+            "Fairways {}   Greens {}   Putts {} ({:.1} a hole)",
+            out_of(tally.fairways_hit, tally.fairway_chances),
+            out_of(tally.greens_in_regulation, tally.holes),
+            tally.putts,
+            putts_a_hole
+        )),
+        at(1, top + 2),
+        Print(format!(
+            "Sand saves {}   Up and downs {}",
+            out_of(tally.sand_saves, tally.sand_chances),
+            out_of(tally.up_and_downs, tally.up_and_down_chances)
+        ))
+    )?;
+    // Under par reads as climbing.
+    let trajectory: Vec<f32> = running
+        .iter()
+        .filter_map(|(_, standing)| standing.map(|s| -s as f32))
+        .collect();
+    if trajectory.len() > 1 {
+        queue!(
+            stdout,
+            at(1, top + 3),
+            SetForegroundColor(Color::Green),
+            Print(format!("To par   {}", chart::sparkline(&trajectory)))
+        )?;
+    }
+    for (i, award) in game.round_awards.iter().enumerate() {
+        queue!(
+            stdout,
+            at(1, top + 4 + i as u16),
+            SetForegroundColor(Color::Magenta),
+            Print(format!("* {} - {}", award.title, award.why))
+        )?;
+    }
+    let (strokes, par) = card.total();
+    let course_par: u32 = running.iter().map(|(line, _)| line.par).sum();
+    queue!(
+        stdout,
+        at(1, top),
+        SetForegroundColor(Color::Green),
+        Print(format!(
+            "Total {} ({}) on a par {} round of {} holes",
+            strokes,
+            to_par_label(strokes as i32 - par as i32),
+            course_par,
+            card.order.len()
+        )),
+        at(1, (HEIGHT - 2) as u16),
+        SetForegroundColor(Color::DarkGrey),
+        Print(if game.tournament.is_some() {
+            "Any key: the leaderboard"
+        } else {
+            "Any key: start a new round"
+        })
+    )
+}
+
+// The tournament after the round just signed for: each golfer's rounds and
+// total to par, the player picked out and those cut greyed below the rest.
+fn draw_leaderboard(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let Some(tournament) = &game.tournament else {
+        return Ok(());
+    };
+    let played = tournament.rounds_played();
+    let title = if tournament.is_over() {
+        format!("FINAL LEADERBOARD - {}", tournament.course)
+    } else {
+        format!(
+            "LEADERBOARD - {} - after round {} of {}",
+            tournament.course, played, TOURNAMENT_ROUNDS
+        )
+    };
+    let mut header = format!("{:<5}{:<12}", "Pos", "Golfer");
+    for round in 1..=TOURNAMENT_ROUNDS {
+        header.push_str(&format!("{:>5}", format!("R{}", round)));
+    }
+    header.push_str(&format!("{:>7}", "Total"));
+    queue!(
+        stdout,
+        at(1, 0),
+        SetForegroundColor(Color::Cyan),
+        Print(title),
+        at(1, 2),
+        Print(header)
+    )?;
+
+    for (row, (place, entrant)) in tournament.leaderboard().into_iter().enumerate() {
+        let mut line = format!("{:<5}{:<12}", place, entrant.name);
+        for round in 0..TOURNAMENT_ROUNDS {
+            let score = entrant
+                .rounds
+                .get(round)
+                .map_or("-".to_string(), |s| to_par_label(*s));
+            line.push_str(&format!("{:>5}", score));
+        }
+        line.push_str(&format!("{:>7}", to_par_label(entrant.total())));
+        let color = match entrant {
+            e if e.is_player() => Color::Yellow,
+            e if e.missed_cut => Color::DarkGrey,
+            _ => Color::White,
+        };
+        queue!(
+            stdout,
+            at(1, 3 + row as u16),
+            SetForegroundColor(color),
+            Print(line)
         )?;
     }
 
-    if let Some(air) = game.airborne {
-        let ground = air.ground_pos();
-        let arc = air.arc_height();
-        let air_y = (ground.y - arc).max(0.0);
+    let place = tournament.player_place();
+    let (verdict, next) = match tournament.player() {
+        player if player.missed_cut => (
+            "You missed the cut".to_string(),
+            "Any key: start a new tournament",
+        ),
+        _ if tournament.is_over() && place == "1" => (
+            "You won the tournament!".to_string(),
+            "Any key: start a new tournament",
+        ),
+        _ if tournament.is_over() => (
+            format!("You finished {}", place),
+            "Any key: start a new tournament",
+        ),
+        _ if played == CUT_AFTER => (
+            format!("You made the cut, lying {}", place),
+            "Any key: to the next round",
+        ),
+        _ => (
+            format!("You're lying {}", place),
+            "Any key: to the next round",
+        ),
+    };
+    queue!(
+        stdout,
+        at(1, (HEIGHT - 4) as u16),
+        SetForegroundColor(Color::Green),
+        Print(verdict),
+        at(1, (HEIGHT - 2) as u16),
+        SetForegroundColor(Color::DarkGrey),
+        Print(next)
+    )
+}
+
+fn draw_hole_browser(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let Some(browser) = &game.hole_browser else {
+        return Ok(());
+    };
+    let (thumb_w, thumb_h) = (22, 9);
+    queue!(
+        stdout,
+        at(1, 0),
+        SetForegroundColor(Color::Cyan),
+        Print("CHOOSE THE NEXT HOLE"),
+        at(1, 22),
+        Print("<-/-> browse  Enter/1-3 play  N new choices  Esc cancel")
+    )?;
+
+    for (i, candidate) in browser.candidates.iter().enumerate() {
+        let left = 1 + i as u16 * (thumb_w as u16 + 2);
+        let selected = i == browser.selected;
+        let (label_fg, label_bg) = if selected {
+            (Color::Black, Color::Yellow)
+        } else {
+            (Color::Yellow, Color::Reset)
+        };
+        queue!(
+            stdout,
+            at(left, 2),
+            SetForegroundColor(label_fg),
+            SetBackgroundColor(label_bg),
+            Print(format!(" {} ", i + 1)),
+            SetBackgroundColor(Color::Reset)
+        )?;
+
+        draw_thumbnail(
+            stdout,
+            game,
+            &candidate.layout,
+            (left, 3),
+            (thumb_w, thumb_h),
+        )?;
+
+        let info = [
+            format!(
+                "Par {}  {}",
+                candidate.par,
+                game.settings.units.short(candidate.layout.length_yd())
+            ),
+            candidate.layout.template.name().to_string(),
+            candidate.conditions.name().to_string(),
+            candidate
+                .difficulty
+                .map(|d| format!("Plays {}", d.rating()))
+                .unwrap_or_default(),
+        ];
+        let color = if selected { Color::White } else { Color::Grey };
+        for (row, line) in info.iter().enumerate() {
+            queue!(
+                stdout,
+                at(left, 4 + thumb_h as u16 + row as u16),
+                SetForegroundColor(color),
+                Print(line)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn draw_help_screen(stdout: &mut impl Write) -> std::io::Result<()> {
+    let lines = [
+        ("HOW TO PLAY (H to close)", Color::Cyan),
+        ("", Color::Cyan),
+        (
+            "Get the ball (●) into the cup (◉) in as few strokes",
+            Color::White,
+        ),
+        (
+            "as you can. Par is the target score for the hole.",
+            Color::White,
+        ),
+        ("", Color::Cyan),
+        (
+            "1. Turn the aim line with A/D or the left/right arrows.",
+            Color::Green,
+        ),
+        (
+            "2. Pick a club with W/S; the HUD shows how far it goes.",
+            Color::Green,
+        ),
+        (
+            "3. E changes the swing for shorter, softer shots.",
+            Color::Green,
+        ),
+        ("4. Space or Enter hits the ball.", Color::Green),
+        ("", Color::Cyan),
+        (
+            "C lets the caddie pick the club and swing for you,",
+            Color::Yellow,
+        ),
+        (
+            "and F takes the caddie's line (the grey ghost aim).",
+            Color::Yellow,
+        ),
+        ("On the green the putter picks its own pace.", Color::Yellow),
+        ("", Color::Cyan),
+        (
+            "Fairway is the easiest lie; rough, sand and wind all",
+            Color::White,
+        ),
+        (
+            "cost distance or accuracy. The full control list is",
+            Color::White,
+        ),
+        ("on the right (Tab shows the full HUD).", Color::White),
+    ];
+    for (i, (line, color)) in lines.iter().enumerate() {
+        queue!(
+            stdout,
+            at(1, i as u16 + 1),
+            SetForegroundColor(*color),
+            Print(line)
+        )?;
+    }
+    Ok(())
+}
+
+fn draw_pause_banner(stdout: &mut impl Write) -> std::io::Result<()> {
+    let banner = " PAUSED - terminal lost focus ";
+    queue!(
+        stdout,
+        SetAttribute(Attribute::NormalIntensity),
+        at(
+            (WIDTH as usize - banner.len()) as u16 / 2,
+            HEIGHT as u16 / 2
+        ),
+        SetForegroundColor(Color::Black),
+        SetBackgroundColor(Color::White),
+        Print(banner),
+        SetBackgroundColor(Color::Reset)
+    )
+}
+
+// Announces the restricted bag on the tee so it can't be missed.
+// The warm-up balls so far around the flag, in the corner of the range.
+fn draw_range_dispersion(stdout: &mut impl Write, misses: &[ApproachMiss]) -> std::io::Result<()> {
+    let (width, height) = (13, 7);
+    let left = WIDTH as u16 - width as u16 - 1;
+    let points: Vec<(f32, f32)> = misses
+        .iter()
+        .map(|m| (m.right_ft / 3.0, m.long_ft / 3.0))
+        .collect();
+    let furthest = points
+        .iter()
+        .map(|(x, y)| x.abs().max(y.abs()))
+        .fold(0.0, f32::max);
+    let extent = ((furthest / 5.0).ceil() * 5.0).max(5.0);
+    queue!(
+        stdout,
+        at(left, 0),
+        SetForegroundColor(Color::Cyan),
+        Print(format!("{:<13}", format!("+/-{:.0} yd", extent)))
+    )?;
+    for (i, row) in chart::scatter(&points, extent, width, height)
+        .iter()
+        .enumerate()
+    {
+        queue!(
+            stdout,
+            at(left, 1 + i as u16),
+            SetForegroundColor(Color::White),
+            Print(row)
+        )?;
+    }
+    Ok(())
+}
+
+fn draw_bag_banner(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let Some(rule) = game.bag.rule_label() else {
+        return Ok(());
+    };
+    let banner = format!(" This hole: {} ", rule);
+    queue!(
+        stdout,
+        at(1, 0),
+        SetForegroundColor(Color::Black),
+        SetBackgroundColor(Color::Yellow),
+        Print(banner),
+        SetBackgroundColor(Color::Reset)
+    )
+}
+
+fn draw_terrain(stdout: &mut impl Write, game: &Game, camera: Camera) -> std::io::Result<()> {
+    let shade_slopes = game.settings.slope_shading && camera.zoom > 1.0;
+    for sy in 0..HEIGHT {
+        for sx in 0..WIDTH {
+            let (wx, wy) = camera.screen_to_world(sx, sy);
+            if wx >= 0 && wy >= 0 && wx < game.layout.width && wy < game.layout.height {
+                if heat_shown(game, camera) {
+                    let heat = heat_color(game, wx, wy).map_or(Color::Reset, |c| scaled(c, 0.4));
+                    queue!(stdout, SetBackgroundColor(heat))?;
+                }
+                draw_tile(stdout, game, (sx, sy), (wx, wy), shade_slopes)?;
+            }
+        }
+    }
+    queue!(stdout, SetBackgroundColor(Color::Reset))?;
+    Ok(())
+}
+
+fn draw_shot_markers(stdout: &mut impl Write, game: &Game, camera: Camera) -> std::io::Result<()> {
+    for (i, shot) in game.shot_history.iter().enumerate() {
+        if let Some((sx, sy)) = camera.world_to_screen(shot.from.x, shot.from.y) {
+            let label = std::char::from_digit(i as u32 + 1, 10).unwrap_or('+');
+            queue!(
+                stdout,
+                at(sx as u16, sy as u16),
+                SetForegroundColor(Color::Magenta),
+                Print(label)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// The heat map goes under the full view once it's been worked out.
+fn heat_shown(game: &Game, camera: Camera) -> bool {
+    game.settings.heat_map && game.heat_map.is_some() && camera.zoom <= 1.0
+}
+
+// Green where the caddie expects to get down quickest from, through yellow
+// to red where it's `SPREAD_STROKES` worse. Water has no color of its own.
+fn heat_color(game: &Game, wx: i32, wy: i32) -> Option<Color> {
+    let map = game.heat_map.as_ref()?;
+    if game.terrain.surface(wx, wy) == Surface::Water {
+        return None;
+    }
+    let share = map.share(map.strokes_at(wx, wy)?);
+    Some(Color::Rgb {
+        r: (255.0 * (2.0 * share).min(1.0)) as u8,
+        g: (255.0 * (2.0 - 2.0 * share).min(1.0)) as u8,
+        b: 0,
+    })
+}
+
+// Like the arcs on a broadcast flyover: a dotted curve at each distance from
+// the pin, across the hole, labelled where it meets the line from the tee.
+// Arcs as long as the hole or longer are left out.
+fn draw_distance_arcs(stdout: &mut impl Write, game: &Game, camera: Camera) -> std::io::Result<()> {
+    let pin = game.hole;
+    let back = Vec2::new(game.layout.tee.x - pin.x, game.layout.tee.y - pin.y);
+    let heading = back.y.atan2(back.x);
+    let on_map = |p: Vec2| {
+        p.x >= 0.0
+            && p.y >= 0.0
+            && p.x < game.layout.width as f32
+            && p.y < game.layout.height as f32
+    };
+    for yards in DISTANCE_ARCS_YD {
+        let radius = yards / game.layout.yards_per_tile;
+        if radius >= back.length() {
+            continue;
+        }
+        let around =
+            |angle: f32| Vec2::new(pin.x + radius * angle.cos(), pin.y + radius * angle.sin());
+        // A point every half tile keeps the curve unbroken on screen.
+        let steps = (radius * ARC_HALF_ANGLE * 4.0).ceil() as i32;
+        for i in -steps..=steps {
+            let point = around(heading + ARC_HALF_ANGLE * i as f32 / steps as f32);
+            let Some((sx, sy)) = camera
+                .world_to_screen(point.x, point.y)
+                .filter(|_| on_map(point))
+            else {
+                continue;
+            };
+            queue!(
+                stdout,
+                at(sx as u16, sy as u16),
+                SetForegroundColor(Color::White),
+                Print('·')
+            )?;
+        }
+        if let Some((sx, sy)) = camera.world_to_screen(around(heading).x, around(heading).y) {
+            let label = format!("{:.0}", yards);
+            let left = (sx - label.len() as i32 / 2).clamp(0, WIDTH - label.len() as i32);
+            queue!(
+                stdout,
+                at(left as u16, sy as u16),
+                SetForegroundColor(Color::White),
+                Print(label)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn draw_stats_screen(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let bar_width = 30;
+    let mut lines = vec![
+        ("HOLE-SIDE STATS (G for lifetime)".to_string(), Color::Cyan),
+        ("".to_string(), Color::Cyan),
+        (
+            "Average proximity (ft) by approach distance".to_string(),
+            Color::Cyan,
+        ),
+    ];
+
+    let max_ft = game
+        .stats
+        .approaches
+        .iter()
+        .filter_map(|b| b.average_ft())
+        .fold(0.0, f32::max);
+    for (bucket, (_, _, label)) in game.stats.approaches.iter().zip(APPROACH_BUCKETS) {
+        let line = match bucket.average_ft() {
+            Some(ft) => format!(
+                "{:>11} {} {:>5.0} ft ({})",
+                label,
+                chart::bar(ft, max_ft, bar_width),
+                ft,
+                bucket.shots
+            ),
+            None => format!("{:>11} {:bar_width$} no shots", label, ""),
+        };
+        lines.push((line, Color::Green));
+    }
+    let recent: Vec<f32> = game
+        .stats
+        .recent_misses
+        .iter()
+        .map(|m| m.proximity_ft())
+        .collect();
+    if !recent.is_empty() {
+        lines.push((
+            format!("{:>11} {}", "recent", chart::sparkline(&recent)),
+            Color::Green,
+        ));
+    }
+
+    lines.push(("".to_string(), Color::Cyan));
+    lines.push(("Putts holed by length".to_string(), Color::Cyan));
+    for (bucket, (_, _, label)) in game.stats.putts.iter().zip(PUTT_BUCKETS) {
+        let line = match bucket.make_rate() {
+            Some(rate) => format!(
+                "{:>11} {} {:>5.0}% ({}/{})",
+                label,
+                chart::bar(rate, 1.0, bar_width),
+                rate * 100.0,
+                bucket.makes,
+                bucket.attempts
+            ),
+            None => format!("{:>11} {:bar_width$} no putts", label, ""),
+        };
+        lines.push((line, Color::Yellow));
+    }
+
+    for (i, (line, color)) in lines.iter().enumerate() {
+        queue!(
+            stdout,
+            at(1, i as u16 + 1),
+            SetForegroundColor(*color),
+            Print(line)
+        )?;
+    }
+    draw_miss_pattern(stdout, game)?;
+    draw_challenge_record(stdout, game)
+}
+
+fn draw_lifetime_screen(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let profile = &game.profile;
+    let average = profile
+        .average_strokes()
+        .map(|avg| format!("{:.2}", avg))
+        .unwrap_or_else(|| "-".to_string());
+    let best_by_par = (3..=5)
+        .map(|par| {
+            let best = profile.best_holes.get(&format!("par {}", par));
+            format!(
+                "par {} {}",
+                par,
+                best.map_or("-".to_string(), u32::to_string)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let lines = [
+        (
+            format!("LIFETIME STATS: {} (G to close)", profile.name),
+            Color::Cyan,
+        ),
+        ("".to_string(), Color::Cyan),
+        (
+            format!("Time played   {}", format_duration(profile.play_secs)),
+            Color::Green,
+        ),
+        (
+            format!("This session  {}", format_duration(profile.session_secs)),
+            Color::Green,
+        ),
+        ("".to_string(), Color::Cyan),
+        (format!("Rounds        {}", profile.rounds), Color::Yellow),
+        (format!("Holes         {}", profile.holes), Color::Yellow),
+        (format!("Avg strokes   {}", average), Color::Yellow),
+        (format!("Best holes    {}", best_by_par), Color::Yellow),
+        (format!("Aces          {}", profile.aces), Color::Magenta),
+        (
+            profile
+                .history
+                .iter()
+                .rev()
+                .find(|round| !round.titles.is_empty())
+                .map(|round| format!("Last title    {}", award_titles(&round.titles)))
+                .unwrap_or_default(),
+            Color::Magenta,
+        ),
+        ("".to_string(), Color::Cyan),
+        (
+            format!("Career earnings ${}", profile.career_earnings),
+            Color::Green,
+        ),
+        (
+            format!("To spend        ${}", profile.balance),
+            Color::Green,
+        ),
+    ];
+    for (i, (line, color)) in lines.iter().enumerate() {
+        queue!(
+            stdout,
+            at(1, i as u16 + 1),
+            SetForegroundColor(*color),
+            Print(line)
+        )?;
+    }
+    Ok(())
+}
+
+// The catalog one slot after another, marking what's owned and worn.
+fn draw_pro_shop(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let profile = &game.profile;
+    queue!(
+        stdout,
+        at(1, 1),
+        SetForegroundColor(Color::Cyan),
+        Print(format!(
+            "PRO SHOP - ${} to spend (<-/-> browse, Enter buy or wear, G to close)",
+            profile.balance
+        ))
+    )?;
+    for (i, item) in shop::CATALOG.iter().enumerate() {
+        let worn = std::ptr::eq(profile.equipped(item.slot()), item);
+        let status = if worn {
+            "wearing".to_string()
+        } else if profile.owns(item) {
+            "owned".to_string()
+        } else {
+            format!("${}", item.price)
+        };
+        let preview = match item.look {
+            Look::Palette { ball, aim } => {
+                queue!(
+                    stdout,
+                    at(35, i as u16 + 3),
+                    SetForegroundColor(rgb(ball)),
+                    Print('●'),
+                    SetForegroundColor(rgb(aim)),
+                    Print(" ···")
+                )?;
+                String::new()
+            }
+            Look::Ball(glyph) => glyph.to_string(),
+            Look::Golfer { head, body, .. } => format!("{}{}", head, body),
+        };
+        let cursor = if i == game.shop_cursor { '>' } else { ' ' };
+        let color = match (i == game.shop_cursor, profile.owns(item)) {
+            (true, _) => Color::Yellow,
+            (false, true) => Color::Green,
+            (false, false) => Color::DarkGrey,
+        };
+        queue!(
+            stdout,
+            at(1, i as u16 + 3),
+            SetForegroundColor(color),
+            Print(format!(
+                "{} {:<8} {:<12} {:>7}   {}",
+                cursor,
+                item.slot().name(),
+                item.name,
+                status,
+                preview
+            ))
+        )?;
+    }
+    Ok(())
+}
+
+// One row of numbers per nine, with the nine's total at the end and the
+// whole round's after the back nine.
+fn draw_scorecard(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let Some(round) = &game.course_round else {
+        return Ok(());
+    };
+    let course = &round.course;
+    queue!(
+        stdout,
+        at(1, 1),
+        SetForegroundColor(Color::Cyan),
+        Print(format!("SCORECARD - {} (G to close)", course.name))
+    )?;
+
+    let mut top = 3;
+    let nines = [("Out", &course.front), ("In", &course.back)];
+    for (label, nine) in nines.iter().filter(|(_, nine)| !nine.is_empty()) {
+        let cell = |value: String| format!("{:>3}", value);
+        let mut holes = format!("{:<6}", "Hole");
+        let mut pars = format!("{:<6}", "Par");
+        let mut scores = format!("{:<6}", "Score");
+        for &idx in nine.iter() {
+            let hole = &course.holes[idx];
+            holes.push_str(&cell(hole.number.to_string()));
+            pars.push_str(&cell(hole.layout.par.to_string()));
+            scores.push_str(&cell(
+                round
+                    .card
+                    .strokes(idx)
+                    .map_or("-".to_string(), |s| s.to_string()),
+            ));
+        }
+        let (strokes, par) = round.card.played_total(nine);
+        let nine_par: u32 = nine.iter().map(|&i| course.holes[i].layout.par).sum();
+        holes.push_str(&format!("{:>5}", label));
+        pars.push_str(&format!("{:>5}", nine_par));
+        scores.push_str(&format!("{:>5}", strokes));
+        if strokes > 0 {
+            scores.push_str(&format!(" ({:+})", strokes as i32 - par as i32));
+        }
+        for (i, (line, color)) in [
+            (holes, Color::Cyan),
+            (pars, Color::White),
+            (scores, Color::Yellow),
+        ]
+        .iter()
+        .enumerate()
+        {
+            queue!(
+                stdout,
+                at(1, top + i as u16),
+                SetForegroundColor(*color),
+                Print(line)
+            )?;
+        }
+        top += 4;
+    }
+
+    draw_course_notes(stdout, game, top + 2)?;
+    let (strokes, par) = round.card.total();
+    let start = match round.start {
+        StartOption::FirstTee => "from the 1st tee".to_string(),
+        StartOption::BackNineOnly => "back nine only".to_string(),
+        StartOption::Shotgun(hole) => format!("shotgun start on {}", hole),
+    };
+    queue!(
+        stdout,
+        at(1, top),
+        SetForegroundColor(Color::Green),
+        Print(format!(
+            "Total {} ({:+}) after {} of {} holes, {}",
+            strokes,
+            strokes as i32 - par as i32,
+            round.card.holes_played(),
+            round.order.len(),
+            start
+        ))
+    )
+}
+
+// The player's rating of the course and the yardage book notes on its holes,
+// with the note for the hole in play being written if one is.
+fn draw_course_notes(stdout: &mut impl Write, game: &Game, top: u16) -> std::io::Result<()> {
+    let Some(round) = &game.course_round else {
+        return Ok(());
+    };
+    let notes = game.notes.course(&round.course.name);
+    queue!(
+        stdout,
+        at(1, top),
+        SetForegroundColor(Color::Yellow),
+        Print(format!(
+            "Your rating: {}  (1-5 rates the course)",
+            notes::stars(notes.and_then(|n| n.rating))
+        )),
+        at(1, top + 1),
+        SetForegroundColor(Color::Cyan),
+        Print(format!(
+            "Yardage book (Enter writes a note for hole {})",
+            round.current().number
+        ))
+    )?;
+    let mut row = top + 2;
+    if let Some(draft) = &game.note_draft {
+        queue!(
+            stdout,
+            at(1, row),
+            SetForegroundColor(Color::Yellow),
+            Print(format!(
+                "{:>4}  {}_  (Enter keeps, Esc drops)",
+                round.current().number,
+                draft
+            ))
+        )?;
+        row += 1;
+    }
+    for (hole, note) in notes.iter().flat_map(|n| &n.holes) {
+        if row >= HEIGHT as u16 {
+            break;
+        }
+        queue!(
+            stdout,
+            at(1, row),
+            SetForegroundColor(Color::White),
+            Print(format!("{:>4}  {}", hole, note))
+        )?;
+        row += 1;
+    }
+    Ok(())
+}
+
+// Re-renders a saved snapshot in the map area, with its caption along the
+// top and the hole's details along the bottom.
+fn draw_gallery(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let gallery = &game.gallery;
+    let Some(shot) = gallery.current() else {
+        return queue!(
+            stdout,
+            at(1, 1),
+            SetForegroundColor(Color::Cyan),
+            Print("GALLERY (G to close)"),
+            at(1, 3),
+            SetForegroundColor(Color::White),
+            Print("No snapshots yet. Hole-outs, near aces and disasters are saved here.")
+        );
+    };
+
+    for (sy, row) in shot.frame.iter().enumerate().take(HEIGHT as usize) {
+        for (sx, cell) in row.iter().enumerate().take(WIDTH as usize) {
+            let (glyph, color) = match *cell {
+                Cell::Off => continue,
+                Cell::Ground(surface) => (
+                    terrain_char(surface, sx as i32, sy as i32),
+                    tile_color(game, surface),
+                ),
+                Cell::Trail => ('*', Color::Grey),
+                Cell::Marker(n) => (
+                    std::char::from_digit(n as u32, 10).unwrap_or('+'),
+                    Color::Magenta,
+                ),
+                Cell::Cup => ('◉', cup_color(game)),
+                Cell::Ball => ('●', Color::White),
+            };
+            queue!(
+                stdout,
+                at(sx as u16, sy as u16),
+                SetForegroundColor(color),
+                Print(glyph)
+            )?;
+        }
+    }
+
+    let title = format!(
+        " GALLERY {}/{} - {}: {} ",
+        gallery.selected + 1,
+        gallery.shots.len(),
+        shot.moment.name(),
+        shot.caption
+    );
+    let details = format!(
+        " Par {}, {}, {} - <-/-> browse, G closes ",
+        shot.par,
+        game.settings.units.short(shot.yards),
+        shot.conditions
+    );
+    let fit = |line: String| line.chars().take(WIDTH as usize).collect::<String>();
+    queue!(
+        stdout,
+        at(0, 0),
+        SetForegroundColor(Color::Black),
+        SetBackgroundColor(Color::Yellow),
+        Print(fit(title)),
+        at(0, HEIGHT as u16 - 1),
+        SetBackgroundColor(Color::DarkGrey),
+        SetForegroundColor(Color::White),
+        Print(fit(details)),
+        SetBackgroundColor(Color::Reset)
+    )
+}
+
+fn draw_challenge_record(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let (left, top) = (64, 17);
+    queue!(
+        stdout,
+        at(left, top),
+        SetForegroundColor(Color::Cyan),
+        Print("Challenge holes (avg to par)")
+    )?;
+    let played = Challenge::ALL
+        .iter()
+        .filter_map(|c| game.stats.challenges.get(c.key()).map(|r| (c.name(), r)));
+    for (i, (name, record)) in played.enumerate() {
+        queue!(
+            stdout,
+            at(left, top + 1 + i as u16),
+            SetForegroundColor(Color::Magenta),
+            Print(format!(
+                "{:<20} {:>3} {:>+5.1}",
+                name,
+                record.holes,
+                record.average_to_par()
+            ))
+        )?;
+    }
+    Ok(())
+}
+
+fn draw_miss_pattern(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let (left, top) = (64, 3);
+    let points: Vec<(f32, f32)> = game
+        .stats
+        .recent_misses
+        .iter()
+        .map(|m| (m.right_ft, m.long_ft))
+        .collect();
+    let furthest = points
+        .iter()
+        .map(|(x, y)| x.abs().max(y.abs()))
+        .fold(0.0, f32::max);
+    let extent = ((furthest / 10.0).ceil() * 10.0).max(10.0);
+
+    queue!(
+        stdout,
+        at(left, top),
+        SetForegroundColor(Color::Cyan),
+        Print(format!("Miss pattern (+/-{:.0} ft)", extent)),
+        at(left, top + 1),
+        Print("long up, right to the right")
+    )?;
+    for (i, row) in chart::scatter(&points, extent, 21, 11).iter().enumerate() {
+        queue!(
+            stdout,
+            at(left, top + 2 + i as u16),
+            SetForegroundColor(Color::Green),
+            Print(row)
+        )?;
+    }
+    Ok(())
+}
+
+fn draw_tile(
+    stdout: &mut impl Write,
+    game: &Game,
+    (sx, sy): (i32, i32),
+    (wx, wy): (i32, i32),
+    shade_slopes: bool,
+) -> std::io::Result<()> {
+    if game.settings.theme == Theme::Retro {
+        return draw_tile_retro(stdout, game, sx, sy, wx, wy);
+    }
+
+    let tile = game.terrain.tile(wx, wy);
+    let green = tile.surface == Surface::Green;
+    let (slope, shaded) = (tile.slope, green && shade_slopes);
+    let glyph = if (green && tile.elevation > 0.0) || (shaded && slope.length() > SLOPE_READ_MIN) {
+        slope_arrow(slope.x, slope.y)
+    } else {
+        terrain_char(tile.surface, wx, wy)
+    };
+    let mut color = raised(tile_color(game, tile.surface), tile.elevation);
+    if shaded {
+        color = hillshade(color, slope);
+    }
+    queue!(
+        stdout,
+        at(sx as u16, sy as u16),
+        SetForegroundColor(color),
+        Print(glyph)
+    )?;
+    Ok(())
+}
+
+// Banks catch the light a little brighter than the flat around them.
+fn raised(color: Color, elevation: f32) -> Color {
+    scaled(color, 1.0 + elevation * 0.08)
+}
+
+// Light from the top left of the map: ground falling toward it is lit, ground
+// falling away is in shade, by more the steeper it is.
+fn hillshade(color: Color, slope: Vec2) -> Color {
+    let steepness = slope.length();
+    if steepness == 0.0 {
+        return color;
+    }
+    let facing = -(slope.x + slope.y) / (steepness * std::f32::consts::SQRT_2);
+    scaled(
+        color,
+        1.0 + 0.4 * facing * (steepness / MAX_SHADE_SLOPE).min(1.0),
+    )
+}
+
+fn scaled(color: Color, factor: f32) -> Color {
+    match color {
+        Color::Rgb { r, g, b } => {
+            let up = |c: u8| (c as f32 * factor).clamp(0.0, 255.0) as u8;
+            Color::Rgb {
+                r: up(r),
+                g: up(g),
+                b: up(b),
+            }
+        }
+        other => other,
+    }
+}
+
+// Points downhill so the contours on a green can be read from the map.
+fn slope_arrow(dx: f32, dy: f32) -> char {
+    const ARROWS: [char; 8] = ['→', '↘', '↓', '↙', '←', '↖', '↑', '↗'];
+    let octant = (dy.atan2(dx) / (PI / 4.0)).round() as i32;
+    ARROWS[octant.rem_euclid(8) as usize]
+}
+
+// Each cell is two stacked pixels via '▀': the top takes the surface colour
+// and the bottom a darker scanline. Columns are sampled in pairs so pixels
+// come out double-wide and chunky.
+fn draw_tile_retro(
+    stdout: &mut impl Write,
+    game: &Game,
+    sx: i32,
+    sy: i32,
+    wx: i32,
+    wy: i32,
+) -> std::io::Result<()> {
+    let px = wx - wx.rem_euclid(2);
+    let surface = game.terrain.surface(px, wy);
+    let dither = (px / 2 + wy).rem_euclid(2) == 0;
+    let (top, scanline) = retro_palette(surface, dither);
+    queue!(
+        stdout,
+        at(sx as u16, sy as u16),
+        SetForegroundColor(top),
+        SetBackgroundColor(scanline),
+        Print('▀')
+    )?;
+    Ok(())
+}
+
+fn retro_palette(surface: Surface, dither: bool) -> (Color, Color) {
+    match (surface, dither) {
+        (Surface::Green, _) => (Color::Green, Color::DarkGreen),
+        (Surface::Fringe, true) => (Color::Green, Color::DarkGreen),
+        (Surface::Fringe, false) => (Color::DarkGreen, Color::Green),
+        (Surface::Fairway, true) => (Color::DarkGreen, Color::DarkGreen),
+        (Surface::Fairway, false) => (Color::DarkGreen, Color::Black),
+        (Surface::Rough, true) => (Color::DarkGreen, Color::Black),
+        (Surface::Rough, false) => (Color::Black, Color::DarkGreen),
+        (Surface::HeavyRough, _) => (Color::Black, Color::DarkGreen),
+        (Surface::Bunker, true) => (Color::Yellow, Color::DarkYellow),
+        (Surface::Bunker, false) => (Color::DarkYellow, Color::DarkYellow),
+        (Surface::Water, true) => (Color::Blue, Color::DarkBlue),
+        (Surface::Water, false) => (Color::DarkBlue, Color::DarkBlue),
+    }
+}
+
+fn tile_color(game: &Game, surface: Surface) -> Color {
+    if !game.settings.high_contrast {
+        return terrain_color(surface);
+    }
+    match surface {
+        Surface::Green => Color::Rgb {
+            r: 140,
+            g: 255,
+            b: 140,
+        },
+        Surface::Fringe => Color::Rgb {
+            r: 70,
+            g: 230,
+            b: 70,
+        },
+        Surface::Fairway => Color::Rgb { r: 0, g: 200, b: 0 },
+        Surface::Rough => Color::Rgb { r: 0, g: 80, b: 0 },
+        Surface::HeavyRough => Color::Rgb { r: 0, g: 50, b: 0 },
+        Surface::Bunker => Color::Rgb {
+            r: 255,
+            g: 235,
+            b: 130,
+        },
+        Surface::Water => Color::Rgb {
+            r: 90,
+            g: 170,
+            b: 255,
+        },
+    }
+}
+
+fn hud_color(game: &Game) -> Color {
+    if game.settings.high_contrast {
+        Color::White
+    } else {
+        Color::Cyan
+    }
+}
+
+fn cup_color(game: &Game) -> Color {
+    if game.settings.high_contrast {
+        Color::White
+    } else {
+        Color::Blue
+    }
+}
+
+fn draw_entities(
+    stdout: &mut impl Write,
+    game: &Game,
+    camera: Camera,
+    style: BallStyle,
+    graphics: Option<&mut Graphics>,
+) -> std::io::Result<()> {
+    let view = game.ball_view(game.frame_alpha);
+    if !game.settings.reduced_motion {
+        draw_trail(stdout, game, &trail_to(game, view), camera)?;
+    }
+
+    if let Some((hx, hy)) = camera
+        .world_to_screen(game.hole.x, game.hole.y)
+        .filter(|_| graphics.is_none())
+    {
+        queue!(
+            stdout,
+            at(hx as u16, hy as u16),
+            SetForegroundColor(cup_color(game)),
+            Print('◉')
+        )?;
+    }
+
+    if let Some(elapsed) = game.celebration {
+        draw_celebration(stdout, game, camera, elapsed)?;
+    }
+
+    draw_spin_check(stdout, game, camera)?;
+
+    let ground = view.ground;
+    if game.airborne.is_some() {
+        if let Some((gx, gy)) = camera.world_to_screen(ground.x, ground.y) {
+            queue!(
+                stdout,
+                at(gx as u16, gy as u16),
+                SetForegroundColor(Color::DarkGrey),
+                Print('◌')
+            )?;
+        }
+    }
+    let air_y = (ground.y - view.height).max(0.0);
+    if let Some(cell) = camera.world_to_screen(ground.x, air_y) {
+        draw_ball(stdout, cell, style.ball, ball_glyph(game), graphics)?;
+    }
+
+    if game.can_shoot() || game.swing_active {
+        draw_golfer(stdout, game, camera)?;
+    }
+    if let Some(charge) = game.putt_charge {
+        draw_backswing(stdout, game, charge, camera)?;
+    }
+    if game.can_shoot() {
+        draw_relief_options(stdout, game, camera)?;
+        draw_fringe_preview(stdout, game, camera)?;
+        draw_aim_presets(stdout, game, camera)?;
+        draw_target(stdout, game, camera)?;
+        let aim_len = if game.on_green() { 9 } else { 6 };
+        if let Some(ghost) = game.caddie_aim {
+            for i in 2..=aim_len + 2 {
+                let gx = game.ball.x + ghost.cos() * i as f32;
+                let gy = game.ball.y + ghost.sin() * i as f32;
+                if let Some((sx, sy)) = camera.world_to_screen(gx, gy) {
+                    queue!(
+                        stdout,
+                        at(sx as u16, sy as u16),
+                        SetForegroundColor(Color::DarkGrey),
+                        Print('∙')
+                    )?;
+                }
+            }
+        }
+        for i in 1..=aim_len {
+            let ax = game.ball.x + game.angle.cos() * i as f32;
+            let ay = game.ball.y + game.angle.sin() * i as f32;
+            if let Some((sx, sy)) = camera.world_to_screen(ax, ay) {
+                queue!(
+                    stdout,
+                    at(sx as u16, sy as u16),
+                    SetForegroundColor(style.aim),
+                    Print('·')
+                )?;
+            }
+        }
+    }
+
+    if game.can_shoot() {
+        draw_hazard_warning(stdout, game, camera)?;
+    }
+
+    Ok(())
+}
+
+// Joins each trail point to the one before it, so a fast ball leaves an
+// unbroken line: box-drawing strokes along the ground, dots through the air.
+// As a sprite when the map is an image, or as a glyph.
+fn draw_ball(
+    stdout: &mut impl Write,
+    (x, y): (i32, i32),
+    color: Color,
+    glyph: char,
+    graphics: Option<&mut Graphics>,
+) -> std::io::Result<()> {
+    match graphics {
+        Some(view) => view.draw_ball(stdout, color_rgb(color), (x as u16, y as u16 + HEADER_ROWS)),
+        None => queue!(
+            stdout,
+            at(x as u16, y as u16),
+            SetForegroundColor(color),
+            Print(glyph)
+        ),
+    }
+}
+
+// The raster backends need actual colors; the named ones the balls use are
+// taken at their usual xterm values.
+fn color_rgb(color: Color) -> Rgb {
+    match color {
+        Color::Rgb { r, g, b } => (r, g, b),
+        Color::Magenta => (205, 0, 205),
+        Color::DarkGrey => (127, 127, 127),
+        _ => (229, 229, 229),
+    }
+}
+
+fn tile_rgb(game: &Game, camera: Camera, wx: i32, wy: i32) -> Rgb {
+    let tile = game.terrain.tile(wx, wy);
+    let mut color = raised(tile_color(game, tile.surface), tile.elevation);
+    if game.settings.slope_shading && tile.surface == Surface::Green {
+        color = hillshade(color, tile.slope);
+    }
+    let (r, g, b) = color_rgb(color);
+    match heat_color(game, wx, wy).filter(|_| heat_shown(game, camera)) {
+        // Half and half, so the ground still shows through.
+        Some(heat) => {
+            let (hr, hg, hb) = color_rgb(heat);
+            let mix = |a: u8, b: u8| ((a as u16 + b as u16) / 2) as u8;
+            (mix(r, hr), mix(g, hg), mix(b, hb))
+        }
+        None => (r, g, b),
+    }
+}
+
+// The computer's ball and its flight, in the rival's color and without a
+// golfer or aim line of its own.
+fn draw_ai_ball(
+    stdout: &mut impl Write,
+    ai: &Game,
+    camera: Camera,
+    alpha: f32,
+    graphics: Option<&mut Graphics>,
+) -> std::io::Result<()> {
+    let view = ai.ball_view(alpha);
+    if !ai.settings.reduced_motion {
+        draw_trail(stdout, ai, &trail_to(ai, view), camera)?;
+    }
+    if ai.hole_done {
+        return Ok(());
+    }
+    let ball = Vec2::new(view.ground.x, (view.ground.y - view.height).max(0.0));
+    if let Some(cell) = camera.world_to_screen(ball.x, ball.y) {
+        draw_ball(stdout, cell, RIVAL_STYLE.ball, '●', graphics)?;
+    }
+    Ok(())
+}
+
+// The trail is sampled as the ball goes; while it moves, the head runs on
+// to where the ball is drawn.
+fn trail_to(game: &Game, view: BallView) -> Vec<TrailPoint> {
+    let mut trail = game.trail.clone();
+    if game.in_motion() {
+        trail.push(TrailPoint {
+            pos: Vec2::new(view.ground.x, view.ground.y - view.height),
+            airborne: game.airborne.is_some(),
+            spun_back: trail.last().is_some_and(|p| p.spun_back),
+        });
+    }
+    trail
+}
+
+fn draw_trail(
+    stdout: &mut impl Write,
+    game: &Game,
+    trail: &[TrailPoint],
+    camera: Camera,
+) -> std::io::Result<()> {
+    let mut prev: Option<(TrailPoint, (i32, i32))> = None;
+    for (i, p) in trail.iter().enumerate() {
+        let Some(cell) = camera.world_to_screen(p.pos.x, p.pos.y) else {
+            prev = None;
+            continue;
+        };
+        let from = prev
+            .filter(|(before, _)| before.airborne == p.airborne)
+            .map_or(cell, |(_, at)| at);
+        let (dx, dy) = (cell.0 - from.0, cell.1 - from.1);
+        if (dx, dy) != (0, 0) || prev.is_none() {
+            let fade = i as f32 / (trail.len().max(1) as f32);
+            queue!(stdout, SetForegroundColor(trail_color(game, *p, fade)))?;
+            let glyph = trail_glyph(dx, dy, p.airborne);
+            for (x, y) in cells_between(from, cell) {
+                queue!(stdout, at(x as u16, y as u16), Print(glyph))?;
+            }
+        }
+        prev = Some((*p, cell));
+    }
+    Ok(())
+}
+
+// Terminal cells are about twice as tall as wide, so a step of one column
+// and one row is already steep.
+fn trail_glyph(dx: i32, dy: i32, airborne: bool) -> char {
+    let (ax, ay) = (dx.abs(), dy.abs());
+    if airborne {
+        '·'
+    } else if ax == 0 && ay == 0 {
+        '∙'
+    } else if ay * 3 <= ax {
+        '─'
+    } else if ax <= ay {
+        '│'
+    } else if (dx > 0) == (dy > 0) {
+        '╲'
+    } else {
+        '╱'
+    }
+}
+
+// Screen cells on the straight line from `from` to `to`, leaving out `from`
+// unless the two are the same cell.
+fn cells_between(from: (i32, i32), to: (i32, i32)) -> Vec<(i32, i32)> {
+    let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs());
+    if steps == 0 {
+        return vec![to];
+    }
+    (1..=steps)
+        .map(|s| {
+            let t = s as f32 / steps as f32;
+            (
+                from.0 + ((to.0 - from.0) as f32 * t).round() as i32,
+                from.1 + ((to.1 - from.1) as f32 * t).round() as i32,
+            )
+        })
+        .collect()
+}
+
+// The flight is drawn in sky blue; on the ground the trail takes a pale
+// tint of the surface it rolled over, dimmed through the rough.
+fn trail_color(game: &Game, point: TrailPoint, fade: f32) -> Color {
+    let (r, g, b) = if point.airborne {
+        (150.0, 200.0, 255.0)
+    } else if point.spun_back {
+        SPIN_RGB
+    } else {
+        match game.terrain.surface(point.pos.x as i32, point.pos.y as i32) {
+            Surface::Green => (200.0, 255.0, 200.0),
+            Surface::Fringe => (205.0, 245.0, 195.0),
+            Surface::Fairway => (215.0, 235.0, 190.0),
+            Surface::Rough => (120.0, 140.0, 110.0),
+            Surface::HeavyRough => (90.0, 110.0, 85.0),
+            Surface::Bunker => (240.0, 220.0, 160.0),
+            Surface::Water => (170.0, 210.0, 255.0),
+        }
+    };
+    let shade = 1.0 - fade * 0.6;
+    Color::Rgb {
+        r: (r * shade) as u8,
+        g: (g * shade) as u8,
+        b: (b * shade) as u8,
+    }
+}
+
+// Each spot the rules allow the ball to be played from is marked with the
+// number that picks it; the one in use sits under the ball.
+fn draw_relief_options(
+    stdout: &mut impl Write,
+    game: &Game,
+    camera: Camera,
+) -> std::io::Result<()> {
+    let Some(choice) = &game.relief else {
+        return Ok(());
+    };
+    for (i, option) in choice.options.iter().enumerate() {
+        if i == choice.chosen {
+            continue;
+        }
+        if let Some((sx, sy)) = camera.world_to_screen(option.spot.x, option.spot.y) {
+            queue!(
+                stdout,
+                at(sx as u16, sy as u16),
+                SetForegroundColor(Color::Yellow),
+                Print(i + 1)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+const SPIN_RGB: (f32, f32, f32) = (230.0, 140.0, 255.0);
+
+// While backspin has hold of the ball a curl sits on the far side of it, so
+// the check or the suck back reads even a cell at a time.
+fn draw_spin_check(stdout: &mut impl Write, game: &Game, camera: Camera) -> std::io::Result<()> {
+    let Some(check) = game.spin_check.filter(|c| c.gripping()) else {
+        return Ok(());
+    };
+    if game.settings.reduced_motion {
+        return Ok(());
+    }
+    let (x, y) = (game.ball.x + check.dir.x, game.ball.y + check.dir.y);
+    if let Some((sx, sy)) = camera.world_to_screen(x, y) {
+        let (r, g, b) = SPIN_RGB;
+        queue!(
+            stdout,
+            at(sx as u16, sy as u16),
+            SetForegroundColor(Color::Rgb {
+                r: r as u8,
+                g: g as u8,
+                b: b as u8
+            }),
+            Print('↺')
+        )?;
+    }
+    Ok(())
+}
+
+// A burst of stars rings the cup after a ball is jarred from the air.
+fn draw_celebration(
+    stdout: &mut impl Write,
+    game: &Game,
+    camera: Camera,
+    elapsed: f32,
+) -> std::io::Result<()> {
+    let progress = if !game.settings.ambient_motion() {
+        0.5
+    } else {
+        elapsed / CELEBRATION_SECS
+    };
+    let radius = 1.0 + progress * 3.0;
+    let colors = [Color::Yellow, Color::Magenta, Color::Cyan, Color::White];
+    for i in 0..12 {
+        let t = i as f32 / 12.0 * 2.0 * PI;
+        let x = game.hole.x + t.cos() * radius;
+        let y = game.hole.y + t.sin() * radius;
+        if let Some((sx, sy)) = camera.world_to_screen(x, y) {
+            let shift = if !game.settings.ambient_motion() {
+                0
+            } else {
+                (elapsed * 8.0) as usize
+            };
+            queue!(
+                stdout,
+                at(sx as u16, sy as u16),
+                SetForegroundColor(colors[(i + shift) % colors.len()]),
+                Print('✶')
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// Where the selected fringe option finishes on average, traced from the ball.
+fn draw_hazard_warning(
+    stdout: &mut impl Write,
+    game: &Game,
+    camera: Camera,
+) -> std::io::Result<()> {
+    let Some(warning) = game.hazard_warning else {
+        return Ok(());
+    };
+    let reach = 7.0 / camera.zoom;
+    let (x, y) = (
+        game.ball.x + game.angle.cos() * reach,
+        game.ball.y + game.angle.sin() * reach,
+    );
+    let Some((sx, sy)) = camera.world_to_screen(x, y) else {
+        return Ok(());
+    };
+    let label = format!("! {:.0}% {}", warning.share * 100.0, warning.hazard);
+    let sx = sx.min(WIDTH - label.len() as i32).max(0);
+    let color = if warning.share >= 0.5 {
+        Color::Red
+    } else {
+        Color::Yellow
+    };
+    queue!(
+        stdout,
+        at(sx as u16, sy as u16),
+        SetForegroundColor(color),
+        Print(label)
+    )
+}
+
+fn draw_fringe_preview(
+    stdout: &mut impl Write,
+    game: &Game,
+    camera: Camera,
+) -> std::io::Result<()> {
+    let Some(fringe) = game.fringe else {
+        return Ok(());
+    };
+    let (option, color) = if game.current_club().putter {
+        (fringe.putt, Color::Yellow)
+    } else {
+        (fringe.chip, Color::Cyan)
+    };
+    let (dx, dy) = (option.finish.x - game.ball.x, option.finish.y - game.ball.y);
+    let steps = (dx * dx + dy * dy).sqrt().ceil().max(1.0) as i32;
+    for i in 1..=steps {
+        let t = i as f32 / steps as f32;
+        if let Some((sx, sy)) = camera.world_to_screen(game.ball.x + dx * t, game.ball.y + dy * t) {
+            let ch = if i == steps { '◇' } else { '˙' };
+            queue!(
+                stdout,
+                at(sx as u16, sy as u16),
+                SetForegroundColor(color),
+                Print(ch)
+            )?;
+        }
+    }
+    Ok(())
+}
 
-        if let Some((gx, gy)) = world_to_screen(ground.x, ground.y, left, top, zoom) {
+fn draw_aim_presets(stdout: &mut impl Write, game: &Game, camera: Camera) -> std::io::Result<()> {
+    for (i, point) in game.aim_presets.iter().enumerate() {
+        if let Some((sx, sy)) = camera.world_to_screen(point.x, point.y) {
+            let color = if game.active_aim_preset() == Some(i) {
+                Color::Cyan
+            } else {
+                Color::DarkCyan
+            };
             queue!(
                 stdout,
-                MoveTo(gx as u16, gy as u16),
-                SetForegroundColor(Color::DarkGrey),
-                Print('◌')
+                at(sx as u16, sy as u16),
+                SetForegroundColor(color),
+                Print((b'A' + i as u8) as char)
             )?;
         }
+    }
+    Ok(())
+}
 
-        if let Some((ax, ay)) = world_to_screen(ground.x, air_y, left, top, zoom) {
+fn draw_target(stdout: &mut impl Write, game: &Game, camera: Camera) -> std::io::Result<()> {
+    let (Some(target), Some((side, short, long))) = (game.target_cursor, game.target_spread())
+    else {
+        return Ok(());
+    };
+    let (fx, fy) = (game.angle.cos(), game.angle.sin());
+    for i in 0..16 {
+        let t = i as f32 / 16.0 * 2.0 * PI;
+        let depth = if t.sin() >= 0.0 { long } else { short };
+        let across = side.max(0.5) * t.cos();
+        let along = depth.max(0.5) * t.sin();
+        let x = target.x + fx * along - fy * across;
+        let y = target.y + fy * along + fx * across;
+        if let Some((sx, sy)) = camera.world_to_screen(x, y) {
             queue!(
                 stdout,
-                MoveTo(ax as u16, ay as u16),
-                SetForegroundColor(Color::White),
-                Print('●')
+                at(sx as u16, sy as u16),
+                SetForegroundColor(Color::Cyan),
+                Print('∘')
             )?;
         }
-    } else if let Some((bx, by)) = world_to_screen(game.ball.x, game.ball.y, left, top, zoom) {
+    }
+    if let Some((sx, sy)) = camera.world_to_screen(target.x, target.y) {
         queue!(
             stdout,
-            MoveTo(bx as u16, by as u16),
-            SetForegroundColor(Color::White),
-            Print('●')
+            at(sx as u16, sy as u16),
+            SetForegroundColor(Color::Cyan),
+            Print('✛')
         )?;
     }
+    Ok(())
+}
 
-    if game.can_shoot() || game.swing_active {
-        draw_golfer(stdout, game, left, top, zoom)?;
-    }
-    if game.can_shoot() {
-        let aim_len = if game.on_green() { 9 } else { 6 };
-        for i in 1..=aim_len {
-            let ax = game.ball.x + game.angle.cos() * i as f32;
-            let ay = game.ball.y + game.angle.sin() * i as f32;
-            if let Some((sx, sy)) = world_to_screen(ax, ay, left, top, zoom) {
-                queue!(
-                    stdout,
-                    MoveTo(sx as u16, sy as u16),
-                    SetForegroundColor(Color::Yellow),
-                    Print('·')
-                )?;
-            }
+fn draw_backswing(
+    stdout: &mut impl Write,
+    game: &Game,
+    charge: f32,
+    camera: Camera,
+) -> std::io::Result<()> {
+    let steps = (charge * 4.0).ceil() as i32;
+    let color = if charge < 0.4 {
+        Color::Green
+    } else if charge < 0.75 {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+    for i in 1..=steps {
+        let bx = game.ball.x - game.angle.cos() * i as f32 * 0.6;
+        let by = game.ball.y - game.angle.sin() * i as f32 * 0.6;
+        if let Some((sx, sy)) = camera.world_to_screen(bx, by) {
+            queue!(
+                stdout,
+                at(sx as u16, sy as u16),
+                SetForegroundColor(color),
+                Print('═')
+            )?;
         }
     }
-
     Ok(())
 }
 
-fn draw_golfer(
-    stdout: &mut Stdout,
-    game: &Game,
-    left: i32,
-    top: i32,
-    zoom: i32,
-) -> std::io::Result<()> {
+fn draw_golfer(stdout: &mut impl Write, game: &Game, camera: Camera) -> std::io::Result<()> {
+    let (head, body, shirt) = match game.profile.equipped(Slot::Golfer).look {
+        Look::Golfer { head, body, shirt } => (head, body, rgb(shirt)),
+        _ => ('●', '█', Color::White),
+    };
     let back_x = game.golfer_anchor.x - game.angle.cos() * 1.6;
     let back_y = game.golfer_anchor.y - game.angle.sin() * 1.6;
 
-    if let Some((hx, hy)) = world_to_screen(back_x, back_y, left, top, zoom) {
+    if let Some((hx, hy)) = camera.world_to_screen(back_x, back_y) {
         queue!(
             stdout,
-            MoveTo(hx as u16, hy as u16),
+            at(hx as u16, hy as u16),
             SetForegroundColor(Color::Rgb {
                 r: 240,
                 g: 225,
                 b: 190
             }),
-            Print('●')
+            Print(head)
         )?;
     }
 
-    if let Some((bx, by)) = world_to_screen(back_x, back_y + 0.8, left, top, zoom) {
+    // The body a row under the head.
+    let body_y = back_y + 0.8 * CELL_ASPECT as f32;
+    if let Some((bx, by)) = camera.world_to_screen(back_x, body_y) {
         queue!(
             stdout,
-            MoveTo(bx as u16, by as u16),
-            SetForegroundColor(Color::White),
-            Print('█')
+            at(bx as u16, by as u16),
+            SetForegroundColor(shirt),
+            Print(body)
         )?;
     }
 
     // Methodical swing path: backswing -> downswing -> follow-through.
     let phase_offsets = [1.45_f32, 1.05, 0.6, 0.2, -0.35, -0.8];
     let frame = game.swing_frame.min(phase_offsets.len() - 1);
-    let club_angle = game.angle + phase_offsets[frame];
+    // A left-hander stands on the other side and swings the other way.
+    let (side, shaft) = if game.settings.left_handed {
+        (-1.0, '\\')
+    } else {
+        (1.0, '/')
+    };
+    let club_angle = game.angle + side * phase_offsets[frame];
     let shaft_dx = club_angle.cos();
     let shaft_dy = club_angle.sin();
 
     let arm_x = back_x + game.angle.cos() * 0.45;
     let arm_y = back_y + game.angle.sin() * 0.45;
-    if let Some((cx, cy)) = world_to_screen(arm_x + shaft_dx, arm_y + shaft_dy, left, top, zoom) {
+    if let Some((cx, cy)) = camera.world_to_screen(arm_x + shaft_dx, arm_y + shaft_dy) {
         queue!(
             stdout,
-            MoveTo(cx as u16, cy as u16),
+            at(cx as u16, cy as u16),
             SetForegroundColor(Color::DarkGrey),
-            Print('/')
+            Print(shaft)
         )?;
     }
 
-    if let Some((cx2, cy2)) = world_to_screen(
-        arm_x + shaft_dx * 1.8,
-        arm_y + shaft_dy * 1.8,
-        left,
-        top,
-        zoom,
-    ) {
+    if let Some((cx2, cy2)) = camera.world_to_screen(arm_x + shaft_dx * 1.8, arm_y + shaft_dy * 1.8)
+    {
         queue!(
             stdout,
-            MoveTo(cx2 as u16, cy2 as u16),
+            at(cx2 as u16, cy2 as u16),
             SetForegroundColor(Color::Grey),
             Print('─')
         )?;
@@ -229,23 +2387,7 @@ fn draw_golfer(
     Ok(())
 }
 
-fn world_to_screen(wx: f32, wy: f32, left: i32, top: i32, zoom: i32) -> Option<(i32, i32)> {
-    let lx = wx - left as f32;
-    let ly = wy - top as f32;
-    if lx < 0.0 || ly < 0.0 {
-        return None;
-    }
-
-    let sx = (lx * zoom as f32).round() as i32;
-    let sy = (ly * zoom as f32).round() as i32;
-    if sx < 0 || sy < 0 || sx >= WIDTH || sy >= HEIGHT {
-        None
-    } else {
-        Some((sx, sy))
-    }
-}
-
-fn draw_hud(stdout: &mut Stdout, game: &Game) -> std::io::Result<()> {
+fn draw_hud(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
     let panel_x = WIDTH as u16 + 2;
 
     let score = game.strokes as i32 - game.par as i32;
@@ -258,8 +2400,14 @@ fn draw_hud(stdout: &mut Stdout, game: &Game) -> std::io::Result<()> {
     };
 
     let angle_deg = (game.angle * 180.0 / PI) as i32;
-    let status = if game.hole_done {
+    let status = if game.replay.is_some() {
+        "REPLAY"
+    } else if game.hole_done {
         "SUNK"
+    } else if game.pressure.is_some() {
+        "PRESSURE PUTT"
+    } else if game.putt_charge.is_some() {
+        "PULLING BACK"
     } else if game.airborne.is_some() {
         "BALL IN AIR"
     } else if game.rolling {
@@ -281,19 +2429,162 @@ fn draw_hud(stdout: &mut Stdout, game: &Game) -> std::io::Result<()> {
         "Controls:".to_string(),
         "A/D or <-/-> : Aim (360)".to_string(),
         "W/S or ^/v    : Club +/-".to_string(),
-        "E             : Swing Type".to_string(),
+        "E / Shift+E   : Swing Type/Shape".to_string(),
         "C             : Auto Caddie".to_string(),
+        "F             : Take Caddie Aim".to_string(),
+        "X             : Target Mode".to_string(),
+        "B / J         : Bank/Next Aim".to_string(),
+        "Shift+B       : Low-bandwidth".to_string(),
+        "O             : Putt/Chip Preview".to_string(),
         "Space/Enter   : Hit".to_string(),
+        "L             : Wind On Putts".to_string(),
+        "Y             : Pressure Putts".to_string(),
+        "P             : Pull-back Putts".to_string(),
+        "T             : Theme".to_string(),
+        "Tab           : HUD Layout".to_string(),
+        ".             : Green Slope Shading".to_string(),
+        "Z             : Zoom Transition".to_string(),
+        "G / H         : Stats/Help".to_string(),
+        "K             : Conditions".to_string(),
+        "U / Shift+U   : Challenge/Unplayable".to_string(),
+        "V / M         : Contrast/Motion".to_string(),
         "R             : Restart".to_string(),
+        "N             : New Hole".to_string(),
         "Q/Esc         : Quit".to_string(),
         "".to_string(),
         format!("Strokes: {}", game.strokes),
+        if game.penalty_strokes > 0 {
+            format!("Penalties: +{}", game.penalty_strokes)
+        } else {
+            String::new()
+        },
+        game.penalty_drop
+            .filter(|_| game.can_shoot())
+            .map(|penalty| penalty.announcement())
+            .unwrap_or_default(),
+        round_total_label(game),
+        match_label(game),
+        ai_label(game),
+        foursomes_label(game),
+        tournament_label(game),
+        ghost_label(game),
+        heat_map_label(game),
+        if game.settings.energy_audit {
+            format!("Energy audit: {} gains", game.energy_imbalances)
+        } else {
+            String::new()
+        },
+        game.auto_advance_in()
+            .map(|secs| {
+                let next = if last_course_hole(game) {
+                    "Final card"
+                } else {
+                    "Next tee"
+                };
+                format!("{} in {:.0}s (N: now)", next, secs.ceil())
+            })
+            .unwrap_or_default(),
         format!("Par: {} ({})", game.par, score_label),
-        format!("Distance: {:.0} yd", game.distance_to_hole_yd()),
-        format!("Lie: {}", game.current_surface().name()),
+        game.hole_key()
+            .and_then(|hole| game.profile.best_holes.get(&hole))
+            .map(|best| format!("Your best: {}", best))
+            .unwrap_or_default(),
+        format!(
+            "Hole: {}",
+            game.settings.units.short(game.layout.length_yd())
+        ),
+        match (&game.warm_up, &game.course_round) {
+            (Some(warm_up), _) => format!(
+                "Range: ball {}/{} (N: first tee)",
+                (warm_up.balls_hit + 1).min(WARM_UP_BALLS),
+                WARM_UP_BALLS
+            ),
+            (None, Some(round)) => format!(
+                "Hole {}/{}: {}",
+                round.position + 1,
+                round.order.len(),
+                round.current().name
+            ),
+            (None, None) => String::new(),
+        },
+        game.hole_note()
+            .map(|note| format!("Your note: {}", note))
+            .unwrap_or_default(),
+        game.day_form
+            .map(|form| format!("Form: {}", form.label()))
+            .unwrap_or_default(),
+        format!("Design: {}", game.layout.template.name()),
+        game.difficulty
+            .map(|d| {
+                format!(
+                    "Difficulty: {} (bot avg {:.1})",
+                    d.rating(),
+                    d.average_strokes
+                )
+            })
+            .unwrap_or_default(),
+        if game.settings.challenge == game.challenge {
+            format!("Challenge: {}", game.challenge.name())
+        } else {
+            format!(
+                "Challenge: {} (next: {})",
+                game.challenge.name(),
+                game.settings.challenge.name()
+            )
+        },
+        game.bag
+            .rule_label()
+            .map(|rule| format!("Bag: {}", rule))
+            .unwrap_or_default(),
+        format!(
+            "Conditions: {} (next: {})",
+            game.conditions.badge(),
+            game.settings
+                .conditions
+                .map(|preset| preset.name())
+                .unwrap_or("Random")
+        ),
+        format!(
+            "Greens: stimp {:.1}, firm {:.0}%",
+            game.layout.green.stimp,
+            game.layout.green.firmness * 100.0
+        ),
+        format!("Course: {}", game.settings.course_conditions.describe()),
+        format!(
+            "Contour: {}",
+            game.layout.contour.describe().unwrap_or("flat")
+        ),
+        format!(
+            "Distance: {}",
+            game.settings.units.short(game.distance_to_hole_yd())
+        ),
+        format!("Lie: {}", game.lie_name()),
+        game.stance()
+            .and_then(|stance| stance.warning())
+            .unwrap_or_default()
+            .to_string(),
+        relief_label(game),
         format!("Club: {}", club.name),
         format!("Shot: {}", game.selected_shot.name()),
-        format!("Play: {:.0} yd", game.selected_shot_distance_yd()),
+        format!("Shape: {} (Shift+E)", game.selected_shape.name()),
+        format!(
+            "Play: {}",
+            game.settings.units.short(game.selected_shot_distance_yd())
+        ),
+        caddie_plan_label(game),
+        game.target_distance_yd()
+            .map(|yd| {
+                format!(
+                    "Target: {} (arrows move, X exits)",
+                    game.settings.units.short(yd)
+                )
+            })
+            .unwrap_or_default(),
+        aim_preset_label(game),
+        fringe_label(game, true),
+        fringe_label(game, false),
+        caddie_option_label(game, false),
+        caddie_option_label(game, true),
         format!(
             "Caddie: {}",
             if game.auto_caddie { "AUTO" } else { "MANUAL" }
@@ -301,45 +2592,708 @@ fn draw_hud(stdout: &mut Stdout, game: &Game) -> std::io::Result<()> {
         format!("Aim: {:+} deg", angle_deg),
         format!("Cup Dir: {:+.0} deg", to_hole_deg),
         format!("Aim Err: {:+.0} deg", putt_hint),
-        format!("Wind: {:+.1} mph", game.wind * 12.0),
+        game.caddie_aim_offset_deg()
+            .map(|deg| format!("Caddie Aim: {:+.0} deg (F)", deg))
+            .unwrap_or_default(),
+        wind_label(game),
+        putt_wind_label(game),
         format!(
             "View: {}",
-            if game.on_green() {
+            if game.view_zoomed {
                 "GREEN ZOOM"
             } else {
                 "FULL HOLE"
             }
         ),
         format!("Status: {}", status),
+        format!("Theme: {}", game.settings.theme.name()),
+        format!("HUD: {} (Tab)", game.settings.hud.name()),
+        format!(
+            "Slope shading: {} (.)",
+            if game.settings.slope_shading {
+                "ON"
+            } else {
+                "OFF"
+            }
+        ),
+        format!(
+            "Distance arcs: {} (,)",
+            if game.settings.distance_arcs {
+                "ON"
+            } else {
+                "OFF"
+            }
+        ),
+        format!(
+            "Heat map: {} (Shift+H)",
+            if game.settings.heat_map { "ON" } else { "OFF" }
+        ),
+        format!(
+            "Zoom: {}",
+            if game.settings.instant_camera {
+                "INSTANT"
+            } else {
+                "ANIMATED"
+            }
+        ),
+        format!(
+            "Access: contrast {}, motion {}",
+            if game.settings.high_contrast {
+                "HIGH"
+            } else {
+                "NORMAL"
+            },
+            if game.settings.reduced_motion {
+                "REDUCED"
+            } else {
+                "FULL"
+            }
+        ),
+        game.layout
+            .green
+            .caddie_note()
+            .map(|note| format!("Caddie: \"{}\"", note))
+            .unwrap_or_default(),
+        game.presentation
+            .as_ref()
+            .map(|p| format!("Result: {} (any key)", p.summary))
+            .unwrap_or_default(),
+        queued_input_label(game),
+        strike_label(game),
+        game.hazard_warning
+            .map(|w| {
+                format!(
+                    "Hazard: {:.0}% of landings in {}",
+                    w.share * 100.0,
+                    w.hazard
+                )
+            })
+            .unwrap_or_default(),
+        pressure_label(game),
+        pull_back_label(game),
     ];
 
+    if game.settings.high_contrast {
+        queue!(stdout, SetBackgroundColor(Color::Black))?;
+    }
     for (i, line) in lines.iter().enumerate() {
         queue!(
             stdout,
-            MoveTo(panel_x, i as u16),
-            SetForegroundColor(Color::Cyan),
+            at(panel_x, i as u16),
+            SetForegroundColor(hud_color(game)),
             Print(line)
         )?;
     }
+    // The high-contrast background stops with the panel.
+    queue!(stdout, ResetColor)?;
 
     if game.hole_done {
-        let msg = if game.strokes == 1 {
-            "Hole in one! Press R"
-        } else {
-            "Hole complete. Press R"
+        let result = game.match_play.as_ref().and_then(|m| m.last);
+        let msg = match (game.strokes == 1, game.jarred) {
+            _ if result.is_some() => result.map_or("", |r| r.describe()),
+            (true, true) => "Hole in one, straight in! Press R",
+            (true, false) => "Hole in one! Press R",
+            (false, true) => "Jarred it from the air! Press R",
+            (false, false) if last_course_hole(game) => "Round complete! N: final card",
+            (false, false) => "Hole complete. Press R",
         };
 
         queue!(
             stdout,
-            MoveTo(panel_x, 24),
+            at(panel_x, lines.len() as u16 + 1),
             SetForegroundColor(Color::Green),
             Print(msg)
         )?;
+
+        for (i, shot) in game.shot_history.iter().enumerate() {
+            queue!(
+                stdout,
+                at(panel_x, lines.len() as u16 + 2 + i as u16),
+                SetForegroundColor(Color::Magenta),
+                Print(format!(
+                    "{}. {} from {} ({})",
+                    i + 1,
+                    shot.club,
+                    lie_name(shot.lie, shot.buried),
+                    game.settings.units.short(shot.to_pin_yd)
+                ))
+            )?;
+        }
+        if game.payout > 0 {
+            queue!(
+                stdout,
+                at(
+                    panel_x,
+                    lines.len() as u16 + 3 + game.shot_history.len() as u16
+                ),
+                SetForegroundColor(Color::Yellow),
+                Print(format!("+${} career earnings (G: pro shop)", game.payout))
+            )?;
+        }
+        // A course round saves its statistics and titles for the final card.
+        if game.course_round.is_none() && game.warm_up.is_none() {
+            let tally = &game.round_tally;
+            queue!(
+                stdout,
+                at(
+                    panel_x,
+                    lines.len() as u16 + 4 + game.shot_history.len() as u16
+                ),
+                SetForegroundColor(Color::White),
+                Print(format!(
+                    "FIR {}  GIR {}  Putts {}  Sand {}  U&D {}",
+                    out_of(tally.fairways_hit, tally.fairway_chances),
+                    out_of(tally.greens_in_regulation, tally.holes),
+                    tally.putts,
+                    out_of(tally.sand_saves, tally.sand_chances),
+                    out_of(tally.up_and_downs, tally.up_and_down_chances)
+                ))
+            )?;
+        }
+        if game.course_round.is_none() && !game.round_awards.is_empty() {
+            queue!(
+                stdout,
+                at(
+                    panel_x,
+                    lines.len() as u16 + 5 + game.shot_history.len() as u16
+                ),
+                SetForegroundColor(Color::Magenta),
+                Print(format!("Earned: {}", award_titles(&game.round_awards)))
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn draw_speed_round_hud(stdout: &mut impl Write, round: &SpeedRound) -> std::io::Result<()> {
+    let panel_x = WIDTH as u16 + 2;
+    let mut lines = vec![
+        ("SPEED ROUND".to_string(), Color::Cyan),
+        ("-----------".to_string(), Color::Cyan),
+        ("First to hole out wins".to_string(), Color::Cyan),
+        ("".to_string(), Color::Cyan),
+    ];
+
+    let clusters = [
+        "A/D aim  W/S club  E swing  Space hit",
+        "<-/-> aim  ^/v club  / swing  Enter hit",
+    ];
+    for (i, player) in round.players.iter().enumerate() {
+        let style = if i == 0 { PLAYER_STYLE } else { RIVAL_STYLE };
+        let status = if player.hole_done {
+            "HOLED"
+        } else if player.is_afk() {
+            if player.settings.afk_autoplay {
+                "AFK, caddie steps in soon"
+            } else {
+                "AFK"
+            }
+        } else if player.ball_in_motion() {
+            "moving"
+        } else {
+            "ready"
+        };
+        if i == 1 && player.shadow.is_some() {
+            lines.push((format!("Shadow rival ({})", status), style.ball));
+            lines.push(("Plays like you, from your stats".to_string(), style.ball));
+        } else {
+            lines.push((format!("P{} ({})", i + 1, status), style.ball));
+            lines.push((clusters[i].to_string(), style.ball));
+        }
+        lines.push((
+            format!(
+                "Strokes {}  {}  {} {}",
+                player.strokes,
+                player.settings.units.short(player.distance_to_hole_yd()),
+                player.current_club().name,
+                player.selected_shot.name()
+            ),
+            style.ball,
+        ));
+        lines.push(("".to_string(), Color::Cyan));
+    }
+
+    lines.push((
+        "R: Rematch  N: New Hole  Esc: Quit".to_string(),
+        Color::Cyan,
+    ));
+    if round.result.is_some() {
+        lines.push((
+            format!(
+                "M: Match summary ({})",
+                standing_text(round.log.standing, round.names())
+            ),
+            Color::Cyan,
+        ));
+    }
+    match round.result {
+        Some(SpeedResult::Winner(i)) => {
+            lines.push((format!("P{} wins the race!", i + 1), Color::Green));
+        }
+        Some(SpeedResult::Tie) => lines.push(("Dead heat!".to_string(), Color::Green)),
+        None => {}
+    }
+
+    for (i, (line, color)) in lines.iter().enumerate() {
+        queue!(
+            stdout,
+            at(panel_x, i as u16),
+            SetForegroundColor(*color),
+            Print(line)
+        )?;
+    }
+    Ok(())
+}
+
+// Strokes so far over the course round, counting the hole in play, with
+// the score against par over the holes already finished.
+fn round_total_label(game: &Game) -> String {
+    let Some(round) = &game.course_round else {
+        return String::new();
+    };
+    let (strokes, par) = round.card.total();
+    let holes = round.card.holes_played();
+    let in_play = if game.hole_done { 0 } else { game.strokes };
+    format!(
+        "Round: {} ({} thru {})",
+        strokes + in_play,
+        to_par_label(strokes as i32 - par as i32),
+        holes
+    )
+}
+
+// "Terminal Golf — Hole 7, +2" for the tab or window, and a progress bar
+// while the player waits on a replay, the computer playing on, or the walk
+// to the next tee.
+pub fn terminal_status(game: &Game) -> (String, Option<Progress>) {
+    let place = match (&game.warm_up, &game.course_round) {
+        (Some(_), _) => "Driving range".to_string(),
+        (None, Some(round)) => {
+            let (strokes, par) = round.card.total();
+            format!(
+                "Hole {}, {}",
+                round.current().number,
+                to_par_label(strokes as i32 - par as i32)
+            )
+        }
+        (None, None) => format!("Par {}", game.par),
+    };
+    let progress = if let Some(replay) = &game.replay {
+        Some(percent(replay.progress()))
+    } else if game.hole_done && game.ai_to_play() {
+        Some(Progress::Busy)
+    } else {
+        game.auto_advance_in()
+            .map(|secs| percent(1.0 - secs / AUTO_ADVANCE_SECS))
+    };
+    (format!("Terminal Golf — {}", place), progress)
+}
+
+pub fn speed_round_title(round: &SpeedRound) -> String {
+    format!("Terminal Golf — Speed round, Par {}", round.players[0].par)
+}
+
+fn percent(fraction: f32) -> Progress {
+    Progress::Percent((fraction.clamp(0.0, 1.0) * 100.0).round() as u8)
+}
+
+// The match standing, and the rival's score on the hole once it's in.
+fn match_label(game: &Game) -> String {
+    let Some(m) = &game.match_play else {
+        return String::new();
+    };
+    match m.rival {
+        _ if m.is_over() => format!("Match: {}", m.status()),
+        Some(rival) => format!("{} | Rival: {}", m.status(), rival),
+        None if m.rival_first() => m.status(),
+        None => format!("{} | Rival next", m.status()),
+    }
+}
+
+// Where the computer opponent stands on the hole.
+fn ai_label(game: &Game) -> String {
+    let Some(ai) = &game.ai else {
+        return String::new();
+    };
+    let name = format!("CPU ({})", ai.skill.name);
+    if ai.game.hole_done {
+        format!("{}: in for {}", name, ai.game.strokes)
+    } else if ai.picked_up() {
+        format!("{}: picked up", name)
+    } else if game.ai_to_play() {
+        format!("{}: {} | to play", name, ai.game.strokes)
+    } else {
+        format!(
+            "{}: {} | {}",
+            name,
+            ai.game.strokes,
+            game.settings.units.short(ai.game.distance_to_hole_yd())
+        )
+    }
+}
+
+// Whose turn it is with the shared ball, or once the hole is done how the
+// round's shots have split and who drives the next.
+fn foursomes_label(game: &Game) -> String {
+    let (Some(foursomes), Some(partner)) = (&game.foursomes, game.partner_to_play()) else {
+        return String::new();
+    };
+    if game.hole_done {
+        let [first, second] = foursomes.shots;
+        format!(
+            "Foursomes: {}-{} shots | {} drives next",
+            first,
+            second,
+            PARTNERS[foursomes.on_tee()]
+        )
+    } else if game.strokes == game.penalty_strokes {
+        format!("Foursomes: {} on the tee", PARTNERS[partner])
+    } else {
+        format!("Foursomes: {} to play", PARTNERS[partner])
     }
+}
+
+// The round in play and where the player stood on the board after the last.
+fn tournament_label(game: &Game) -> String {
+    let Some(tournament) = &game.tournament else {
+        return String::new();
+    };
+    let played = tournament.rounds_played();
+    let round = format!("Round {}/{}", played + 1, TOURNAMENT_ROUNDS);
+    match tournament.player_place() {
+        _ if tournament.player().missed_cut => "Tournament: missed the cut".to_string(),
+        place if tournament.is_over() => format!("Tournament: finished {}", place),
+        _ if played == 0 => round,
+        place => format!("{} | lying {}", round, place),
+    }
+}
+
+// The ghost's score and time on the hole, once it's holed out.
+fn ghost_label(game: &Game) -> String {
+    match game.ghost_hole() {
+        Some((hole, secs)) if secs >= hole.finish_secs() => format!(
+            "Ghost: holed in {} at {}",
+            hole.strokes,
+            format_duration(hole.finish_secs() as f64)
+        ),
+        Some(_) => "Ghost: still playing".to_string(),
+        None => String::new(),
+    }
+}
+
+// The expected score from the ball and the range across the hole, while the
+// heat map is on.
+fn heat_map_label(game: &Game) -> String {
+    let Some(map) = game.heat_map.as_ref().filter(|_| game.settings.heat_map) else {
+        return String::new();
+    };
+    let range = format!("{:.1}-{:.1}", map.best, map.worst);
+    match map.strokes_at(game.ball.x as i32, game.ball.y as i32) {
+        Some(here) => format!("Expected: {:.1} here ({})", here, range),
+        None => format!("Expected: {}", range),
+    }
+}
+
+fn award_titles(awards: &[&Award]) -> String {
+    awards
+        .iter()
+        .map(|award| award.title)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn last_course_hole(game: &Game) -> bool {
+    game.warm_up.is_none()
+        && game
+            .course_round
+            .as_ref()
+            .is_some_and(|round| round.is_last_hole())
+}
+
+fn to_par_label(diff: i32) -> String {
+    match diff {
+        0 => "E".to_string(),
+        diff => format!("{:+}", diff),
+    }
+}
+
+// Hole, score, distance, club and wind on one line under the map, the way a
+// TV graphic would show them.
+fn draw_broadcast_bar(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let hole = match (&game.warm_up, &game.course_round) {
+        (Some(_), _) => "Range".to_string(),
+        (None, Some(round)) => format!("Hole {} Par {}", round.position + 1, game.par),
+        (None, None) => format!("Par {}", game.par),
+    };
+    let mut score = format!(
+        "{} ({})",
+        game.strokes,
+        to_par_label(game.strokes as i32 - game.par as i32)
+    );
+    if let Some(round) = &game.course_round {
+        let (strokes, par) = round.card.total();
+        let holes = round.card.holes_played();
+        score.push_str(&format!(
+            ", Rd {} thru {}",
+            to_par_label(strokes as i32 - par as i32),
+            holes
+        ));
+    }
+    let bar = [
+        hole,
+        score,
+        game.settings.units.short(game.distance_to_hole_yd()),
+        game.current_club().name.to_string(),
+        format!("{} {:.0} mph", wind_compass(game), game.wind.mph()),
+    ]
+    .join(" │ ");
+
+    let width = terminal::size().map_or(WIDTH as u16, |(cols, _)| cols) as usize;
+    if game.settings.high_contrast {
+        queue!(stdout, SetBackgroundColor(Color::Black))?;
+    }
+    queue!(
+        stdout,
+        at(0, HEIGHT as u16),
+        SetForegroundColor(hud_color(game)),
+        Print(bar.chars().take(width).collect::<String>()),
+        ResetColor
+    )?;
+    Ok(())
+}
 
+// Under the map on a course round, once a hole is in: how many are done, the
+// average time they took and when the round should finish at that pace.
+fn draw_pace_footer(stdout: &mut impl Write, game: &Game) -> std::io::Result<()> {
+    let Some(round) = &game.course_round else {
+        return Ok(());
+    };
+    let Some(pace) = round.pace() else {
+        return Ok(());
+    };
+    let line = format!(
+        "Pace: {}/{} holes · {:.1} min/hole · round {}",
+        pace.holes_done,
+        round.order.len(),
+        pace.secs_per_hole / 60.0,
+        format_duration(pace.projected_secs as f64)
+    );
+    queue!(
+        stdout,
+        at(0, HEIGHT as u16),
+        SetForegroundColor(hud_color(game)),
+        Print(line)
+    )?;
     Ok(())
 }
 
+// A compass arrow for where the wind is blowing and its strength over the
+// last half minute, on a scale up to the day's strongest wind.
+fn wind_label(game: &Game) -> String {
+    let gusts = chart::sparkline_to(
+        &game.wind_history.speeds_mph(),
+        game.conditions.max_wind() * 12.0,
+    );
+    format!(
+        "Wind: {} {:.1} mph {}",
+        wind_compass(game),
+        game.wind.mph(),
+        gusts
+    )
+}
+
+fn wind_compass(game: &Game) -> char {
+    if game.wind.mph() < 0.5 {
+        '○'
+    } else {
+        let wind = game.wind.vector();
+        slope_arrow(wind.x, wind.y)
+    }
+}
+
+fn putt_wind_label(game: &Game) -> String {
+    if !game.settings.wind_on_putts {
+        return "Putt Wind: OFF".to_string();
+    }
+
+    let drift = game.rolling_wind_accel(Surface::Green);
+    let label = if drift.length() < 0.001 {
+        "calm".to_string()
+    } else {
+        format!("drift {}", slope_arrow(drift.x, drift.y))
+    };
+    format!(
+        "Putt Wind: ON ({}, {:.0}% exposed)",
+        label,
+        game.layout.green.exposure * 100.0
+    )
+}
+
+fn caddie_plan_label(game: &Game) -> String {
+    match game.shots_to_green() {
+        0 => "Plan: putt".to_string(),
+        1 => "Plan: go for green".to_string(),
+        n => format!(
+            "Plan: {} shots, aim {}",
+            n,
+            game.settings.units.short(game.caddie_target_yd())
+        ),
+    }
+}
+
+fn relief_label(game: &Game) -> String {
+    if let Some(choice) = &game.relief {
+        let drop = choice.chosen();
+        format!(
+            "Relief {}: {} (+{}), 1-{} to change",
+            choice.chosen + 1,
+            drop.relief.name(),
+            drop.penalty,
+            choice.options.len()
+        )
+    } else if game.buried && game.current_surface() == Surface::Bunker {
+        "Buried: no relief in a bunker, play it as it lies".to_string()
+    } else {
+        String::new()
+    }
+}
+
+fn fringe_label(game: &Game, putt: bool) -> String {
+    let Some(fringe) = game.fringe else {
+        return String::new();
+    };
+    let option = if putt { fringe.putt } else { fringe.chip };
+    let marker = if game.current_club().putter == putt {
+        '>'
+    } else {
+        ' '
+    };
+    let name = if putt {
+        "Putt".to_string()
+    } else {
+        format!("{} {}", CLUBS[option.club_idx].name, option.shot.name())
+    };
+    format!(
+        "{}{}: {:.0}% in, leaves {:.0} ft (O)",
+        marker,
+        name,
+        option.make_rate * 100.0,
+        option.leave_ft
+    )
+}
+
+fn aim_preset_label(game: &Game) -> String {
+    match game.active_aim_preset() {
+        Some(i) => format!(
+            "Aim Presets: {}/{} on {}",
+            game.aim_presets.len(),
+            AIM_PRESET_LIMIT,
+            (b'A' + i as u8) as char
+        ),
+        None => String::new(),
+    }
+}
+
+fn caddie_option_label(game: &Game, alternative: bool) -> String {
+    let Some(advice) = game.caddie_advice else {
+        return String::new();
+    };
+    let (prefix, option) = if alternative {
+        ("Alt ", advice.alternative)
+    } else {
+        ("Pick", Some(advice.primary))
+    };
+    let target = if advice.going_for_green {
+        "green"
+    } else {
+        "short grass"
+    };
+    option
+        .map(|o| {
+            format!(
+                "{}: {} {} {:.0}% {}",
+                prefix,
+                CLUBS[o.club_idx].name,
+                o.shot.name(),
+                o.chance * 100.0,
+                target
+            )
+        })
+        .unwrap_or_default()
+}
+
+fn pull_back_label(game: &Game) -> String {
+    if !game.settings.pull_back_putting {
+        return "".to_string();
+    }
+    match game.putt_charge {
+        Some(charge) => format!(
+            "Power: {:.0}% ({}) release",
+            charge * 100.0,
+            game.settings.units.short(game.selected_shot_distance_yd())
+        ),
+        None => "Putting: hold Space to pull back".to_string(),
+    }
+}
+
+fn strike_label(game: &Game) -> String {
+    match game.strike_quality {
+        Some(strike) if game.ball_in_motion() || game.presentation.is_some() => {
+            format!("Strike: {}", strike.name().to_uppercase())
+        }
+        _ => String::new(),
+    }
+}
+
+fn pressure_label(game: &Game) -> String {
+    if let Some(meter) = game.pressure {
+        let width = 17;
+        let marker = (meter.position() * (width - 1) as f32).round() as usize;
+        let sweet = (SWEET_SPOT * (width - 1) as f32).round() as usize;
+        let center = width / 2;
+        let bar: String = (0..width)
+            .map(|i| {
+                if i == marker {
+                    '^'
+                } else if i + sweet >= center && i <= center + sweet {
+                    '='
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        return format!("Steady: [{}] Space", bar);
+    }
+
+    match game.pressure_result {
+        Some(result) => format!("Pressure: {}", result.name()),
+        None if !game.settings.pressure_putts => "Pressure: OFF".to_string(),
+        None => "".to_string(),
+    }
+}
+
+fn queued_input_label(game: &Game) -> String {
+    if !game.has_queued_input() {
+        return "".to_string();
+    }
+
+    let mut parts = Vec::new();
+    if game.queued_aim_steps != 0 {
+        parts.push(format!("aim {:+}", game.queued_aim_steps));
+    }
+    if game.queued_club_steps != 0 {
+        parts.push(format!("club {:+}", game.queued_club_steps));
+    }
+    if game.queued_shot_cycles > 0 {
+        parts.push(format!("swing x{}", game.queued_shot_cycles));
+    }
+    if game.queued_shape_cycles > 0 {
+        parts.push(format!("shape x{}", game.queued_shape_cycles));
+    }
+    format!("Queued: {}", parts.join(", "))
+}
+
 fn normalize_angle_deg(mut angle: f32) -> f32 {
     while angle <= -180.0 {
         angle += 360.0;
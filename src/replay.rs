@@ -0,0 +1,108 @@
+use std::collections::VecDeque;
+
+use crate::game::Vec2;
+
+// Seconds of ball movement kept for a replay.
+const REPLAY_WINDOW_SECS: f32 = 2.0;
+pub const REPLAY_SLOWDOWN: f32 = 3.0;
+// The ball sits in the cup for a beat before the replay ends.
+const REPLAY_HOLD_SECS: f32 = 0.3;
+
+#[derive(Clone, Copy)]
+pub struct ReplayFrame {
+    pub ball: Vec2,
+    pub height: f32,
+    pub dt: f32,
+}
+
+// The last couple of seconds of the ball in motion, one frame per tick.
+#[derive(Default)]
+pub struct ReplayBuffer {
+    frames: VecDeque<ReplayFrame>,
+    window: f32,
+}
+
+impl ReplayBuffer {
+    pub fn push(&mut self, frame: ReplayFrame) {
+        self.window += frame.dt;
+        self.frames.push_back(frame);
+        while self.window > REPLAY_WINDOW_SECS {
+            match self.frames.pop_front() {
+                Some(old) => self.window -= old.dt,
+                None => break,
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.window = 0.0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // Hands the recorded frames over to a replay that finishes at `rest`.
+    pub fn take_replay(&mut self, rest: Vec2, jarred: bool) -> Replay {
+        let frames: Vec<ReplayFrame> = self.frames.drain(..).collect();
+        self.window = 0.0;
+        Replay::ending_at(frames, rest, jarred)
+    }
+
+    // A copy of what has been recorded so far, holding wherever the ball got to.
+    pub fn snapshot(&self, jarred: bool) -> Option<Replay> {
+        let rest = self.frames.back()?.ball;
+        Some(Replay::ending_at(
+            self.frames.iter().copied().collect(),
+            rest,
+            jarred,
+        ))
+    }
+}
+
+#[derive(Clone)]
+pub struct Replay {
+    pub frames: Vec<ReplayFrame>,
+    pub elapsed: f32,
+    pub jarred: bool,
+}
+
+impl Replay {
+    fn ending_at(mut frames: Vec<ReplayFrame>, rest: Vec2, jarred: bool) -> Self {
+        frames.push(ReplayFrame {
+            ball: rest,
+            height: 0.0,
+            dt: REPLAY_HOLD_SECS,
+        });
+        Self {
+            frames,
+            elapsed: 0.0,
+            jarred,
+        }
+    }
+
+    // Frames up to and including the one on screen now.
+    pub fn shown(&self) -> &[ReplayFrame] {
+        let mut clock = self.elapsed / REPLAY_SLOWDOWN;
+        let count = self
+            .frames
+            .iter()
+            .take_while(|frame| {
+                clock -= frame.dt;
+                clock >= 0.0
+            })
+            .count();
+        &self.frames[..(count + 1).min(self.frames.len())]
+    }
+
+    // How far through the replay is, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        let real: f32 = self.frames.iter().map(|f| f.dt).sum();
+        (self.elapsed / (real * REPLAY_SLOWDOWN)).min(1.0)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.progress() >= 1.0
+    }
+}
@@ -0,0 +1,202 @@
+use crate::game::Vec2;
+
+// A lateral drop goes within two club-lengths of the ball, or of where it
+// crossed into a penalty area; at this scale that's about a tile.
+const LATERAL_TILES: f32 = 1.0;
+// How far back along the line from the hole a drop may be walked looking
+// for playable ground.
+const BACK_ON_LINE_MAX_TILES: f32 = 12.0;
+const SEARCH_STEP_TILES: f32 = 0.5;
+const CROSSING_STEP_TILES: f32 = 0.25;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Relief {
+    AsItLies,
+    FreeDrop,
+    // Stroke and distance.
+    PreviousSpot,
+    BackOnLine,
+    Lateral,
+    DropZone,
+}
+
+impl Relief {
+    pub fn name(self) -> &'static str {
+        match self {
+            Relief::AsItLies => "Play it as it lies",
+            Relief::FreeDrop => "Free drop",
+            Relief::PreviousSpot => "Previous spot",
+            Relief::BackOnLine => "Back on the line",
+            Relief::Lateral => "Lateral drop",
+            Relief::DropZone => "Drop zone",
+        }
+    }
+
+    pub fn action(self) -> &'static str {
+        match self {
+            Relief::AsItLies => "playing it as it lies",
+            Relief::FreeDrop => "taking a free drop",
+            Relief::PreviousSpot => "re-hitting",
+            Relief::BackOnLine => "dropping back on the line",
+            Relief::Lateral => "dropping within two club-lengths",
+            Relief::DropZone => "dropping in the drop zone",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct DropOption {
+    pub relief: Relief,
+    pub spot: Vec2,
+    pub penalty: u32,
+}
+
+// The options the rules allow from one situation, and the one the ball is
+// currently placed by. Changing it is allowed until the next stroke.
+#[derive(Clone)]
+pub struct ReliefChoice {
+    pub options: Vec<DropOption>,
+    pub chosen: usize,
+}
+
+impl ReliefChoice {
+    pub fn chosen(&self) -> DropOption {
+        self.options[self.chosen]
+    }
+
+    // Only worth asking about when there is more than one way to go on.
+    pub fn is_choice(&self) -> bool {
+        self.options.len() > 1
+    }
+}
+
+// Out of bounds is always stroke and distance.
+pub fn out_of_bounds(previous: Vec2) -> ReliefChoice {
+    ReliefChoice {
+        options: vec![DropOption {
+            relief: Relief::PreviousSpot,
+            spot: previous,
+            penalty: 1,
+        }],
+        chosen: 0,
+    }
+}
+
+// A ball in a pond, which plays as a red penalty area: for one stroke,
+// replay it, drop back on the line from the hole through where it crossed,
+// drop beside that point no nearer the hole, or use the drop zone if the
+// pond has one. The drop zone is taken by default, otherwise the replay.
+pub fn penalty_area(
+    previous: Vec2,
+    crossed: Vec2,
+    hole: Vec2,
+    drop_zone: Option<Vec2>,
+    playable: impl Fn(Vec2) -> bool,
+) -> ReliefChoice {
+    let option = |relief, spot| DropOption {
+        relief,
+        spot,
+        penalty: 1,
+    };
+    let mut options = vec![option(Relief::PreviousSpot, previous)];
+    if let Some(spot) = back_on_line(crossed, hole, &playable) {
+        options.push(option(Relief::BackOnLine, spot));
+    }
+    if let Some(spot) = lateral(crossed, hole, &playable) {
+        options.push(option(Relief::Lateral, spot));
+    }
+    if let Some(zone) = drop_zone {
+        options.push(option(Relief::DropZone, zone));
+    }
+    let chosen = if drop_zone.is_some() {
+        options.len() - 1
+    } else {
+        0
+    };
+    ReliefChoice { options, chosen }
+}
+
+// An unplayable ball, for one stroke: replay it, drop back on the line from
+// the hole through the ball, or drop within two club-lengths of it no nearer
+// the hole. The lateral drop is taken by default.
+pub fn unplayable(
+    previous: Vec2,
+    ball: Vec2,
+    hole: Vec2,
+    playable: impl Fn(Vec2) -> bool,
+) -> ReliefChoice {
+    let option = |relief, spot| DropOption {
+        relief,
+        spot,
+        penalty: 1,
+    };
+    let mut options = vec![option(Relief::PreviousSpot, previous)];
+    if let Some(spot) = back_on_line(ball, hole, &playable) {
+        options.push(option(Relief::BackOnLine, spot));
+    }
+    if let Some(spot) = lateral(ball, hole, &playable) {
+        options.push(option(Relief::Lateral, spot));
+    }
+    let chosen = options.len() - 1;
+    ReliefChoice { options, chosen }
+}
+
+// An embedded ball in the general area gets a free drop, or can be played.
+pub fn embedded(ball: Vec2, drop: Vec2) -> ReliefChoice {
+    ReliefChoice {
+        options: vec![
+            DropOption {
+                relief: Relief::AsItLies,
+                spot: ball,
+                penalty: 0,
+            },
+            DropOption {
+                relief: Relief::FreeDrop,
+                spot: drop,
+                penalty: 0,
+            },
+        ],
+        chosen: 0,
+    }
+}
+
+// Walks back from where the ball finished toward where it was struck to the
+// last point outside the area it ended in.
+pub fn crossing_point(from: Vec2, to: Vec2, inside: impl Fn(Vec2) -> bool) -> Vec2 {
+    let line = Vec2::new(from.x - to.x, from.y - to.y);
+    let steps = (line.length() / CROSSING_STEP_TILES).ceil().max(1.0) as u32;
+    (0..=steps)
+        .map(|i| {
+            let t = i as f32 / steps as f32;
+            Vec2::new(to.x + line.x * t, to.y + line.y * t)
+        })
+        .find(|point| !inside(*point))
+        .unwrap_or(from)
+}
+
+fn back_on_line(crossed: Vec2, hole: Vec2, playable: impl Fn(Vec2) -> bool) -> Option<Vec2> {
+    let away = Vec2::new(crossed.x - hole.x, crossed.y - hole.y).normalized();
+    let steps = (BACK_ON_LINE_MAX_TILES / SEARCH_STEP_TILES) as u32;
+    (1..=steps)
+        .map(|i| {
+            let d = i as f32 * SEARCH_STEP_TILES;
+            Vec2::new(crossed.x + away.x * d, crossed.y + away.y * d)
+        })
+        .find(|spot| playable(*spot))
+}
+
+fn lateral(crossed: Vec2, hole: Vec2, playable: impl Fn(Vec2) -> bool) -> Option<Vec2> {
+    let to_hole = Vec2::new(hole.x - crossed.x, hole.y - crossed.y);
+    let reach = to_hole.length();
+    let side = Vec2::new(-to_hole.y, to_hole.x).normalized();
+    let no_nearer = |spot: Vec2| Vec2::new(hole.x - spot.x, hole.y - spot.y).length() >= reach;
+    [1.0, -1.0, 0.5, -0.5]
+        .iter()
+        .map(|k| {
+            Vec2::new(
+                crossed.x + side.x * LATERAL_TILES * k,
+                crossed.y + side.y * LATERAL_TILES * k,
+            )
+        })
+        .find(|spot| no_nearer(*spot) && playable(*spot))
+}
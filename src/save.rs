@@ -0,0 +1,265 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::awards::RoundTally;
+use crate::bag::{Bag, Challenge};
+use crate::conditions::ConditionPreset;
+use crate::course::{Course, CourseRound, StartOption};
+use crate::game::{Game, ShotShape, ShotType, Vec2};
+use crate::hole::HoleLayout;
+use crate::storage::{self, Schema};
+use crate::wind::Wind;
+
+const SAVE_FILE: &str = "round.toml";
+const SCHEMA: Schema = Schema {
+    name: "saved round",
+    migrations: &[storage::unversioned, add_round_statistics],
+};
+
+// How far round a course the saved round had got.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedCourse {
+    source: PathBuf,
+    start: StartOption,
+    position: usize,
+    hole_clock: f32,
+    // Each finished hole by its place in the course: strokes and seconds.
+    finished: Vec<(usize, u32, f32)>,
+    tally: RoundTally,
+}
+
+// A round left part way through, kept in `round.toml` in the data
+// directory: the hole in play as it was laid out that day, down to the
+// ball, the club in hand and the wind, and the course around it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedRound {
+    pub strokes: u32,
+    pub penalty_strokes: u32,
+    pub angle: f32,
+    pub club: usize,
+    pub shot: ShotType,
+    pub shape: ShotShape,
+    pub buried: bool,
+    pub challenge: Challenge,
+    pub conditions: ConditionPreset,
+    pub ball: Vec2,
+    pub cup: Vec2,
+    pub bag: Bag,
+    pub wind: Wind,
+    pub layout: HoleLayout,
+    // Left on a finished hole, before the walk to the next tee.
+    #[serde(default)]
+    pub hole_done: bool,
+    pub course: Option<SavedCourse>,
+}
+
+impl SavedRound {
+    // The game with a shot to play, or nothing once the round is over. A
+    // moving ball is saved from where it was played, to be hit again, and a
+    // finished course hole walks on to the next tee when it's picked up.
+    pub fn of(game: &Game) -> Option<Self> {
+        if game.warm_up.is_some() {
+            return None;
+        }
+        if game.in_motion() {
+            return game.pre_shot.clone();
+        }
+        if game.hole_done && game.course_round.as_ref()?.is_last_hole() {
+            return None;
+        }
+        let course = game.course_round.as_ref().map(|round| SavedCourse {
+            source: round.course.source.clone(),
+            start: round.start,
+            position: round.position,
+            hole_clock: round.hole_clock,
+            finished: (0..round.course.holes.len())
+                .filter_map(|idx| Some((idx, round.card.strokes(idx)?, round.hole_secs[idx]?)))
+                .collect(),
+            tally: round.tally,
+        });
+        Some(Self {
+            strokes: game.strokes,
+            penalty_strokes: game.penalty_strokes,
+            angle: game.angle,
+            club: game.selected_club_idx,
+            shot: game.selected_shot,
+            shape: game.selected_shape,
+            buried: game.buried,
+            challenge: game.challenge,
+            conditions: game.conditions,
+            ball: game.ball,
+            cup: game.hole,
+            bag: game.bag,
+            wind: game.wind,
+            layout: game.layout.clone(),
+            hole_done: game.hole_done,
+            course,
+        })
+    }
+
+    // The course round it was part of, loaded again and brought up to the
+    // hole in play.
+    fn course_round(&self) -> Result<Option<CourseRound>, String> {
+        let Some(saved) = &self.course else {
+            return Ok(None);
+        };
+        let course = Course::load(&saved.source)?;
+        let mut round = CourseRound::new(course, saved.start)?;
+        if saved.position >= round.order.len() {
+            return Err("the saved round is past the course's last hole".to_string());
+        }
+        round.position = saved.position;
+        for &(idx, strokes, secs) in &saved.finished {
+            round.card.record(idx, strokes);
+            if let Some(slot) = round.hole_secs.get_mut(idx) {
+                *slot = Some(secs);
+            }
+        }
+        round.hole_clock = saved.hole_clock;
+        round.tally = saved.tally;
+        Ok(Some(round))
+    }
+}
+
+// Version 2 counted fairways, greens, putts and saves on the course round's
+// tally. A round saved before that picks them up from the resumed hole on.
+fn add_round_statistics(text: &str) -> Result<String, String> {
+    let mut saved: toml::Table = text
+        .parse()
+        .map_err(|err: toml::de::Error| err.to_string())?;
+    let tally = saved
+        .get_mut("course")
+        .and_then(|course| course.get_mut("tally"))
+        .and_then(toml::Value::as_table_mut);
+    if let Some(tally) = tally {
+        for key in [
+            "fairways_hit",
+            "fairway_chances",
+            "greens_in_regulation",
+            "putts",
+            "sand_saves",
+            "sand_chances",
+            "up_and_downs",
+            "up_and_down_chances",
+        ] {
+            tally.entry(key).or_insert(toml::Value::Integer(0));
+        }
+    }
+    toml::to_string(&saved).map_err(|err| err.to_string())
+}
+
+fn save_path() -> Option<PathBuf> {
+    storage::data_dir().map(|dir| dir.join(SAVE_FILE))
+}
+
+// Saves the round in progress on the way out, or clears the last save when
+// there's nothing to come back to.
+pub fn store(game: &Game) -> std::io::Result<()> {
+    let Some(path) = save_path() else {
+        return Ok(());
+    };
+    let Some(saved) = SavedRound::of(game) else {
+        return match fs::remove_file(&path) {
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            result => result,
+        };
+    };
+    let text = toml::to_string(&saved).map_err(std::io::Error::other)?;
+    storage::write(&path, &SCHEMA, &text)
+}
+
+// The saved round and its course, if there is one to pick up.
+pub fn load() -> Result<Option<(SavedRound, Option<CourseRound>)>, String> {
+    let Some(path) = save_path() else {
+        return Ok(None);
+    };
+    let Some(text) = storage::read(&path, &SCHEMA)? else {
+        return Ok(None);
+    };
+    let saved: SavedRound =
+        toml::from_str(&text).map_err(|err| storage::set_aside(&path, &SCHEMA, err.message()))?;
+    let round = saved.course_round()?;
+    Ok(Some((saved, round)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A round saved part way round a course, as an older release wrote it:
+    // the tally without the round's statistics.
+    fn old_save() -> String {
+        let mut saved = SavedRound::of(&Game::new()).unwrap();
+        saved.course = Some(SavedCourse {
+            source: PathBuf::from("course.toml"),
+            start: StartOption::FirstTee,
+            position: 3,
+            hole_clock: 12.0,
+            finished: vec![(0, 4, 90.0), (1, 5, 120.0), (2, 3, 75.0)],
+            tally: RoundTally {
+                holes: 3,
+                one_putts: 1,
+                ..RoundTally::default()
+            },
+        });
+        let mut table: toml::Table = toml::to_string(&saved).unwrap().parse().unwrap();
+        let tally = table["course"]["tally"].as_table_mut().unwrap();
+        for key in [
+            "fairways_hit",
+            "fairway_chances",
+            "greens_in_regulation",
+            "putts",
+            "sand_saves",
+            "sand_chances",
+            "up_and_downs",
+            "up_and_down_chances",
+        ] {
+            tally.remove(key);
+        }
+        toml::to_string(&table).unwrap()
+    }
+
+    #[test]
+    fn older_saves_migrate_to_the_current_version() {
+        let text = old_save();
+        assert!(!text.contains("\nputts ="));
+        for (version, header) in [(0, String::new()), (1, "# version 1\n".to_string())] {
+            let path = storage::scratch_dir(&format!("save_v{}", version)).join(SAVE_FILE);
+            fs::write(&path, format!("{}{}", header, text)).unwrap();
+            let migrated = storage::read(&path, &SCHEMA).unwrap().unwrap();
+            let saved: SavedRound = toml::from_str(&migrated)
+                .unwrap_or_else(|err| panic!("version {} save: {}", version, err));
+            let course = saved.course.unwrap();
+            assert_eq!(course.position, 3, "version {} save", version);
+            assert_eq!(course.tally.holes, 3, "version {} save", version);
+            assert_eq!(course.tally.putts, 0, "version {} save", version);
+            assert_eq!(course.tally.sand_chances, 0, "version {} save", version);
+        }
+    }
+
+    #[test]
+    fn a_moving_ball_is_saved_from_where_it_was_played() {
+        let mut game = Game::new();
+        let (tee, strokes) = (game.ball, game.strokes);
+        game.strike(0.0, None);
+        assert!(game.in_motion());
+        let saved = SavedRound::of(&game).unwrap();
+        assert_eq!((saved.ball.x, saved.ball.y), (tee.x, tee.y));
+        assert_eq!(saved.strokes, strokes);
+        assert!(game.in_motion(), "saving played the shot out");
+        assert_eq!(game.strokes, strokes + 1);
+    }
+
+    #[test]
+    fn a_corrupt_save_is_set_aside() {
+        let path = storage::scratch_dir("save_corrupt").join(SAVE_FILE);
+        let text = "# version 1\nstrokes = [\n";
+        fs::write(&path, text).unwrap();
+        assert!(storage::read(&path, &SCHEMA).is_err());
+        assert!(!path.exists());
+        assert_eq!(storage::backups(&path), [text]);
+    }
+}
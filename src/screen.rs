@@ -0,0 +1,431 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::queue;
+use crossterm::terminal::SetTitle;
+
+// Frames slower than this to get out, on average, suggest the link can't
+// keep up; so does a remote session pushing more than the budget.
+const SLOW_FLUSH: Duration = Duration::from_millis(20);
+const REMOTE_BYTES_PER_SEC: usize = 64 * 1024;
+const METER_WINDOW: Duration = Duration::from_secs(2);
+const METER_MIN_FRAMES: usize = 10;
+
+// The xterm defaults for the sixteen basic colors, to match anything richer
+// against in sixteen-color output.
+const BASIC_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (128, 0, 0),
+    (0, 128, 0),
+    (128, 128, 0),
+    (0, 0, 128),
+    (128, 0, 128),
+    (0, 128, 128),
+    (192, 192, 192),
+    (128, 128, 128),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum Paint {
+    #[default]
+    Default,
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Paint {
+    fn rgb(self) -> Option<(u8, u8, u8)> {
+        match self {
+            Paint::Default => None,
+            Paint::Indexed(n @ 0..=15) => Some(BASIC_RGB[n as usize]),
+            Paint::Indexed(n @ 16..=231) => {
+                let n = n - 16;
+                Some((
+                    CUBE_LEVELS[(n / 36) as usize],
+                    CUBE_LEVELS[(n / 6 % 6) as usize],
+                    CUBE_LEVELS[(n % 6) as usize],
+                ))
+            }
+            Paint::Indexed(n) => {
+                let grey = 8 + 10 * (n - 232);
+                Some((grey, grey, grey))
+            }
+            Paint::Rgb(r, g, b) => Some((r, g, b)),
+        }
+    }
+
+    fn basic(self) -> Option<u8> {
+        let (r, g, b) = self.rgb()?;
+        let distance = |&(pr, pg, pb): &(u8, u8, u8)| {
+            let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            d(r, pr) + d(g, pg) + d(b, pb)
+        };
+        match self {
+            Paint::Indexed(n @ 0..=15) => Some(n),
+            _ => (0..16u8).min_by_key(|&i| distance(&BASIC_RGB[i as usize])),
+        }
+    }
+
+    // Always one of the sixteen basic colors, which every terminal has and
+    // which take the fewest bytes to ask for.
+    fn sgr(self, base: u8) -> String {
+        match self.basic() {
+            None => format!("{}9", base),
+            Some(n @ 0..=7) => format!("{}", base * 10 + n),
+            Some(n) => format!("{}", base * 10 + 60 + n - 8),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+struct Style {
+    fg: Paint,
+    bg: Paint,
+    // SGR attribute numbers 1 to 9 as bits.
+    attrs: u16,
+}
+
+impl Style {
+    fn apply(&mut self, params: &str) {
+        let mut codes = params.split(';').map(|p| p.parse::<u16>().unwrap_or(0));
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Style::default(),
+                1..=9 => self.attrs |= 1 << code,
+                22 => self.attrs &= !(1 << 1 | 1 << 2),
+                23..=29 => self.attrs &= !(1 << (code - 20)),
+                30..=37 => self.fg = Paint::Indexed((code - 30) as u8),
+                90..=97 => self.fg = Paint::Indexed((code - 90 + 8) as u8),
+                40..=47 => self.bg = Paint::Indexed((code - 40) as u8),
+                100..=107 => self.bg = Paint::Indexed((code - 100 + 8) as u8),
+                39 => self.fg = Paint::Default,
+                49 => self.bg = Paint::Default,
+                38 | 48 => {
+                    let paint = match codes.next() {
+                        Some(5) => Paint::Indexed(codes.next().unwrap_or(0) as u8),
+                        Some(2) => Paint::Rgb(
+                            codes.next().unwrap_or(0) as u8,
+                            codes.next().unwrap_or(0) as u8,
+                            codes.next().unwrap_or(0) as u8,
+                        ),
+                        _ => Paint::Default,
+                    };
+                    if code == 38 {
+                        self.fg = paint;
+                    } else {
+                        self.bg = paint;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn sgr(self) -> String {
+        let mut parts = vec!["0".to_string()];
+        parts.extend(
+            (1..=9)
+                .filter(|n| self.attrs & (1 << n) != 0)
+                .map(|n| n.to_string()),
+        );
+        parts.push(self.fg.sgr(3));
+        parts.push(self.bg.sgr(4));
+        format!("\x1b[{}m", parts.join(";"))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Cell {
+    ch: char,
+    style: Style,
+}
+
+const BLANK: Cell = Cell {
+    ch: ' ',
+    style: Style {
+        fg: Paint::Default,
+        bg: Paint::Default,
+        attrs: 0,
+    },
+};
+
+// What the terminal shows, read back from the stream the renderer writes:
+// cursor moves, clears, colors and text.
+#[derive(Clone, Default)]
+struct Grid {
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Grid {
+    fn read(text: &str) -> Self {
+        let mut grid = Grid::default();
+        let (mut x, mut y) = (0, 0);
+        let mut style = Style::default();
+        let mut chars = text.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\x1b' => {
+                    if chars.next() != Some('[') {
+                        continue;
+                    }
+                    let mut params = String::new();
+                    let Some(end) = chars.by_ref().find(|c| {
+                        let done = ('@'..='~').contains(c);
+                        if !done {
+                            params.push(*c);
+                        }
+                        done
+                    }) else {
+                        break;
+                    };
+                    match end {
+                        'H' => {
+                            let mut at = params.split(';').map(|p| p.parse().unwrap_or(1));
+                            y = at.next().unwrap_or(1).max(1) - 1;
+                            x = at.next().unwrap_or(1).max(1) - 1;
+                        }
+                        'J' if params == "2" => grid.rows.clear(),
+                        'm' => style.apply(&params),
+                        _ => {}
+                    }
+                }
+                '\n' => {
+                    x = 0;
+                    y += 1;
+                }
+                '\r' => x = 0,
+                ch => {
+                    grid.put(x, y, Cell { ch, style });
+                    x += 1;
+                }
+            }
+        }
+        grid
+    }
+
+    fn put(&mut self, x: usize, y: usize, cell: Cell) {
+        if self.rows.len() <= y {
+            self.rows.resize(y + 1, Vec::new());
+        }
+        let row = &mut self.rows[y];
+        if row.len() <= x {
+            row.resize(x + 1, BLANK);
+        }
+        row[x] = cell;
+    }
+
+    fn get(&self, x: usize, y: usize) -> Cell {
+        self.rows
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(BLANK)
+    }
+
+    fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    // Only the cells that differ from `shown`, each run placed with one
+    // cursor move and restyled only where its look changes.
+    fn changes_from(&self, shown: &Grid) -> String {
+        let mut out = String::new();
+        let mut cursor = None;
+        let mut style = None;
+        let width = self.width().max(shown.width());
+        for y in 0..self.rows.len().max(shown.rows.len()) {
+            for x in 0..width {
+                let cell = self.get(x, y);
+                if cell == shown.get(x, y) {
+                    continue;
+                }
+                if cursor != Some((x, y)) {
+                    out.push_str(&format!("\x1b[{};{}H", y + 1, x + 1));
+                }
+                if style != Some(cell.style) {
+                    out.push_str(&cell.style.sgr());
+                    style = Some(cell.style);
+                }
+                out.push(cell.ch);
+                cursor = Some((x + 1, y));
+            }
+        }
+        if style.is_some() {
+            out.push_str("\x1b[0m");
+        }
+        out
+    }
+}
+
+// What the tab or taskbar shows for a wait, over OSC 9;4.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    Percent(u8),
+    Busy,
+}
+
+impl Progress {
+    fn osc(progress: Option<Self>) -> String {
+        let state = match progress {
+            None => "0;0".to_string(),
+            Some(Progress::Percent(pct)) => format!("1;{}", pct.min(100)),
+            Some(Progress::Busy) => "3;0".to_string(),
+        };
+        format!("\x1b]9;4;{}\x07", state)
+    }
+}
+
+// Other terminals take OSC 9 as a desktop notification, so progress only goes
+// to the ones known to draw it.
+fn progress_supported() -> bool {
+    std::env::var_os("WT_SESSION").is_some()
+        || std::env::var_os("ConEmuPID").is_some()
+        || std::env::var("TERM_PROGRAM")
+            .is_ok_and(|term| matches!(term.as_str(), "ghostty" | "WezTerm"))
+}
+
+// Tracks how long recent frames took to get out and how big they were.
+#[derive(Default)]
+struct LinkMeter {
+    frames: VecDeque<(Instant, usize, Duration)>,
+}
+
+impl LinkMeter {
+    fn record(&mut self, bytes: usize, took: Duration) {
+        let now = Instant::now();
+        self.frames.push_back((now, bytes, took));
+        while self
+            .frames
+            .front()
+            .is_some_and(|(at, _, _)| now.duration_since(*at) > METER_WINDOW)
+        {
+            self.frames.pop_front();
+        }
+    }
+
+    fn slow(&self, remote: bool) -> bool {
+        if self.frames.len() < METER_MIN_FRAMES {
+            return false;
+        }
+        let took: Duration = self.frames.iter().map(|(_, _, took)| *took).sum();
+        let bytes: usize = self.frames.iter().map(|(_, bytes, _)| bytes).sum();
+        let bytes_per_sec = bytes as f32 / METER_WINDOW.as_secs_f32();
+        took / self.frames.len() as u32 >= SLOW_FLUSH
+            || (remote && bytes_per_sec >= REMOTE_BYTES_PER_SEC as f32)
+    }
+}
+
+// Everything drawn goes through here. Normally a frame is passed on as it
+// was written; in low-bandwidth mode only the cells that changed since the
+// last frame are sent.
+pub struct Screen<W: Write> {
+    out: W,
+    pending: Vec<u8>,
+    low_bandwidth: bool,
+    shown: Option<Grid>,
+    meter: LinkMeter,
+    remote: bool,
+    // The title and progress last sent, so each goes out only when it changes.
+    title: Option<String>,
+    progress: Option<Progress>,
+    progress_supported: bool,
+}
+
+impl<W: Write> Screen<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            pending: Vec::new(),
+            low_bandwidth: false,
+            shown: None,
+            meter: LinkMeter::default(),
+            remote: std::env::var_os("SSH_CONNECTION").is_some()
+                || std::env::var_os("SSH_TTY").is_some(),
+            title: None,
+            progress: None,
+            progress_supported: progress_supported(),
+        }
+    }
+
+    // Sent straight to the terminal rather than with the frame, so neither
+    // lands in the low-bandwidth grid; both go out with the next flush.
+    pub fn report(&mut self, title: &str, progress: Option<Progress>) -> io::Result<()> {
+        if self.title.as_deref() != Some(title) {
+            queue!(self.out, SetTitle(title))?;
+            self.title = Some(title.to_string());
+        }
+        let progress = progress.filter(|_| self.progress_supported);
+        if progress != self.progress {
+            self.out.write_all(Progress::osc(progress).as_bytes())?;
+            self.progress = progress;
+        }
+        Ok(())
+    }
+
+    // Switching on starts over from a full frame.
+    pub fn set_low_bandwidth(&mut self, on: bool) {
+        if on != self.low_bandwidth {
+            self.repaint();
+        }
+        self.low_bandwidth = on;
+    }
+
+    // The next frame is sent whole, for when the terminal may have lost
+    // what it showed.
+    pub fn repaint(&mut self) {
+        self.shown = None;
+    }
+
+    pub fn slow_link(&self) -> bool {
+        self.meter.slow(self.remote)
+    }
+
+    fn frame(&mut self) -> Vec<u8> {
+        let written = std::mem::take(&mut self.pending);
+        if !self.low_bandwidth {
+            return written;
+        }
+        let grid = Grid::read(&String::from_utf8_lossy(&written));
+        let bytes = match &self.shown {
+            Some(shown) => grid.changes_from(shown),
+            None => format!("\x1b[2J{}", grid.changes_from(&Grid::default())),
+        };
+        self.shown = Some(grid);
+        bytes.into_bytes()
+    }
+}
+
+// A bar left up would outlast the game in the tab.
+impl<W: Write> Drop for Screen<W> {
+    fn drop(&mut self) {
+        if self.progress.is_some() {
+            let _ = self.out.write_all(Progress::osc(None).as_bytes());
+            let _ = self.out.flush();
+        }
+    }
+}
+
+impl<W: Write> Write for Screen<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let frame = self.frame();
+        let started = Instant::now();
+        self.out.write_all(&frame)?;
+        self.out.flush()?;
+        self.meter.record(frame.len(), started.elapsed());
+        Ok(())
+    }
+}
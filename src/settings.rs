@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ai::AiSkill;
+use crate::bag::Challenge;
+use crate::conditions::{ConditionPreset, GroundConditions};
+use crate::game::TICK_MS;
+
+// Bounds for the update step and the frame cap set on the command line.
+const TICK_MS_BOUNDS: (u64, u64) = (10, 100);
+const FPS_BOUNDS: (u64, u64) = (2, 60);
+// Low-bandwidth mode redraws this often unless a cap is given, and sixel
+// output, which sends the whole map every frame, this often.
+const LOW_BANDWIDTH_FPS: u64 = 8;
+const SIXEL_FPS: u64 = 15;
+// Frames a second while a ball moves when no cap is given.
+const MOTION_FPS: u64 = 60;
+const METERS_PER_YARD: f32 = 0.9144;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Classic,
+    Retro,
+}
+
+impl Theme {
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Classic => "Classic",
+            Theme::Retro => "Retro 8-bit",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Theme::Classic => Theme::Retro,
+            Theme::Retro => Theme::Classic,
+        }
+    }
+}
+
+// How distances read. The game itself works in yards throughout.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Units {
+    #[default]
+    Yards,
+    Meters,
+}
+
+impl Units {
+    pub fn name(self) -> &'static str {
+        match self {
+            Units::Yards => "yards",
+            Units::Meters => "meters",
+        }
+    }
+
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        [Units::Yards, Units::Meters]
+            .into_iter()
+            .find(|units| units.name().eq_ignore_ascii_case(arg))
+    }
+
+    // A distance in yards as the player reads it, for the screen.
+    pub fn short(self, yd: f32) -> String {
+        match self {
+            Units::Yards => format!("{:.0} yd", yd),
+            Units::Meters => format!("{:.0} m", yd * METERS_PER_YARD),
+        }
+    }
+
+    // The same, in words for the screen reader.
+    pub fn spoken(self, yd: f32) -> String {
+        match self {
+            Units::Yards => format!("{:.0} yards", yd),
+            Units::Meters => format!("{:.0} meters", yd * METERS_PER_YARD),
+        }
+    }
+}
+
+// Where the HUD goes: the full side panel, a single broadcast-style line
+// under the map, or whichever fits the terminal.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HudLayout {
+    Auto,
+    Full,
+    Broadcast,
+}
+
+impl HudLayout {
+    pub fn name(self) -> &'static str {
+        match self {
+            HudLayout::Auto => "Auto",
+            HudLayout::Full => "Full",
+            HudLayout::Broadcast => "Broadcast",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            HudLayout::Auto => HudLayout::Full,
+            HudLayout::Full => HudLayout::Broadcast,
+            HudLayout::Broadcast => HudLayout::Auto,
+        }
+    }
+
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        [HudLayout::Auto, HudLayout::Full, HudLayout::Broadcast]
+            .into_iter()
+            .find(|layout| layout.name().eq_ignore_ascii_case(arg))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Settings {
+    pub wind_on_putts: bool,
+    pub pressure_putts: bool,
+    pub theme: Theme,
+    pub hud: HudLayout,
+    pub high_contrast: bool,
+    pub reduced_motion: bool,
+    pub pull_back_putting: bool,
+    // The golfer swings from the other side of the ball.
+    pub left_handed: bool,
+    pub units: Units,
+    // Hints along the bottom of the map walk through the controls until the
+    // first hole is holed.
+    pub tutorial: bool,
+    pub instant_camera: bool,
+    // Shades the zoomed green by which way each tile falls.
+    pub slope_shading: bool,
+    // Arcs at 200, 150 and 100 yards from the pin over the full view.
+    pub distance_arcs: bool,
+    // Colors the full view by the caddie's expected score from each spot.
+    pub heat_map: bool,
+    pub conditions: Option<ConditionPreset>,
+    // How firm and fast the course plays, for the whole session.
+    pub course_conditions: GroundConditions,
+    pub challenge: Challenge,
+    pub pause_on_focus_loss: bool,
+    // Seconds without input at address before the help hint shows and a
+    // racing player counts as away; zero turns both off.
+    pub idle_hint_secs: f32,
+    pub afk_autoplay: bool,
+    // Generated holes that play far easier or harder than usual are redrawn.
+    pub balanced_holes: bool,
+    // Course rounds open with a few balls on the range that set the day's form.
+    pub warm_up: bool,
+    // Balls landing on soft ground can pick up mud that swerves the next swing.
+    pub mud_balls: bool,
+    // The game steps every `tick_ms` and redraws at most `fps_cap` times a
+    // second: no more often than it steps while everything is still, and
+    // up to the cap in between steps while a ball moves. Over a slow link
+    // a low cap keeps the terminal from falling behind.
+    pub tick_ms: u64,
+    pub fps_cap: Option<u64>,
+    // For slow SSH or mosh sessions: fewer frames, sixteen colors, nothing
+    // moving for show, and only the cells that changed sent each frame.
+    pub low_bandwidth: bool,
+    // Play each hole against the shadow rival as a match.
+    pub match_play: bool,
+    // A computer golfer plays each hole alongside the player.
+    pub ai: Option<AiSkill>,
+    // Two players share the ball and alternate shots.
+    pub foursomes: bool,
+    // Course rounds are played as a four-round tournament with a cut.
+    pub tournament: bool,
+    // Race a grey ball replaying the best round kept for the course.
+    pub ghost: bool,
+    // Draw the map as an image on terminals with the kitty graphics
+    // protocol; elsewhere the characters stay.
+    pub kitty: bool,
+    // Draw the map as sixel images, for xterm, mlterm and the like.
+    pub sixel: bool,
+    // Checks every roll substep for energy the forces on the ball can't
+    // account for.
+    pub energy_audit: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            wind_on_putts: false,
+            pressure_putts: false,
+            theme: Theme::Classic,
+            hud: HudLayout::Auto,
+            high_contrast: false,
+            reduced_motion: false,
+            pull_back_putting: false,
+            left_handed: false,
+            units: Units::Yards,
+            tutorial: false,
+            instant_camera: false,
+            slope_shading: false,
+            distance_arcs: false,
+            heat_map: false,
+            conditions: None,
+            course_conditions: GroundConditions::default(),
+            challenge: Challenge::None,
+            pause_on_focus_loss: true,
+            idle_hint_secs: 20.0,
+            afk_autoplay: true,
+            balanced_holes: false,
+            warm_up: false,
+            mud_balls: false,
+            tick_ms: TICK_MS,
+            fps_cap: None,
+            low_bandwidth: false,
+            match_play: false,
+            ai: None,
+            foursomes: false,
+            tournament: false,
+            ghost: false,
+            kitty: false,
+            sixel: false,
+            energy_audit: false,
+        }
+    }
+}
+
+impl Settings {
+    pub fn toggle_wind_on_putts(&mut self) {
+        self.wind_on_putts = !self.wind_on_putts;
+    }
+
+    pub fn toggle_pressure_putts(&mut self) {
+        self.pressure_putts = !self.pressure_putts;
+    }
+
+    pub fn toggle_high_contrast(&mut self) {
+        self.high_contrast = !self.high_contrast;
+    }
+
+    pub fn toggle_reduced_motion(&mut self) {
+        self.reduced_motion = !self.reduced_motion;
+    }
+
+    pub fn toggle_pull_back_putting(&mut self) {
+        self.pull_back_putting = !self.pull_back_putting;
+    }
+
+    pub fn toggle_instant_camera(&mut self) {
+        self.instant_camera = !self.instant_camera;
+    }
+
+    pub fn toggle_slope_shading(&mut self) {
+        self.slope_shading = !self.slope_shading;
+    }
+
+    pub fn toggle_distance_arcs(&mut self) {
+        self.distance_arcs = !self.distance_arcs;
+    }
+
+    pub fn toggle_heat_map(&mut self) {
+        self.heat_map = !self.heat_map;
+    }
+
+    pub fn cycle_conditions(&mut self) {
+        self.conditions = ConditionPreset::next_choice(self.conditions);
+    }
+
+    pub fn cycle_challenge(&mut self) {
+        self.challenge = self.challenge.next();
+    }
+
+    pub fn cycle_theme(&mut self) {
+        self.theme = self.theme.next();
+    }
+
+    pub fn cycle_hud(&mut self) {
+        self.hud = self.hud.next();
+    }
+
+    pub fn set_tick_ms(&mut self, ms: u64) {
+        self.tick_ms = ms.clamp(TICK_MS_BOUNDS.0, TICK_MS_BOUNDS.1);
+    }
+
+    pub fn set_fps_cap(&mut self, fps: u64) {
+        self.fps_cap = Some(fps.clamp(FPS_BOUNDS.0, FPS_BOUNDS.1));
+    }
+
+    pub fn toggle_low_bandwidth(&mut self) {
+        self.low_bandwidth = !self.low_bandwidth;
+    }
+
+    fn fps(&self) -> Option<u64> {
+        match self.fps_cap {
+            None if self.low_bandwidth => Some(LOW_BANDWIDTH_FPS),
+            None if self.sixel => Some(SIXEL_FPS),
+            fps => fps,
+        }
+    }
+
+    pub fn frame_ms(&self) -> u64 {
+        self.fps().map_or(0, |fps| 1000 / fps).max(self.tick_ms)
+    }
+
+    // Frames between steps draw a moving ball part way along, so they can
+    // come faster than the steps.
+    pub fn motion_frame_ms(&self) -> u64 {
+        1000 / self.fps().unwrap_or(MOTION_FPS)
+    }
+
+    // Animation that is only there for show: pulsing hints and sparkles.
+    pub fn ambient_motion(&self) -> bool {
+        !self.reduced_motion && !self.low_bandwidth
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+
+use crate::stats::{bucket_index, HoleSideStats, PUTT_BUCKETS};
+
+// Records a club or a miss pattern needs before the rival copies it.
+const MIN_CARRY_SHOTS: u32 = 3;
+const MIN_MISSES: usize = 5;
+const MIN_PUTTS: u32 = 5;
+// Roughly how far across the line the caddie's own approaches scatter; a
+// wider pattern in the stats widens the rival's by the difference.
+const CADDIE_SCATTER_FT: f32 = 30.0;
+// Misses are recorded from approaches of all lengths; turned into angles
+// they're read as if from a typical one, and kept within reason.
+const TYPICAL_APPROACH_FT: f32 = 360.0;
+const MAX_AIM_ERROR: f32 = 0.1;
+// Putts the rival misses are off by about this much at the hole.
+const PUTT_MISS_FT: f32 = 0.6;
+
+// A caddie bot tuned to play like the player: each club carries what the
+// player's has on average, approaches lean and scatter the way the recent
+// misses do, and putts drop at the player's make rates.
+#[derive(Clone)]
+pub struct ShadowRival {
+    carry_ratios: BTreeMap<usize, f32>,
+    // Radians right of the line on average, and the extra spread either side.
+    lean: f32,
+    extra_scatter: f32,
+    putt_make_rates: [Option<f32>; PUTT_BUCKETS.len()],
+}
+
+impl ShadowRival {
+    pub fn from_stats(stats: &HoleSideStats) -> Self {
+        let carry_ratios = stats
+            .carries
+            .iter()
+            .filter(|(_, record)| record.shots >= MIN_CARRY_SHOTS)
+            .filter_map(|(&idx, record)| Some((idx, record.average_ratio()?.clamp(0.7, 1.15))))
+            .collect();
+
+        let misses = &stats.recent_misses;
+        let (mut lean, mut extra_scatter) = (0.0, 0.0);
+        if misses.len() >= MIN_MISSES {
+            let n = misses.len() as f32;
+            let lean_right_ft = misses.iter().map(|m| m.right_ft).sum::<f32>() / n;
+            let variance = misses
+                .iter()
+                .map(|m| (m.right_ft - lean_right_ft).powi(2))
+                .sum::<f32>()
+                / n;
+            let extra_ft = (variance.sqrt() - CADDIE_SCATTER_FT).max(0.0);
+            lean = (lean_right_ft / TYPICAL_APPROACH_FT).clamp(-MAX_AIM_ERROR, MAX_AIM_ERROR);
+            extra_scatter = (extra_ft / TYPICAL_APPROACH_FT).min(MAX_AIM_ERROR);
+        }
+
+        let mut putt_make_rates = [None; PUTT_BUCKETS.len()];
+        for (rate, bucket) in putt_make_rates.iter_mut().zip(&stats.putts) {
+            if bucket.attempts >= MIN_PUTTS {
+                *rate = bucket.make_rate();
+            }
+        }
+
+        Self {
+            carry_ratios,
+            lean,
+            extra_scatter,
+            putt_make_rates,
+        }
+    }
+
+    pub fn carry_mult(&self, club_idx: usize) -> f32 {
+        self.carry_ratios.get(&club_idx).copied().unwrap_or(1.0)
+    }
+
+    // Radians off the chosen line for a shot off the green.
+    pub fn aim_error<R: Rng>(&self, rng: &mut R) -> f32 {
+        self.lean + rng.gen_range(-1.0..=1.0) * self.extra_scatter
+    }
+
+    // Aim and pace changes for a putt of this length: none when it drops,
+    // enough to miss when the player's make rate says it shouldn't.
+    pub fn putt_error<R: Rng>(&self, length_ft: f32, rng: &mut R) -> (f32, f32) {
+        let rate = bucket_index(&PUTT_BUCKETS, length_ft).and_then(|i| self.putt_make_rates[i]);
+        match rate {
+            Some(rate) if rng.gen::<f32>() >= rate => {
+                let side = if rng.gen() { 1.0 } else { -1.0 };
+                (
+                    side * PUTT_MISS_FT / length_ft.max(1.0),
+                    rng.gen_range(0.85..1.2),
+                )
+            }
+            _ => (0.0, 1.0),
+        }
+    }
+}
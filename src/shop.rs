@@ -0,0 +1,168 @@
+// Career earnings are play money: finishing course rounds and challenge holes
+// pays out, and the only thing to spend it on is how the game looks.
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Palette,
+    Ball,
+    Golfer,
+}
+
+impl Slot {
+    pub fn name(self) -> &'static str {
+        match self {
+            Slot::Palette => "Palette",
+            Slot::Ball => "Ball",
+            Slot::Golfer => "Golfer",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Look {
+    // Colors of the ball and the aim line.
+    Palette {
+        ball: (u8, u8, u8),
+        aim: (u8, u8, u8),
+    },
+    Ball(char),
+    Golfer {
+        head: char,
+        body: char,
+        shirt: (u8, u8, u8),
+    },
+}
+
+pub struct Cosmetic {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub price: u32,
+    pub look: Look,
+}
+
+impl Cosmetic {
+    pub fn slot(&self) -> Slot {
+        match self.look {
+            Look::Palette { .. } => Slot::Palette,
+            Look::Ball(_) => Slot::Ball,
+            Look::Golfer { .. } => Slot::Golfer,
+        }
+    }
+}
+
+// The free item of each slot comes first and is what everyone starts with.
+pub const CATALOG: &[Cosmetic] = &[
+    Cosmetic {
+        key: "palette.classic",
+        name: "Classic",
+        price: 0,
+        look: Look::Palette {
+            ball: (255, 255, 255),
+            aim: (255, 255, 0),
+        },
+    },
+    Cosmetic {
+        key: "palette.tour",
+        name: "Tour Yellow",
+        price: 400,
+        look: Look::Palette {
+            ball: (255, 225, 60),
+            aim: (120, 220, 255),
+        },
+    },
+    Cosmetic {
+        key: "palette.sunset",
+        name: "Sunset",
+        price: 900,
+        look: Look::Palette {
+            ball: (255, 150, 90),
+            aim: (255, 110, 180),
+        },
+    },
+    Cosmetic {
+        key: "palette.neon",
+        name: "Neon",
+        price: 1500,
+        look: Look::Palette {
+            ball: (120, 255, 120),
+            aim: (255, 80, 255),
+        },
+    },
+    Cosmetic {
+        key: "ball.standard",
+        name: "Standard",
+        price: 0,
+        look: Look::Ball('●'),
+    },
+    Cosmetic {
+        key: "ball.diamond",
+        name: "Diamond",
+        price: 300,
+        look: Look::Ball('◆'),
+    },
+    Cosmetic {
+        key: "ball.ring",
+        name: "Bullseye",
+        price: 600,
+        look: Look::Ball('◉'),
+    },
+    Cosmetic {
+        key: "ball.star",
+        name: "Star",
+        price: 1200,
+        look: Look::Ball('★'),
+    },
+    Cosmetic {
+        key: "golfer.classic",
+        name: "Club Member",
+        price: 0,
+        look: Look::Golfer {
+            head: '●',
+            body: '█',
+            shirt: (255, 255, 255),
+        },
+    },
+    Cosmetic {
+        key: "golfer.visor",
+        name: "Visor Pro",
+        price: 700,
+        look: Look::Golfer {
+            head: '◓',
+            body: '█',
+            shirt: (80, 160, 255),
+        },
+    },
+    Cosmetic {
+        key: "golfer.sunday",
+        name: "Sunday Red",
+        price: 2000,
+        look: Look::Golfer {
+            head: '●',
+            body: '▆',
+            shirt: (220, 40, 40),
+        },
+    },
+];
+
+pub fn find(key: &str) -> Option<&'static Cosmetic> {
+    CATALOG.iter().find(|item| item.key == key)
+}
+
+pub fn default_for(slot: Slot) -> &'static Cosmetic {
+    CATALOG
+        .iter()
+        .find(|item| item.slot() == slot)
+        .unwrap_or(&CATALOG[0])
+}
+
+// A course round pays like a purse: more for a longer round, and more again
+// for every shot under par, with something for anyone who finishes.
+pub fn round_purse(holes: usize, to_par: i32) -> u32 {
+    let base = 50 * holes as i32;
+    (base - 60 * to_par).max(25 * holes as i32) as u32
+}
+
+// Finishing a hole under a challenge rule.
+pub fn challenge_prize(to_par: i32) -> u32 {
+    (100 - 40 * to_par).clamp(20, 300) as u32
+}
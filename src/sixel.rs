@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use crossterm::cursor::MoveTo;
+use crossterm::{queue, terminal};
+
+use crate::camera::Camera;
+use crate::game::Game;
+use crate::raster::{self, Canvas, CourseKey, Rgb};
+
+// For terminals that don't report how many pixels their cells are.
+const FALLBACK_CELL_PX: (usize, usize) = (10, 20);
+// Color registers the common sixel terminals give an image.
+const MAX_COLORS: usize = 256;
+
+// Draws the map as sixel images, pixel for pixel at the terminal's cell
+// size. Nothing survives the screen being cleared, so every frame sends the
+// course again; it's only encoded anew when the view changes.
+pub struct SixelView {
+    cell_px: (usize, usize),
+    course: Option<(CourseKey, Vec<u8>)>,
+    sprites: Vec<(Rgb, Vec<u8>)>,
+}
+
+impl SixelView {
+    pub fn new() -> Self {
+        Self {
+            cell_px: cell_px(),
+            course: None,
+            sprites: Vec::new(),
+        }
+    }
+
+    // The font may have changed size along with the window.
+    pub fn resize(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn draw_course(
+        &mut self,
+        out: &mut impl Write,
+        game: &Game,
+        camera: Camera,
+        tile: impl Fn(i32, i32) -> Rgb,
+        (left, top): (u16, u16),
+    ) -> std::io::Result<()> {
+        let key = CourseKey::of(game, camera);
+        if self.course.as_ref().is_none_or(|(drawn, _)| *drawn != key) {
+            let size = (game.layout.width, game.layout.height);
+            let canvas = raster::course(camera, size, tile, game.hole, self.cell_px);
+            self.course = Some((key, encode(&canvas)));
+        }
+        queue!(out, MoveTo(left, top))?;
+        if let Some((_, sixel)) = &self.course {
+            out.write_all(sixel)?;
+        }
+        Ok(())
+    }
+
+    pub fn draw_ball(
+        &mut self,
+        out: &mut impl Write,
+        color: Rgb,
+        cell: (u16, u16),
+    ) -> std::io::Result<()> {
+        let i = match self.sprites.iter().position(|(c, _)| *c == color) {
+            Some(i) => i,
+            None => {
+                let sprite = raster::ball_sprite(color, self.cell_px);
+                self.sprites.push((color, encode(&sprite)));
+                self.sprites.len() - 1
+            }
+        };
+        queue!(out, MoveTo(cell.0, cell.1))?;
+        out.write_all(&self.sprites[i].1)
+    }
+}
+
+fn cell_px() -> (usize, usize) {
+    match terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => (
+            (size.width / size.columns) as usize,
+            (size.height / size.rows) as usize,
+        ),
+        _ => FALLBACK_CELL_PX,
+    }
+}
+
+// Every color in the canvas, coarsened a bit at a time until they fit the
+// registers, and the register each pixel takes.
+fn palette(canvas: &Canvas) -> (Vec<Rgb>, Vec<Option<usize>>) {
+    for shift in 0..8 {
+        let coarse = |(r, g, b): Rgb| {
+            (
+                r >> shift << shift,
+                g >> shift << shift,
+                b >> shift << shift,
+            )
+        };
+        let mut registers = HashMap::new();
+        let mut colors = Vec::new();
+        let index: Vec<Option<usize>> = canvas
+            .pixels
+            .iter()
+            .map(|pixel| {
+                pixel.map(|color| {
+                    *registers.entry(coarse(color)).or_insert_with(|| {
+                        colors.push(coarse(color));
+                        colors.len() - 1
+                    })
+                })
+            })
+            .collect();
+        if colors.len() <= MAX_COLORS {
+            return (colors, index);
+        }
+    }
+    (Vec::new(), vec![None; canvas.pixels.len()])
+}
+
+// Bands six pixels tall, each sent one color at a time as a row of column
+// patterns, with repeats packed into `!count`. Pixels left unset stay
+// see-through.
+fn encode(canvas: &Canvas) -> Vec<u8> {
+    let (colors, index) = palette(canvas);
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", canvas.width, canvas.height);
+    let percent = |c: u8| (c as u32 * 100 + 127) / 255;
+    for (i, (r, g, b)) in colors.iter().enumerate() {
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            i,
+            percent(*r),
+            percent(*g),
+            percent(*b)
+        ));
+    }
+    for top in (0..canvas.height).step_by(6) {
+        let mut masks: Vec<Vec<u8>> = vec![Vec::new(); colors.len()];
+        let mut used = Vec::new();
+        for dy in 0..6.min(canvas.height - top) {
+            let row = (top + dy) * canvas.width;
+            for x in 0..canvas.width {
+                let Some(c) = index[row + x] else {
+                    continue;
+                };
+                if masks[c].is_empty() {
+                    masks[c] = vec![0; canvas.width];
+                    used.push(c);
+                }
+                masks[c][x] |= 1 << dy;
+            }
+        }
+        for (n, &c) in used.iter().enumerate() {
+            if n > 0 {
+                out.push('$');
+            }
+            out.push_str(&format!("#{}", c));
+            push_runs(&mut out, &masks[c]);
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out.into_bytes()
+}
+
+fn push_runs(out: &mut String, mask: &[u8]) {
+    let end = mask.iter().rposition(|&m| m != 0).map_or(0, |i| i + 1);
+    let mut x = 0;
+    while x < end {
+        let run = mask[x..end].iter().take_while(|&&m| m == mask[x]).count();
+        let ch = (63 + mask[x]) as char;
+        if run > 3 {
+            out.push_str(&format!("!{}{}", run, ch));
+        } else {
+            (0..run).for_each(|_| out.push(ch));
+        }
+        x += run;
+    }
+}
@@ -0,0 +1,197 @@
+use crate::game::{Game, DEFAULT_YARDS_PER_TILE};
+use crate::hole::HoleLayout;
+use crate::match_log::{MatchLog, MomentKind, BIG_PUTT_FT};
+use crate::settings::Settings;
+use crate::shadow::ShadowRival;
+
+pub const PLAYERS: usize = 2;
+// The shadow rival stands over each shot this long before hitting it.
+const SHADOW_THINK_SECS: f32 = 1.5;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SpeedResult {
+    Winner(usize),
+    Tie,
+}
+
+// Both players play the same layout at once, each with their own ball.
+pub struct SpeedRound {
+    pub players: [Game; PLAYERS],
+    pub result: Option<SpeedResult>,
+    // When set, player two is a bot that plays like player one's stats.
+    pub shadow: Option<ShadowRival>,
+    pub log: MatchLog,
+    // The moment picked on the match summary while it's open, and a player
+    // set up to show that moment's replay while one is on.
+    pub summary: Option<usize>,
+    pub viewer: Option<Game>,
+}
+
+impl SpeedRound {
+    pub fn new() -> Self {
+        Self::with_layout(HoleLayout::classic())
+    }
+
+    pub fn with_layout(layout: HoleLayout) -> Self {
+        Self {
+            players: [Game::with_layout(layout.clone()), Game::with_layout(layout)],
+            result: None,
+            shadow: None,
+            log: MatchLog::default(),
+            summary: None,
+            viewer: None,
+        }
+    }
+
+    pub fn rematch(&mut self) {
+        self.restart_with(self.players[0].layout.clone());
+    }
+
+    pub fn new_hole(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.restart_with(HoleLayout::random(&mut rng, DEFAULT_YARDS_PER_TILE));
+    }
+
+    fn restart_with(&mut self, layout: HoleLayout) {
+        let settings = self.players[0].settings;
+        let shadow = self.shadow.take();
+        let log = std::mem::take(&mut self.log);
+        *self = Self::with_layout(layout);
+        self.log = log;
+        self.apply_settings(settings);
+        if let Some(rival) = shadow {
+            self.set_shadow(rival);
+        }
+    }
+
+    pub fn names(&self) -> [&'static str; 2] {
+        if self.shadow.is_some() {
+            ["P1", "Shadow rival"]
+        } else {
+            ["P1", "P2"]
+        }
+    }
+
+    // The summary opens once a hole of the match has been decided.
+    pub fn toggle_summary(&mut self) {
+        self.summary = match self.summary {
+            None if self.result.is_some() => Some(self.log.moments.len().saturating_sub(1)),
+            _ => None,
+        };
+    }
+
+    pub fn move_summary(&mut self, step: isize) {
+        if let Some(selected) = self.summary.as_mut() {
+            let last = self.log.moments.len().saturating_sub(1) as isize;
+            *selected = (*selected as isize + step).clamp(0, last) as usize;
+        }
+    }
+
+    pub fn replay_selected(&mut self) {
+        let Some(moment) = self.summary.and_then(|i| self.log.moments.get(i)) else {
+            return;
+        };
+        let Some(replay) = moment.replay.clone() else {
+            return;
+        };
+        let mut viewer = Game::with_layout(moment.layout.clone());
+        viewer.settings = self.players[0].settings;
+        viewer.replay = Some(replay);
+        self.viewer = Some(viewer);
+    }
+
+    pub fn close_replay(&mut self) {
+        self.viewer = None;
+    }
+
+    pub fn set_shadow(&mut self, rival: ShadowRival) {
+        self.players[1].shadow = Some(rival.clone());
+        self.shadow = Some(rival);
+    }
+
+    pub fn apply_settings(&mut self, settings: Settings) {
+        // The race brings its own second player.
+        for player in self.players.iter_mut() {
+            player.settings = settings;
+            player.settings.ai = None;
+        }
+    }
+
+    // Input is only routed while the race is still on.
+    pub fn player_mut(&mut self, idx: usize) -> Option<&mut Game> {
+        if self.result.is_some() || (idx == 1 && self.shadow.is_some()) {
+            None
+        } else {
+            self.players.get_mut(idx)
+        }
+    }
+
+    pub fn update(&mut self, dt_secs: f32) {
+        if let Some(replay) = self.viewer.as_mut().and_then(|v| v.replay.as_mut()) {
+            replay.elapsed += dt_secs;
+            if replay.is_over() {
+                self.viewer = None;
+            }
+        }
+
+        let before = self
+            .players
+            .each_ref()
+            .map(|p| (p.hole_done, p.penalty_strokes));
+        for player in self.players.iter_mut() {
+            player.update(dt_secs);
+            if self.result.is_none() && player.afk_autoplay_due() {
+                player.autoplay();
+            }
+        }
+        let rival = &mut self.players[1];
+        if self.result.is_none()
+            && rival.shadow.is_some()
+            && rival.can_shoot()
+            && rival.idle_secs >= SHADOW_THINK_SECS
+        {
+            rival.autoplay();
+        }
+
+        self.log_moments(before);
+        if self.result.is_none() {
+            let done: Vec<usize> = (0..PLAYERS)
+                .filter(|&i| self.players[i].hole_done)
+                .collect();
+            self.result = match done.as_slice() {
+                [] => None,
+                [winner] => Some(SpeedResult::Winner(*winner)),
+                _ => Some(SpeedResult::Tie),
+            };
+            match self.result {
+                Some(SpeedResult::Winner(i)) => self.log_moment(i, MomentKind::HoleWon(i)),
+                Some(SpeedResult::Tie) => self.log_moment(0, MomentKind::Halved),
+                None => {}
+            }
+        }
+    }
+
+    // Penalties and long putts holed since the last tick.
+    fn log_moments(&mut self, before: [(bool, u32); PLAYERS]) {
+        for (i, (was_done, penalties)) in before.into_iter().enumerate() {
+            let player = &self.players[i];
+            let penalty = player.penalty_strokes > penalties;
+            let holed_putt_ft = player
+                .shot_history
+                .last()
+                .filter(|shot| shot.putter && player.hole_done && !was_done)
+                .map(|shot| shot.to_pin_yd * 3.0);
+            if penalty {
+                self.log_moment(i, MomentKind::Penalty(i));
+            }
+            if let Some(feet) = holed_putt_ft.filter(|&ft| ft >= BIG_PUTT_FT) {
+                self.log_moment(i, MomentKind::BigPutt { player: i, feet });
+            }
+        }
+    }
+
+    fn log_moment(&mut self, player: usize, kind: MomentKind) {
+        let game = &self.players[player];
+        self.log.record(kind, game.snapshot_replay(), &game.layout);
+    }
+}
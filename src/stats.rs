@@ -0,0 +1,254 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
+
+use crate::profile;
+use crate::storage::{self, Schema};
+
+pub const APPROACH_BUCKETS: [(f32, f32, &str); 5] = [
+    (0.0, 50.0, "0-50 yd"),
+    (50.0, 100.0, "50-100 yd"),
+    (100.0, 150.0, "100-150 yd"),
+    (150.0, 200.0, "150-200 yd"),
+    (200.0, 260.0, "200-260 yd"),
+];
+
+pub const PUTT_BUCKETS: [(f32, f32, &str); 5] = [
+    (0.0, 3.0, "0-3 ft"),
+    (3.0, 6.0, "3-6 ft"),
+    (6.0, 10.0, "6-10 ft"),
+    (10.0, 20.0, "10-20 ft"),
+    (20.0, f32::MAX, "20+ ft"),
+];
+
+// Like profiles, the first player's stats keep the file they had before
+// there were names; everyone else's sit beside their profile.
+const STATS_FILE: &str = "stats.txt";
+const STATS_EXTENSION: &str = "stats";
+const SCHEMA: Schema = Schema {
+    name: "hole-side stats",
+    migrations: &[storage::unversioned],
+};
+pub const RECENT_MISS_LIMIT: usize = 40;
+
+#[derive(Clone, Copy, Default)]
+pub struct ProximityBucket {
+    pub shots: u32,
+    pub total_ft: f32,
+}
+
+impl ProximityBucket {
+    pub fn average_ft(&self) -> Option<f32> {
+        (self.shots > 0).then(|| self.total_ft / self.shots as f32)
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct PuttBucket {
+    pub attempts: u32,
+    pub makes: u32,
+}
+
+impl PuttBucket {
+    pub fn make_rate(&self) -> Option<f32> {
+        (self.attempts > 0).then(|| self.makes as f32 / self.attempts as f32)
+    }
+}
+
+// Where an approach finished relative to the pin, measured along the line of
+// the shot (long is positive) and across it (right is positive).
+#[derive(Clone, Copy)]
+pub struct ApproachMiss {
+    pub long_ft: f32,
+    pub right_ft: f32,
+}
+
+impl ApproachMiss {
+    pub fn proximity_ft(&self) -> f32 {
+        (self.long_ft * self.long_ft + self.right_ft * self.right_ft).sqrt()
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct ChallengeRecord {
+    pub holes: u32,
+    pub total_to_par: i32,
+}
+
+impl ChallengeRecord {
+    pub fn average_to_par(&self) -> f32 {
+        self.total_to_par as f32 / self.holes.max(1) as f32
+    }
+}
+
+// How far a club carried against what it should have from that lie and
+// swing, summed so the average ratio can be read back.
+#[derive(Clone, Copy, Default)]
+pub struct CarryRecord {
+    pub shots: u32,
+    pub total_ratio: f32,
+}
+
+impl CarryRecord {
+    pub fn average_ratio(&self) -> Option<f32> {
+        (self.shots > 0).then(|| self.total_ratio / self.shots as f32)
+    }
+}
+
+// Approach proximity and putt make rates, kept across holes and saved to the
+// data directory when loaded from it.
+#[derive(Clone, Default)]
+pub struct HoleSideStats {
+    pub approaches: [ProximityBucket; APPROACH_BUCKETS.len()],
+    pub putts: [PuttBucket; PUTT_BUCKETS.len()],
+    pub recent_misses: VecDeque<ApproachMiss>,
+    // Finished holes under each challenge rule, keyed by `Challenge::key`.
+    pub challenges: BTreeMap<String, ChallengeRecord>,
+    // Keyed by the club's index in the bag.
+    pub carries: BTreeMap<usize, CarryRecord>,
+    path: Option<PathBuf>,
+}
+
+impl HoleSideStats {
+    pub fn load(profile_name: &str) -> Self {
+        let path = storage::data_dir().map(|dir| {
+            if profile_name == profile::DEFAULT_NAME {
+                dir.join(STATS_FILE)
+            } else {
+                dir.join(profile::PROFILES_DIR)
+                    .join(format!("{}.{}", profile_name, STATS_EXTENSION))
+            }
+        });
+        let mut stats = Self {
+            path: path.clone(),
+            ..Self::default()
+        };
+        if let Some(text) = path.and_then(|p| storage::read(&p, &SCHEMA).ok().flatten()) {
+            stats.parse(&text);
+        }
+        stats
+    }
+
+    fn parse(&mut self, text: &str) {
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["approach", idx, shots, total] => {
+                    if let (Ok(i), Ok(shots), Ok(total)) =
+                        (idx.parse::<usize>(), shots.parse(), total.parse())
+                    {
+                        if let Some(bucket) = self.approaches.get_mut(i) {
+                            *bucket = ProximityBucket {
+                                shots,
+                                total_ft: total,
+                            };
+                        }
+                    }
+                }
+                ["putt", idx, attempts, makes] => {
+                    if let (Ok(i), Ok(attempts), Ok(makes)) =
+                        (idx.parse::<usize>(), attempts.parse(), makes.parse())
+                    {
+                        if let Some(bucket) = self.putts.get_mut(i) {
+                            *bucket = PuttBucket { attempts, makes };
+                        }
+                    }
+                }
+                ["miss", long, right] => {
+                    if let (Ok(long_ft), Ok(right_ft)) = (long.parse(), right.parse()) {
+                        self.push_miss(ApproachMiss { long_ft, right_ft });
+                    }
+                }
+                ["challenge", key, holes, total] => {
+                    if let (Ok(holes), Ok(total_to_par)) = (holes.parse(), total.parse()) {
+                        self.challenges.insert(
+                            key.to_string(),
+                            ChallengeRecord {
+                                holes,
+                                total_to_par,
+                            },
+                        );
+                    }
+                }
+                ["carry", idx, shots, total] => {
+                    if let (Ok(idx), Ok(shots), Ok(total_ratio)) =
+                        (idx.parse(), shots.parse(), total.parse())
+                    {
+                        self.carries.insert(idx, CarryRecord { shots, total_ratio });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut text = String::new();
+        for (i, b) in self.approaches.iter().enumerate() {
+            text.push_str(&format!("approach {} {} {:.1}\n", i, b.shots, b.total_ft));
+        }
+        for (i, b) in self.putts.iter().enumerate() {
+            text.push_str(&format!("putt {} {} {}\n", i, b.attempts, b.makes));
+        }
+        for miss in &self.recent_misses {
+            text.push_str(&format!("miss {:.1} {:.1}\n", miss.long_ft, miss.right_ft));
+        }
+        for (key, record) in &self.challenges {
+            text.push_str(&format!(
+                "challenge {} {} {}\n",
+                key, record.holes, record.total_to_par
+            ));
+        }
+        for (idx, record) in &self.carries {
+            text.push_str(&format!(
+                "carry {} {} {:.3}\n",
+                idx, record.shots, record.total_ratio
+            ));
+        }
+        storage::write(path, &SCHEMA, &text)
+    }
+
+    pub fn record_approach(&mut self, from_yd: f32, miss: ApproachMiss) {
+        if let Some(i) = bucket_index(&APPROACH_BUCKETS, from_yd) {
+            self.approaches[i].shots += 1;
+            self.approaches[i].total_ft += miss.proximity_ft();
+            self.push_miss(miss);
+        }
+    }
+
+    pub fn record_challenge(&mut self, key: &str, to_par: i32) {
+        let record = self.challenges.entry(key.to_string()).or_default();
+        record.holes += 1;
+        record.total_to_par += to_par;
+    }
+
+    pub fn record_carry(&mut self, club_idx: usize, ratio: f32) {
+        let record = self.carries.entry(club_idx).or_default();
+        record.shots += 1;
+        record.total_ratio += ratio;
+    }
+
+    fn push_miss(&mut self, miss: ApproachMiss) {
+        if self.recent_misses.len() == RECENT_MISS_LIMIT {
+            self.recent_misses.pop_front();
+        }
+        self.recent_misses.push_back(miss);
+    }
+
+    pub fn record_putt(&mut self, length_ft: f32, made: bool) {
+        if let Some(i) = bucket_index(&PUTT_BUCKETS, length_ft) {
+            self.putts[i].attempts += 1;
+            if made {
+                self.putts[i].makes += 1;
+            }
+        }
+    }
+}
+
+pub fn bucket_index(buckets: &[(f32, f32, &str)], value: f32) -> Option<usize> {
+    buckets
+        .iter()
+        .position(|(min, max, _)| value >= *min && value < *max)
+}
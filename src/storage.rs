@@ -0,0 +1,279 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use directories::ProjectDirs;
+
+const APP_DIR: &str = "terminal_golf";
+
+// What went wrong with saved files this run, for the player once the game
+// is over.
+static NOTICES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// Set by `--data-dir` for a portable install, with everything kept under it.
+static ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+// Takes a file's text up one version, or says why it can't.
+pub type Migration = fn(&str) -> Result<String, String>;
+
+pub fn set_root(dir: PathBuf) {
+    let _ = ROOT.set(dir);
+}
+
+// The platform's own places: the XDG directories on Linux, Application
+// Support on macOS and the roaming AppData folder on Windows.
+fn project() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", APP_DIR)
+}
+
+// Profiles, stats, saved rounds, snapshots and installed courses.
+pub fn data_dir() -> Option<PathBuf> {
+    match ROOT.get() {
+        Some(root) => Some(root.join("data")),
+        None => project().map(|dirs| dirs.data_dir().to_path_buf()),
+    }
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    match ROOT.get() {
+        Some(root) => Some(root.join("config")),
+        None => project().map(|dirs| dirs.config_dir().to_path_buf()),
+    }
+}
+
+// Debug logs, which are nobody's data; in the state directory where there
+// is one.
+pub fn log_dir() -> Option<PathBuf> {
+    match ROOT.get() {
+        Some(root) => Some(root.join("logs")),
+        None => project().map(|dirs| {
+            dirs.state_dir()
+                .unwrap_or_else(|| dirs.data_local_dir())
+                .to_path_buf()
+        }),
+    }
+}
+
+// Where data was kept before the platform's directories were used. It's the
+// same place on Linux; elsewhere it was `$APPDATA` or `~/.local/share`.
+fn legacy_data_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_DATA_HOME") {
+        return Some(PathBuf::from(dir).join(APP_DIR));
+    }
+    if let Some(dir) = std::env::var_os("APPDATA") {
+        return Some(PathBuf::from(dir).join(APP_DIR));
+    }
+    std::env::var_os("HOME").map(|home| {
+        PathBuf::from(home)
+            .join(".local")
+            .join("share")
+            .join(APP_DIR)
+    })
+}
+
+// Makes the directories the game keeps things in, moving data over from
+// where an older release kept it first. True on the very first launch,
+// when there was nothing to find.
+pub fn prepare() -> std::io::Result<bool> {
+    let Some(data) = data_dir() else {
+        return Ok(false);
+    };
+    if ROOT.get().is_none() && !data.exists() {
+        if let Some(legacy) = legacy_data_dir().filter(|dir| dir.is_dir() && *dir != data) {
+            move_data(&legacy, &data)?;
+        }
+    }
+    let first = !data.exists();
+    for dir in [Some(data), config_dir(), log_dir()].into_iter().flatten() {
+        fs::create_dir_all(dir)?;
+    }
+    Ok(first)
+}
+
+// Everything in `from` goes into `to`, which can be inside it.
+fn move_data(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        if to.starts_with(entry.path()) {
+            continue;
+        }
+        fs::rename(entry.path(), to.join(entry.file_name()))?;
+    }
+    notify(format!(
+        "Moved your saved games from {} to {}.",
+        from.display(),
+        to.display()
+    ));
+    Ok(())
+}
+
+// A saved file's format and how to bring an older one up to date. Files
+// begin with a `# version N` line; ones saved before there were versions
+// are version 0. The migration at index `i` takes a file's text from
+// version `i` to `i + 1`, so the current version is how many there are.
+pub struct Schema {
+    // What the file is, as the player would call it.
+    pub name: &'static str,
+    pub migrations: &'static [Migration],
+}
+
+impl Schema {
+    pub fn version(&self) -> usize {
+        self.migrations.len()
+    }
+}
+
+// Version 1 only added the version line.
+pub fn unversioned(text: &str) -> Result<String, String> {
+    Ok(text.to_string())
+}
+
+// The text of a saved file brought up to the current version, or nothing if
+// there isn't one. A file that can't be read, is from a newer release or
+// won't migrate is set aside rather than left to be saved over, and the
+// error says where it went.
+pub fn read(path: &Path, schema: &Schema) -> Result<Option<String>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(set_aside(path, schema, &err.to_string())),
+    };
+    let (version, mut text) = match text.strip_prefix("# version ") {
+        Some(rest) => {
+            let (number, body) = rest.split_once('\n').unwrap_or((rest, ""));
+            let Ok(version) = number.trim().parse::<usize>() else {
+                return Err(set_aside(path, schema, "its version line is unreadable"));
+            };
+            (version, body.to_string())
+        }
+        None => (0, text),
+    };
+    if version > schema.version() {
+        let reason = format!("it was saved by a newer release (version {})", version);
+        return Err(set_aside(path, schema, &reason));
+    }
+    for migrate in &schema.migrations[version..] {
+        text = migrate(&text).map_err(|err| set_aside(path, schema, &err))?;
+    }
+    Ok(Some(text))
+}
+
+// Saves `text` at the current version. It goes to a scratch file first and
+// is renamed into place, so a crash part way leaves the old file whole.
+pub fn write(path: &Path, schema: &Schema, text: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let scratch = path.with_extension("tmp");
+    fs::write(
+        &scratch,
+        format!("# version {}\n{}", schema.version(), text),
+    )?;
+    fs::rename(scratch, path)
+}
+
+// Moves a file that couldn't be used out of the way, stamped with the time,
+// and tells the player where it went.
+pub fn set_aside(path: &Path, schema: &Schema, reason: &str) -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".{}.bak", secs));
+    let backup = PathBuf::from(backup);
+    let notice = match fs::rename(path, &backup) {
+        Ok(()) => format!(
+            "Couldn't read your {} at {}: {}. It's been kept as {} and a new one started.",
+            schema.name,
+            path.display(),
+            reason,
+            backup.display()
+        ),
+        Err(err) => format!(
+            "Couldn't read your {} at {}: {}, nor move it aside: {}.",
+            schema.name,
+            path.display(),
+            reason,
+            err
+        ),
+    };
+    notify(notice.clone());
+    notice
+}
+
+// Keeps something for the player to read once the game is over.
+pub fn notify(notice: String) {
+    if let Ok(mut notices) = NOTICES.lock() {
+        notices.push(notice);
+    }
+}
+
+// Everything noted so far, once.
+pub fn take_notices() -> Vec<String> {
+    NOTICES
+        .lock()
+        .map(|mut notices| std::mem::take(&mut *notices))
+        .unwrap_or_default()
+}
+
+// An empty directory of its own for a test to write in.
+#[cfg(test)]
+pub fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("{}_{}_{}", APP_DIR, std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Whatever was set aside from `path`, by its contents.
+#[cfg(test)]
+pub fn backups(path: &Path) -> Vec<String> {
+    let name = path.file_name().unwrap().to_str().unwrap();
+    fs::read_dir(path.parent().unwrap())
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| {
+            let file = p.file_name().unwrap().to_str().unwrap();
+            file.starts_with(&format!("{}.", name)) && file.ends_with(".bak")
+        })
+        .map(|p| fs::read_to_string(p).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: Schema = Schema {
+        name: "test file",
+        migrations: &[unversioned],
+    };
+
+    #[test]
+    fn files_are_read_back_at_the_current_version() {
+        let path = scratch_dir("current").join("file.txt");
+        write(&path, &SCHEMA, "hello\n").unwrap();
+        assert_eq!(read(&path, &SCHEMA), Ok(Some("hello\n".to_string())));
+        fs::write(&path, "hello\n").unwrap();
+        assert_eq!(read(&path, &SCHEMA), Ok(Some("hello\n".to_string())));
+        assert!(backups(&path).is_empty());
+    }
+
+    #[test]
+    fn unreadable_files_are_set_aside() {
+        for (case, text) in [
+            ("corrupt", "# version banana\nhello\n"),
+            ("newer", "# version 9\nhello\n"),
+        ] {
+            let path = scratch_dir(case).join("file.txt");
+            fs::write(&path, text).unwrap();
+            assert!(read(&path, &SCHEMA).is_err(), "{} file read", case);
+            assert!(!path.exists(), "{} file left in place", case);
+            assert_eq!(backups(&path), [text], "{} file not kept", case);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::game::{Surface, Vec2};
+use crate::hole::HoleLayout;
+
+// How far the contour banks around a green stand above the flat.
+const BANK_RISE_FT: f32 = 1.5;
+
+#[derive(Clone, Copy)]
+pub struct Tile {
+    pub surface: Surface,
+    // Feet above the flat of the hole.
+    pub elevation: f32,
+    // Downhill pull on a ball rolling across the tile.
+    pub slope: Vec2,
+    // Ground the caddie and the aim warning steer away from.
+    pub hazard: bool,
+}
+
+impl Tile {
+    fn of(surface: Surface) -> Self {
+        Self {
+            surface,
+            elevation: 0.0,
+            slope: Vec2::default(),
+            hazard: matches!(surface, Surface::Bunker | Surface::Water),
+        }
+    }
+}
+
+// The hole one tile per map cell. A layout's shapes are painted in when the
+// hole is set up, and any tile can be repainted after that.
+#[derive(Clone)]
+pub struct TerrainMap {
+    width: i32,
+    height: i32,
+    tiles: Vec<Tile>,
+}
+
+impl TerrainMap {
+    pub fn from_layout(layout: &HoleLayout) -> Self {
+        let (width, height) = (layout.width.max(0), layout.height.max(0));
+        let mut map = Self {
+            width,
+            height,
+            tiles: vec![Tile::of(Surface::Rough); (width * height) as usize],
+        };
+        for y in 0..height {
+            for x in 0..width {
+                map.paint(x, y, layout.surface(x, y));
+                let (xf, yf) = (x as f32 + 0.5, y as f32 + 0.5);
+                if let Some(i) = map.index(x, y) {
+                    map.tiles[i].slope = layout.green_slope(xf, yf);
+                    if layout.bank_slope(xf, yf).length() > 0.0 {
+                        map.tiles[i].elevation = BANK_RISE_FT;
+                    }
+                }
+            }
+        }
+        map
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let inside = x >= 0 && y >= 0 && x < self.width && y < self.height;
+        inside.then(|| (y * self.width + x) as usize)
+    }
+
+    // Anything off the map reads as flat rough.
+    pub fn tile(&self, x: i32, y: i32) -> Tile {
+        self.index(x, y)
+            .map_or(Tile::of(Surface::Rough), |i| self.tiles[i])
+    }
+
+    pub fn surface(&self, x: i32, y: i32) -> Surface {
+        self.tile(x, y).surface
+    }
+
+    // Keeps the tile's lie of the land; the hazard flag follows the new
+    // surface.
+    pub fn paint(&mut self, x: i32, y: i32, surface: Surface) {
+        if let Some(i) = self.index(x, y) {
+            let Tile {
+                elevation, slope, ..
+            } = self.tiles[i];
+            self.tiles[i] = Tile {
+                elevation,
+                slope,
+                ..Tile::of(surface)
+            };
+        }
+    }
+}
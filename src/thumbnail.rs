@@ -0,0 +1,61 @@
+use crate::game::{Surface, Vec2};
+use crate::hole::HoleLayout;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Pixel {
+    Ground(Surface),
+    Tee,
+    Pin,
+}
+
+// Shrinks a whole layout into `width` x `height` cells without touching the
+// screen. Each cell holds two pixels stacked top and bottom so it can be
+// drawn as one half block.
+pub fn thumbnail(layout: &HoleLayout, width: usize, height: usize) -> Vec<Vec<(Pixel, Pixel)>> {
+    let rows = height * 2;
+    let scale_x = layout.width as f32 / width as f32;
+    let scale_y = layout.height as f32 / rows as f32;
+    let marker = |x: usize, y: usize| {
+        let at = |p: Vec2| (p.x / scale_x) as usize == x && (p.y / scale_y) as usize == y;
+        if at(layout.pin) {
+            Some(Pixel::Pin)
+        } else if at(layout.tee) {
+            Some(Pixel::Tee)
+        } else {
+            None
+        }
+    };
+    // Small features would vanish if each pixel only sampled its centre,
+    // so the pixel shows the most telling surface anywhere under it.
+    let pixel = |x: usize, y: usize| {
+        marker(x, y).unwrap_or_else(|| {
+            let xs = (x as f32 * scale_x) as i32..((x + 1) as f32 * scale_x).ceil() as i32;
+            let ys = (y as f32 * scale_y) as i32..((y + 1) as f32 * scale_y).ceil() as i32;
+            let surface = xs
+                .flat_map(|wx| ys.clone().map(move |wy| layout.surface(wx, wy)))
+                .max_by_key(|s| priority(*s))
+                .unwrap_or(Surface::Rough);
+            Pixel::Ground(surface)
+        })
+    };
+
+    (0..height)
+        .map(|row| {
+            (0..width)
+                .map(|x| (pixel(x, row * 2), pixel(x, row * 2 + 1)))
+                .collect()
+        })
+        .collect()
+}
+
+fn priority(surface: Surface) -> u8 {
+    match surface {
+        Surface::HeavyRough => 0,
+        Surface::Rough => 1,
+        Surface::Fairway => 2,
+        Surface::Fringe => 3,
+        Surface::Bunker => 4,
+        Surface::Water => 5,
+        Surface::Green => 6,
+    }
+}
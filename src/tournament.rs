@@ -0,0 +1,235 @@
+use std::path::PathBuf;
+
+use rand::Rng;
+
+use crate::storage::{self, Schema};
+
+const TOURNAMENT_FILE: &str = "tournament.txt";
+const SCHEMA: Schema = Schema {
+    name: "tournament",
+    migrations: &[storage::unversioned],
+};
+pub const TOURNAMENT_ROUNDS: usize = 4;
+// After this many rounds the field is cut to the leading `CUT_PLACES` and
+// anyone tied with the last of them.
+pub const CUT_AFTER: usize = 2;
+const CUT_PLACES: usize = 8;
+const FIELD: [&str; 15] = [
+    "Ashby",
+    "Brennan",
+    "Castillo",
+    "Dunmore",
+    "Eriksen",
+    "Fairley",
+    "Garza",
+    "Holt",
+    "Ito",
+    "Jaramillo",
+    "Kowalski",
+    "Lindqvist",
+    "Moreau",
+    "Nakamura",
+    "Okafor",
+];
+const PLAYER: &str = "You";
+// The field's golfers average between these to par over eighteen holes, and
+// a round strays either side of that by up to `ROUND_SPREAD`.
+const FIELD_BEST: f32 = -3.0;
+const FIELD_WORST: f32 = 5.0;
+const ROUND_SPREAD: f32 = 5.0;
+
+#[derive(Clone)]
+pub struct Entrant {
+    pub name: String,
+    // Expected score to par over eighteen holes.
+    form: f32,
+    // Score to par for each round played.
+    pub rounds: Vec<i32>,
+    pub missed_cut: bool,
+}
+
+impl Entrant {
+    pub fn total(&self) -> i32 {
+        self.rounds.iter().sum()
+    }
+
+    pub fn is_player(&self) -> bool {
+        self.name == PLAYER
+    }
+}
+
+// Four rounds of one course against a field of computer golfers, with a cut
+// at halfway. The standings are saved after every round, so a tournament
+// can be picked up again the next time the course is played.
+pub struct Tournament {
+    pub course: String,
+    // The player first, then the field.
+    pub entrants: Vec<Entrant>,
+    path: Option<PathBuf>,
+}
+
+impl Tournament {
+    pub fn new(course: &str) -> Self {
+        let mut rng = rand::thread_rng();
+        let field = FIELD.iter().map(|name| Entrant {
+            name: name.to_string(),
+            form: rng.gen_range(FIELD_BEST..=FIELD_WORST),
+            rounds: Vec::new(),
+            missed_cut: false,
+        });
+        let player = Entrant {
+            name: PLAYER.to_string(),
+            form: 0.0,
+            rounds: Vec::new(),
+            missed_cut: false,
+        };
+        Self {
+            course: course.to_string(),
+            entrants: std::iter::once(player).chain(field).collect(),
+            path: storage::data_dir().map(|dir| dir.join(TOURNAMENT_FILE)),
+        }
+    }
+
+    // The saved tournament on this course if it's still going, or a fresh one.
+    pub fn load(course: &str) -> Self {
+        let mut tournament = Self::new(course);
+        let Some(path) = tournament.path.clone() else {
+            return tournament;
+        };
+        let Ok(Some(text)) = storage::read(&path, &SCHEMA) else {
+            return tournament;
+        };
+        match Self::parse(&text) {
+            Some(saved) => {
+                if saved.course == course && !saved.is_over() {
+                    tournament.entrants = saved.entrants;
+                }
+            }
+            None => {
+                storage::set_aside(&path, &SCHEMA, "its standings don't add up");
+            }
+        }
+        tournament
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut course = None;
+        let mut entrants = Vec::new();
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["course", name @ ..] => course = Some(name.join(" ")),
+                ["entrant", name, form, missed_cut, rounds @ ..] => entrants.push(Entrant {
+                    name: name.to_string(),
+                    form: form.parse().ok()?,
+                    rounds: rounds
+                        .iter()
+                        .map(|r| r.parse())
+                        .collect::<Result<_, _>>()
+                        .ok()?,
+                    missed_cut: *missed_cut == "1",
+                }),
+                _ => {}
+            }
+        }
+        entrants.first().filter(|e| e.is_player())?;
+        Some(Self {
+            course: course?,
+            entrants,
+            path: None,
+        })
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut text = format!("course {}\n", self.course);
+        for entrant in &self.entrants {
+            text.push_str(&format!(
+                "entrant {} {:.2} {}",
+                entrant.name, entrant.form, entrant.missed_cut as u8
+            ));
+            for round in &entrant.rounds {
+                text.push_str(&format!(" {}", round));
+            }
+            text.push('\n');
+        }
+        storage::write(path, &SCHEMA, &text)
+    }
+
+    pub fn player(&self) -> &Entrant {
+        &self.entrants[0]
+    }
+
+    pub fn rounds_played(&self) -> usize {
+        self.player().rounds.len()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.rounds_played() >= TOURNAMENT_ROUNDS || self.player().missed_cut
+    }
+
+    // The player's round goes on the board with a round from everyone
+    // still in the field, and the cut is made once it's due.
+    pub fn record_round(&mut self, to_par: i32, holes: usize) {
+        let mut rng = rand::thread_rng();
+        let share = holes as f32 / 18.0;
+        for entrant in self.entrants.iter_mut().filter(|e| !e.missed_cut) {
+            let score = if entrant.is_player() {
+                to_par
+            } else {
+                // Three draws pile up in the middle like real scores do.
+                let spread: f32 = (0..3).map(|_| rng.gen_range(-1.0..=1.0)).sum::<f32>() / 3.0;
+                ((entrant.form + spread * ROUND_SPREAD) * share).round() as i32
+            };
+            entrant.rounds.push(score);
+        }
+        if self.rounds_played() == CUT_AFTER {
+            self.make_cut();
+        }
+    }
+
+    fn make_cut(&mut self) {
+        let mut totals: Vec<i32> = self.entrants.iter().map(Entrant::total).collect();
+        totals.sort();
+        let Some(&line) = totals.get(CUT_PLACES - 1) else {
+            return;
+        };
+        for entrant in self.entrants.iter_mut() {
+            entrant.missed_cut = entrant.total() > line;
+        }
+    }
+
+    // Those still playing lead, best total first, then those cut. Ties share
+    // a place, shown as "T3".
+    pub fn leaderboard(&self) -> Vec<(String, &Entrant)> {
+        let mut board: Vec<&Entrant> = self.entrants.iter().collect();
+        board.sort_by_key(|e| (e.missed_cut, e.total()));
+        board
+            .iter()
+            .map(|entrant| {
+                if entrant.missed_cut {
+                    return ("MC".to_string(), *entrant);
+                }
+                let rivals = board.iter().filter(|e| !e.missed_cut);
+                let ahead = rivals
+                    .clone()
+                    .filter(|e| e.total() < entrant.total())
+                    .count();
+                let tied = rivals.filter(|e| e.total() == entrant.total()).count() > 1;
+                let place = format!("{}{}", if tied { "T" } else { "" }, ahead + 1);
+                (place, *entrant)
+            })
+            .collect()
+    }
+
+    // The player's place on the leaderboard, as shown there.
+    pub fn player_place(&self) -> String {
+        self.leaderboard()
+            .into_iter()
+            .find(|(_, e)| e.is_player())
+            .map(|(place, _)| place)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,35 @@
+// The controls a new player is walked through, in order. Each step is done
+// once the player has tried it, or anything after it; the putt is done
+// when the first hole is holed.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TutorialStep {
+    Aim,
+    Club,
+    Swing,
+    Putt,
+}
+
+impl TutorialStep {
+    pub fn next(self) -> Option<Self> {
+        match self {
+            TutorialStep::Aim => Some(TutorialStep::Club),
+            TutorialStep::Club => Some(TutorialStep::Swing),
+            TutorialStep::Swing => Some(TutorialStep::Putt),
+            TutorialStep::Putt => None,
+        }
+    }
+
+    // What to try next, for where the ball lies and how putts are struck.
+    pub fn hint(self, on_green: bool, pull_back_putting: bool) -> &'static str {
+        match self {
+            TutorialStep::Aim => "Tutorial: A and D (or Left/Right) turn your aim",
+            TutorialStep::Club => "Tutorial: W and S (or Up/Down) change club",
+            TutorialStep::Swing => "Tutorial: Space swings; Play shows how far it goes",
+            TutorialStep::Putt if !on_green => "Tutorial: play on to the green; H shows every key",
+            TutorialStep::Putt if pull_back_putting => {
+                "Tutorial: hold Space to draw the putter back, let go to putt"
+            }
+            TutorialStep::Putt => "Tutorial: the putter's in hand; aim at the cup and press Space",
+        }
+    }
+}
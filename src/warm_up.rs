@@ -0,0 +1,107 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::game::DEFAULT_YARDS_PER_TILE;
+use crate::hole::HoleLayout;
+use crate::stats::ApproachMiss;
+
+pub const WARM_UP_BALLS: u32 = 6;
+const RANGE_YD: f32 = 150.0;
+const RANGE_SEED: u64 = 1;
+// How far the day's form can stretch or tighten every club's dispersion.
+const FORM_SWING: f32 = 0.5;
+const FORM_LIMITS: (f32, f32) = (0.9, 1.1);
+
+// A flat par 3 with a flag at range distance; the same one every time.
+pub fn range_layout() -> HoleLayout {
+    HoleLayout::generate(
+        &mut StdRng::seed_from_u64(RANGE_SEED),
+        RANGE_YD,
+        Some(3),
+        DEFAULT_YARDS_PER_TILE,
+    )
+}
+
+// A few balls hit before a course round. Each ball sends the ball back to the
+// mat once its result has been shown.
+pub struct WarmUp {
+    pub balls_hit: u32,
+    pub ball_out: bool,
+    strikes: Vec<f32>,
+    // Where each ball finished against the flag, for the dispersion plot.
+    pub misses: Vec<ApproachMiss>,
+}
+
+impl WarmUp {
+    pub fn new() -> Self {
+        Self {
+            balls_hit: 0,
+            ball_out: false,
+            strikes: Vec::new(),
+            misses: Vec::new(),
+        }
+    }
+
+    // `roll` is the dispersion draw for the strike, as in `StrikeQuality`.
+    pub fn record_strike(&mut self, roll: f32) {
+        self.strikes.push(roll);
+    }
+
+    pub fn finish_ball(&mut self, miss: Option<ApproachMiss>) {
+        self.misses.extend(miss);
+        self.balls_hit += 1;
+        self.ball_out = true;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.balls_hit >= WARM_UP_BALLS
+    }
+
+    // Centred strikes tighten the day's dispersion a little and wild ones
+    // loosen it. A uniform draw averages 0.5 off centre, which plays neutral.
+    pub fn form(&self) -> DayForm {
+        if self.strikes.is_empty() {
+            return DayForm::default();
+        }
+        let average = self.strikes.iter().map(|r| r.abs()).sum::<f32>() / self.strikes.len() as f32;
+        let (tightest, loosest) = FORM_LIMITS;
+        DayForm {
+            dispersion_mult: (1.0 + (average - 0.5) * FORM_SWING).clamp(tightest, loosest),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct DayForm {
+    pub dispersion_mult: f32,
+}
+
+impl Default for DayForm {
+    fn default() -> Self {
+        Self {
+            dispersion_mult: 1.0,
+        }
+    }
+}
+
+impl DayForm {
+    pub fn label(self) -> &'static str {
+        if self.dispersion_mult <= 0.95 {
+            "Striping it"
+        } else if self.dispersion_mult >= 1.05 {
+            "A bit loose"
+        } else {
+            "Steady"
+        }
+    }
+
+    pub fn verdict(self) -> &'static str {
+        if self.dispersion_mult <= 0.95 {
+            "You're striping it today"
+        } else if self.dispersion_mult >= 1.05 {
+            "Swing's a bit loose today, play to the fat side"
+        } else {
+            "Swing feels about normal today"
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::VecDeque;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::conditions::ConditionPreset;
+use crate::game::Vec2;
+
+// The HUD sparkline covers the last half minute, a reading every two seconds.
+const HISTORY_SECS: f32 = 30.0;
+const READING_SECS: f32 = 2.0;
+// Gusts build and die away over about this long, and a new one (or a lull)
+// comes through every few seconds.
+const GUST_EASE_SECS: f32 = 1.5;
+const GUST_SECS: (f32, f32) = (2.0, 6.0);
+const LULL_CHANCE: f64 = 0.35;
+// How far a gust can swing the wind off its usual line.
+const GUST_VEER_RAD: f32 = 0.3;
+// A shot loses more carry into the wind than it gains with it.
+const HEADWIND_MULT: f32 = 1.3;
+// Tiles of push per unit of wind for every tile of carry.
+const CARRY_PUSH: f32 = 0.08;
+
+// A steady wind from one direction with gusts on top that build, veer and
+// die away as play goes on. Strength is in the game's wind units, a twelfth
+// of a mile an hour.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Wind {
+    // Radians on the map the wind blows toward.
+    pub heading: f32,
+    pub base: f32,
+    gust: f32,
+    veer: f32,
+    gust_target: f32,
+    veer_target: f32,
+    gust_timer: f32,
+}
+
+impl Wind {
+    pub fn new(heading: f32, base: f32) -> Self {
+        Self {
+            heading,
+            base,
+            ..Self::default()
+        }
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.base + self.gust
+    }
+
+    pub fn mph(&self) -> f32 {
+        self.speed() * 12.0
+    }
+
+    pub fn vector(&self) -> Vec2 {
+        let heading = self.heading + self.veer;
+        Vec2::new(heading.cos() * self.speed(), heading.sin() * self.speed())
+    }
+
+    // Gusts ease toward whatever is coming through; the next one is drawn
+    // when the last has had its time.
+    pub fn update<R: Rng>(&mut self, dt_secs: f32, conditions: ConditionPreset, rng: &mut R) {
+        self.gust_timer -= dt_secs;
+        if self.gust_timer <= 0.0 {
+            self.gust_timer = rng.gen_range(GUST_SECS.0..GUST_SECS.1);
+            let peak = self.base * conditions.gustiness();
+            let lull = rng.gen_bool(LULL_CHANCE) || peak <= 0.0;
+            self.gust_target = if lull { 0.0 } else { rng.gen_range(0.0..peak) };
+            self.veer_target = rng.gen_range(-1.0..=1.0) * GUST_VEER_RAD * conditions.gustiness();
+        }
+        let ease = (dt_secs / GUST_EASE_SECS).min(1.0);
+        self.gust += (self.gust_target - self.gust) * ease;
+        self.veer += (self.veer_target - self.veer) * ease;
+    }
+
+    // Between shots the steady wind itself picks up, drops and swings a little.
+    pub fn wander<R: Rng>(&mut self, conditions: ConditionPreset, rng: &mut R) {
+        let drift = conditions.wind_drift();
+        self.base = (self.base + rng.gen_range(-drift..drift)).clamp(0.0, conditions.max_wind());
+        self.heading += rng.gen_range(-drift..drift) * 2.0;
+    }
+}
+
+// Where the wind has moved a shot by the time it lands: across the line as
+// drift, and along it as carry gained or lost.
+pub fn carry_push(wind: Vec2, dir: Vec2, carry_tiles: f32) -> Vec2 {
+    let along = wind.x * dir.x + wind.y * dir.y;
+    let across = wind.y * dir.x - wind.x * dir.y;
+    let along = if along < 0.0 {
+        along * HEADWIND_MULT
+    } else {
+        along
+    };
+    let scale = carry_tiles * CARRY_PUSH;
+    Vec2::new(
+        (dir.x * along - dir.y * across) * scale,
+        (dir.y * along + dir.x * across) * scale,
+    )
+}
+
+// Wind readings over recent play time, oldest first.
+#[derive(Default)]
+pub struct WindHistory {
+    readings: VecDeque<f32>,
+    clock: f32,
+}
+
+impl WindHistory {
+    pub fn record(&mut self, wind: &Wind, dt_secs: f32) {
+        self.clock += dt_secs;
+        if !self.readings.is_empty() && self.clock < READING_SECS {
+            return;
+        }
+        self.clock = 0.0;
+        self.readings.push_back(wind.speed());
+        while self.readings.len() > (HISTORY_SECS / READING_SECS) as usize {
+            self.readings.pop_front();
+        }
+    }
+
+    pub fn speeds_mph(&self) -> Vec<f32> {
+        self.readings.iter().map(|w| w * 12.0).collect()
+    }
+}